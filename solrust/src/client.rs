@@ -1,2 +1,21 @@
+pub mod api;
+pub mod auth;
+pub mod circuit_breaker;
+pub mod cloud;
+pub mod config;
 pub mod core;
+pub mod lb;
+pub mod logging;
+pub mod metrics;
+pub mod middleware;
+pub mod observer;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod reindex;
+pub mod retry;
+pub mod routing;
 pub mod solr;
+#[cfg(test)]
+mod testing;
+pub mod transport;
+pub mod updater;
@@ -0,0 +1,40 @@
+//! This module defines the version of the Solr API to which the requests are sent.
+
+/// Version of the Solr API.
+///
+/// The V1 API is the classic API under `/solr/...`. The [V2 API](https://solr.apache.org/guide/solr/latest/configuration-guide/v2-framework.html)
+/// under `/api/...` is the successor of it, to which the admin APIs are being migrated in the newer versions of Solr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApiVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+/// Return the URL of the context root under which `/solr` and `/api` are served,
+/// e.g. `https://example.com/search-api` of `https://example.com/search-api/solr/`.
+pub(crate) fn context_root(url: &str) -> &str {
+    let url = url.trim_end_matches('/');
+    url.strip_suffix("/solr").unwrap_or(url)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_context_root() {
+        assert_eq!(
+            context_root("http://localhost:8983/solr"),
+            "http://localhost:8983"
+        );
+        assert_eq!(
+            context_root("https://example.com/search-api/solr/"),
+            "https://example.com/search-api"
+        );
+        assert_eq!(
+            context_root("http://localhost:8983"),
+            "http://localhost:8983"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! This module defines the authentication methods used to request to Solr.
+
+use reqwest::RequestBuilder;
+use std::error::Error as StdError;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Error type returned by the token providers.
+pub type TokenError = Box<dyn StdError + Send + Sync>;
+
+/// Future returned by the token providers.
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<String, TokenError>> + Send + 'a>>;
+
+/// The trait of the provider of the token used for [JWT Authentication Plugin](https://solr.apache.org/guide/solr/latest/deployment-guide/jwt-authentication-plugin.html).
+pub trait TokenProvider: Send + Sync {
+    /// Return the token attached to the request.
+    fn token(&self) -> TokenFuture<'_>;
+    /// Fetch a new token after Solr rejected the current one with `401 Unauthorized`.
+    ///
+    /// Return `None` if the token can not be refreshed, in which case the request is not retried.
+    fn refresh(&self) -> Option<TokenFuture<'_>> {
+        None
+    }
+}
+
+/// Token provider that always returns the same token.
+pub struct StaticTokenProvider {
+    token: String,
+}
+
+impl StaticTokenProvider {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: String::from(token),
+        }
+    }
+}
+
+impl TokenProvider for StaticTokenProvider {
+    fn token(&self) -> TokenFuture<'_> {
+        let token = self.token.clone();
+        Box::pin(async move { Ok(token) })
+    }
+}
+
+/// Token provider that fetches the token with the given callback and caches it until Solr rejects it.
+pub struct RefreshingTokenProvider<F> {
+    fetch: F,
+    cache: Mutex<Option<String>>,
+}
+
+impl<F, Fut> RefreshingTokenProvider<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String, TokenError>> + Send + 'static,
+{
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            cache: Mutex::new(None),
+        }
+    }
+
+    async fn fetch(&self) -> Result<String, TokenError> {
+        let token = (self.fetch)().await?;
+        *self.cache.lock().unwrap() = Some(token.clone());
+        Ok(token)
+    }
+}
+
+impl<F, Fut> TokenProvider for RefreshingTokenProvider<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<String, TokenError>> + Send + 'static,
+{
+    fn token(&self) -> TokenFuture<'_> {
+        Box::pin(async move {
+            let cached = self.cache.lock().unwrap().clone();
+            match cached {
+                Some(token) => Ok(token),
+                None => self.fetch().await,
+            }
+        })
+    }
+
+    fn refresh(&self) -> Option<TokenFuture<'_>> {
+        Some(Box::pin(self.fetch()))
+    }
+}
+
+/// Authentication method attached to every request to Solr.
+#[derive(Clone)]
+pub enum SolrAuth {
+    /// Credentials for [Basic Authentication Plugin](https://solr.apache.org/guide/solr/latest/deployment-guide/basic-authentication-plugin.html).
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// Token for [JWT Authentication Plugin](https://solr.apache.org/guide/solr/latest/deployment-guide/jwt-authentication-plugin.html),
+    /// which is sent as `Authorization: Bearer <TOKEN>` header.
+    Bearer(Arc<dyn TokenProvider>),
+}
+
+impl SolrAuth {
+    /// Create credentials for Basic Authentication.
+    pub fn basic(username: &str, password: Option<&str>) -> Self {
+        SolrAuth::Basic {
+            username: String::from(username),
+            password: password.map(String::from),
+        }
+    }
+
+    /// Create Bearer Authentication with a static token.
+    pub fn bearer(token: &str) -> Self {
+        SolrAuth::Bearer(Arc::new(StaticTokenProvider::new(token)))
+    }
+
+    /// Create Bearer Authentication with a token fetched by the given callback.
+    ///
+    /// The callback is called on the first request, and again whenever Solr responds with `401 Unauthorized`.
+    pub fn bearer_with<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, TokenError>> + Send + 'static,
+    {
+        SolrAuth::Bearer(Arc::new(RefreshingTokenProvider::new(fetch)))
+    }
+
+    /// Attach the credentials to the request.
+    pub(crate) async fn apply(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<RequestBuilder, TokenError> {
+        match self {
+            SolrAuth::Basic { username, password } => {
+                Ok(request.basic_auth(username, password.as_ref()))
+            }
+            SolrAuth::Bearer(provider) => {
+                let token = provider.token().await?;
+                Ok(request.bearer_auth(token))
+            }
+        }
+    }
+
+    /// Refresh the credentials. Return false if the credentials can not be refreshed.
+    pub(crate) async fn refresh(&self) -> Result<bool, TokenError> {
+        match self {
+            SolrAuth::Basic { .. } => Ok(false),
+            SolrAuth::Bearer(provider) => match provider.refresh() {
+                Some(refresh) => {
+                    refresh.await?;
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+        }
+    }
+}
+
+/// Implementation of Debug that does not expose the secrets.
+impl Debug for SolrAuth {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SolrAuth::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &"<redacted>")
+                .finish(),
+            SolrAuth::Bearer(_) => f.debug_tuple("Bearer").field(&"<redacted>").finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_basic_auth_header() {
+        let auth = SolrAuth::basic("solr", Some("SolrRocks"));
+        let request = auth
+            .apply(reqwest::Client::new().get("http://localhost:8983"))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.headers()["authorization"],
+            "Basic c29scjpTb2xyUm9ja3M="
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_header() {
+        let auth = SolrAuth::bearer("token");
+        let request = auth
+            .apply(reqwest::Client::new().get("http://localhost:8983"))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(request.headers()["authorization"], "Bearer token");
+    }
+
+    #[tokio::test]
+    async fn test_refreshing_token_provider() {
+        let count = Arc::new(AtomicU32::new(0));
+        let provider = RefreshingTokenProvider::new({
+            let count = count.clone();
+            move || {
+                let count = count.clone();
+                async move {
+                    let n = count.fetch_add(1, Ordering::SeqCst);
+                    Ok(format!("token{}", n))
+                }
+            }
+        });
+
+        assert_eq!(provider.token().await.unwrap(), "token0".to_string());
+        // The token is cached until it is refreshed.
+        assert_eq!(provider.token().await.unwrap(), "token0".to_string());
+        assert_eq!(provider.refresh().unwrap().await.unwrap(), "token1");
+        assert_eq!(provider.token().await.unwrap(), "token1".to_string());
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_debug_does_not_expose_secrets() {
+        let auth = SolrAuth::basic("solr", Some("SolrRocks"));
+        assert!(!format!("{:?}", auth).contains("SolrRocks"));
+
+        let auth = SolrAuth::bearer("secret-token");
+        assert!(!format!("{:?}", auth).contains("secret-token"));
+    }
+}
@@ -0,0 +1,212 @@
+//! This module defines the circuit breaker that stops sending requests to the failing Solr nodes.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// State of the circuit of a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent to the node.
+    Closed,
+    /// Requests to the node are rejected without being sent.
+    Open,
+    /// A single probe request is allowed to check whether the node has recovered.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct NodeCircuit {
+    state: CircuitState,
+    failures: u32,
+    opened_at: Instant,
+    probing: bool,
+}
+
+impl NodeCircuit {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            failures: 0,
+            opened_at: Instant::now(),
+            probing: false,
+        }
+    }
+}
+
+/// Circuit breaker that tracks the failures of each node separately.
+///
+/// After `failure_threshold` consecutive failures, the circuit of the node is opened and the requests to it fail immediately.
+/// When `open_duration` has elapsed, one probe request is sent, and the circuit is closed if it succeeds or opened again if it fails.
+///
+/// Connection errors, timeouts and `5xx` responses are counted as failures.
+/// The circuit breaker is cheap to clone, and the clones share the state of the nodes.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    nodes: Arc<Mutex<HashMap<String, NodeCircuit>>>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreaker {
+    /// Create the circuit breaker that opens after 5 consecutive failures and probes the node after 30 seconds.
+    pub fn new() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+            nodes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Set the number of consecutive failures to open the circuit.
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+
+    /// Set the duration to keep the circuit open before probing the node.
+    pub fn open_duration(mut self, duration: Duration) -> Self {
+        self.open_duration = duration;
+        self
+    }
+
+    /// Return the state of the circuit of the node, e.g. `http://localhost:8983`.
+    pub fn state(&self, node: &str) -> CircuitState {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(node) {
+            Some(circuit) if circuit.state == CircuitState::Open => {
+                if circuit.opened_at.elapsed() >= self.open_duration {
+                    CircuitState::HalfOpen
+                } else {
+                    CircuitState::Open
+                }
+            }
+            Some(circuit) => circuit.state,
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Return true if the request to the node is allowed.
+    ///
+    /// When the request is allowed as the probe, the caller must report the result with
+    /// [`record_success`](Self::record_success), [`record_failure`](Self::record_failure) or [`release`](Self::release).
+    pub(crate) fn try_acquire(&self, node: &str) -> bool {
+        let mut nodes = self.nodes.lock().unwrap();
+        let circuit = nodes
+            .entry(String::from(node))
+            .or_insert_with(NodeCircuit::new);
+
+        match circuit.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                if circuit.opened_at.elapsed() >= self.open_duration {
+                    circuit.state = CircuitState::HalfOpen;
+                    circuit.probing = true;
+                    true
+                } else {
+                    false
+                }
+            }
+            CircuitState::HalfOpen => {
+                if circuit.probing {
+                    false
+                } else {
+                    circuit.probing = true;
+                    true
+                }
+            }
+        }
+    }
+
+    /// Record the successful request to the node, which closes the circuit.
+    pub(crate) fn record_success(&self, node: &str) {
+        let mut nodes = self.nodes.lock().unwrap();
+        if let Some(circuit) = nodes.get_mut(node) {
+            *circuit = NodeCircuit::new();
+        }
+    }
+
+    /// Record the failed request to the node, which opens the circuit after the consecutive failures or the failed probe.
+    pub(crate) fn record_failure(&self, node: &str) {
+        let mut nodes = self.nodes.lock().unwrap();
+        let circuit = nodes
+            .entry(String::from(node))
+            .or_insert_with(NodeCircuit::new);
+
+        circuit.failures += 1;
+        circuit.probing = false;
+        if circuit.state == CircuitState::HalfOpen || circuit.failures >= self.failure_threshold {
+            circuit.state = CircuitState::Open;
+            circuit.opened_at = Instant::now();
+        }
+    }
+
+    /// Release the probe of the node without the result, e.g. when the request was not sent.
+    pub(crate) fn release(&self, node: &str) {
+        let mut nodes = self.nodes.lock().unwrap();
+        if let Some(circuit) = nodes.get_mut(node) {
+            circuit.probing = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const NODE: &str = "http://localhost:8983";
+
+    #[test]
+    fn test_open_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new().failure_threshold(2);
+
+        assert!(breaker.try_acquire(NODE));
+        breaker.record_failure(NODE);
+        assert_eq!(breaker.state(NODE), CircuitState::Closed);
+
+        assert!(breaker.try_acquire(NODE));
+        breaker.record_failure(NODE);
+        assert_eq!(breaker.state(NODE), CircuitState::Open);
+        assert!(!breaker.try_acquire(NODE));
+
+        // The other nodes are not affected.
+        assert!(breaker.try_acquire("http://localhost:8984"));
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = CircuitBreaker::new().failure_threshold(2);
+
+        breaker.record_failure(NODE);
+        breaker.record_success(NODE);
+        breaker.record_failure(NODE);
+
+        assert_eq!(breaker.state(NODE), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_probe_after_open_duration() {
+        let breaker = CircuitBreaker::new()
+            .failure_threshold(1)
+            .open_duration(Duration::ZERO);
+
+        breaker.record_failure(NODE);
+        assert_eq!(breaker.state(NODE), CircuitState::HalfOpen);
+
+        // Only one probe is allowed at a time.
+        assert!(breaker.try_acquire(NODE));
+        assert!(!breaker.try_acquire(NODE));
+
+        // The failed probe opens the circuit again.
+        breaker.record_failure(NODE);
+        assert!(breaker.try_acquire(NODE));
+        breaker.record_success(NODE);
+        assert_eq!(breaker.state(NODE), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,937 @@
+//! This module defines the SolrCloudClient struct.
+//!
+//! SolrCloudClient operates the collections of SolrCloud through the
+//! [Collections API](https://solr.apache.org/guide/solr/latest/configuration-guide/collections-api.html).
+
+use crate::client::api::ApiVersion;
+use crate::client::core::SolrCore;
+use crate::client::lb::LBSolrClient;
+use crate::client::routing::CloudRouter;
+use crate::client::solr::{SolrClient, SolrClientError};
+use crate::types::response::*;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+type Result<T> = std::result::Result<T, SolrClientError>;
+
+/// Maximum interval of polling the status of the asynchronous request.
+const MAX_POLLING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Actions of the Collections API that only read the state of the cluster, which are retried by the retry policy.
+const READ_ACTIONS: [&str; 4] = ["LIST", "CLUSTERSTATUS", "LISTALIASES", "REQUESTSTATUS"];
+
+/// Type of the replica.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplicaType {
+    Nrt,
+    Tlog,
+    Pull,
+}
+
+impl Display for ReplicaType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ReplicaType::Nrt => write!(f, "NRT"),
+            ReplicaType::Tlog => write!(f, "TLOG"),
+            ReplicaType::Pull => write!(f, "PULL"),
+        }
+    }
+}
+
+/// Options of the CREATE action.
+#[derive(Clone, Debug)]
+pub struct CreateCollection {
+    params: Vec<(String, String)>,
+}
+
+impl CreateCollection {
+    pub fn new(name: &str) -> Self {
+        Self {
+            params: vec![(String::from("name"), String::from(name))],
+        }
+    }
+
+    /// Return the name of the collection to be created.
+    pub fn name(&self) -> &str {
+        &self.params[0].1
+    }
+
+    fn param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params.push((String::from(key), value.to_string()));
+        self
+    }
+
+    /// Set `numShards` parameter.
+    pub fn num_shards(self, num: u32) -> Self {
+        self.param("numShards", num)
+    }
+
+    /// Set `replicationFactor` parameter, the number of NRT replicas of each shard.
+    pub fn replication_factor(self, num: u32) -> Self {
+        self.param("replicationFactor", num)
+    }
+
+    /// Set `tlogReplicas` parameter.
+    pub fn tlog_replicas(self, num: u32) -> Self {
+        self.param("tlogReplicas", num)
+    }
+
+    /// Set `pullReplicas` parameter.
+    pub fn pull_replicas(self, num: u32) -> Self {
+        self.param("pullReplicas", num)
+    }
+
+    /// Set `collection.configName` parameter, the name of the configset used by the collection.
+    pub fn config_name(self, config: &str) -> Self {
+        self.param("collection.configName", config)
+    }
+
+    /// Set `router.name` parameter, `compositeId` or `implicit`.
+    pub fn router_name(self, router: &str) -> Self {
+        self.param("router.name", router)
+    }
+
+    /// Set `router.field` parameter.
+    pub fn router_field(self, field: &str) -> Self {
+        self.param("router.field", field)
+    }
+
+    /// Set `shards` parameter, the names of the shards for the implicit router.
+    pub fn shards(self, shards: &[&str]) -> Self {
+        self.param("shards", shards.join(","))
+    }
+
+    /// Set `createNodeSet` parameter, the nodes on which the replicas are created.
+    pub fn create_node_set(self, nodes: &[&str]) -> Self {
+        self.param("createNodeSet", nodes.join(","))
+    }
+
+    /// Set `property.<name>` parameter, the core property of the replicas.
+    pub fn property(self, name: &str, value: &str) -> Self {
+        self.param(&format!("property.{}", name), value)
+    }
+
+    /// Set `async` parameter to create the collection in the background.
+    pub fn async_id(self, id: &str) -> Self {
+        self.param("async", id)
+    }
+
+    pub(crate) fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// Options of the ADDREPLICA action.
+#[derive(Clone, Debug)]
+pub struct AddReplica {
+    params: Vec<(String, String)>,
+}
+
+impl AddReplica {
+    pub fn new(collection: &str, shard: &str) -> Self {
+        Self {
+            params: vec![
+                (String::from("collection"), String::from(collection)),
+                (String::from("shard"), String::from(shard)),
+            ],
+        }
+    }
+
+    /// Set `node` parameter, the node on which the replica is created.
+    pub fn node(mut self, node: &str) -> Self {
+        self.params.push((String::from("node"), String::from(node)));
+        self
+    }
+
+    /// Set `type` parameter.
+    pub fn replica_type(mut self, replica_type: ReplicaType) -> Self {
+        self.params
+            .push((String::from("type"), replica_type.to_string()));
+        self
+    }
+
+    /// Set `async` parameter to add the replica in the background.
+    pub fn async_id(mut self, id: &str) -> Self {
+        self.params.push((String::from("async"), String::from(id)));
+        self
+    }
+
+    pub(crate) fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// Options of the DELETEREPLICA action.
+#[derive(Clone, Debug)]
+pub struct DeleteReplica {
+    params: Vec<(String, String)>,
+}
+
+impl DeleteReplica {
+    /// Delete the replica of the given name, e.g. `core_node2`.
+    pub fn new(collection: &str, shard: &str, replica: &str) -> Self {
+        Self {
+            params: vec![
+                (String::from("collection"), String::from(collection)),
+                (String::from("shard"), String::from(shard)),
+                (String::from("replica"), String::from(replica)),
+            ],
+        }
+    }
+
+    /// Delete the given number of replicas of the shard, chosen by Solr.
+    pub fn count(collection: &str, shard: &str, count: u32) -> Self {
+        Self {
+            params: vec![
+                (String::from("collection"), String::from(collection)),
+                (String::from("shard"), String::from(shard)),
+                (String::from("count"), count.to_string()),
+            ],
+        }
+    }
+
+    /// Set `async` parameter to delete the replicas in the background.
+    pub fn async_id(mut self, id: &str) -> Self {
+        self.params.push((String::from("async"), String::from(id)));
+        self
+    }
+
+    pub(crate) fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// Options of the CREATEALIAS action.
+///
+/// Creating the alias with the existing name replaces its collections, which swaps the collections atomically
+/// in blue/green reindexing.
+#[derive(Clone, Debug)]
+pub struct CreateAlias {
+    params: Vec<(String, String)>,
+}
+
+impl CreateAlias {
+    /// Create the standard alias that points to the given collections.
+    pub fn new(name: &str, collections: &[&str]) -> Self {
+        Self {
+            params: vec![
+                (String::from("name"), String::from(name)),
+                (String::from("collections"), collections.join(",")),
+            ],
+        }
+    }
+
+    /// Create the time routed alias, which creates the collections for each `interval` from `start`,
+    /// e.g. `NOW/DAY` and `+1DAY`, and routes the documents by the date `field`.
+    pub fn time_routed(name: &str, field: &str, start: &str, interval: &str) -> Self {
+        Self {
+            params: vec![
+                (String::from("name"), String::from(name)),
+                (String::from("router.name"), String::from("time")),
+                (String::from("router.field"), String::from(field)),
+                (String::from("router.start"), String::from(start)),
+                (String::from("router.interval"), String::from(interval)),
+            ],
+        }
+    }
+
+    /// Create the category routed alias, which creates the collection for each value of the `field`.
+    pub fn category_routed(name: &str, field: &str) -> Self {
+        Self {
+            params: vec![
+                (String::from("name"), String::from(name)),
+                (String::from("router.name"), String::from("category")),
+                (String::from("router.field"), String::from(field)),
+            ],
+        }
+    }
+
+    /// Set `router.<name>` parameter of the routed alias, such as `maxFutureMs` and `maxCardinality`.
+    pub fn router_param(mut self, name: &str, value: &str) -> Self {
+        self.params
+            .push((format!("router.{}", name), String::from(value)));
+        self
+    }
+
+    /// Set `create-collection.<name>` parameter used to create the collections of the routed alias,
+    /// such as `numShards` and `collection.configName`.
+    pub fn collection_param(mut self, name: &str, value: &str) -> Self {
+        self.params
+            .push((format!("create-collection.{}", name), String::from(value)));
+        self
+    }
+
+    /// Set `async` parameter to create the alias in the background.
+    pub fn async_id(mut self, id: &str) -> Self {
+        self.params.push((String::from("async"), String::from(id)));
+        self
+    }
+
+    pub(crate) fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// Options of the BACKUP action.
+#[derive(Clone, Debug)]
+pub struct BackupCollection {
+    params: Vec<(String, String)>,
+}
+
+impl BackupCollection {
+    /// Back up the collection as the backup of the name in the location, e.g. the path on the shared file system.
+    pub fn new(collection: &str, name: &str, location: &str) -> Self {
+        Self {
+            params: vec![
+                (String::from("collection"), String::from(collection)),
+                (String::from("name"), String::from(name)),
+                (String::from("location"), String::from(location)),
+            ],
+        }
+    }
+
+    /// Set `repository` parameter, the name of the backup repository defined in `solr.xml`.
+    pub fn repository(mut self, repository: &str) -> Self {
+        self.params
+            .push((String::from("repository"), String::from(repository)));
+        self
+    }
+
+    /// Set `async` parameter to run the backup in the background.
+    pub fn async_id(mut self, id: &str) -> Self {
+        self.params.push((String::from("async"), String::from(id)));
+        self
+    }
+
+    pub(crate) fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// Options of the RESTORE action.
+#[derive(Clone, Debug)]
+pub struct RestoreCollection {
+    params: Vec<(String, String)>,
+}
+
+impl RestoreCollection {
+    /// Restore the backup of the name in the location into the collection, which must not exist.
+    pub fn new(collection: &str, name: &str, location: &str) -> Self {
+        Self {
+            params: vec![
+                (String::from("collection"), String::from(collection)),
+                (String::from("name"), String::from(name)),
+                (String::from("location"), String::from(location)),
+            ],
+        }
+    }
+
+    /// Set `repository` parameter, the name of the backup repository defined in `solr.xml`.
+    pub fn repository(mut self, repository: &str) -> Self {
+        self.params
+            .push((String::from("repository"), String::from(repository)));
+        self
+    }
+
+    /// Set `collection.configName` parameter, the configset used instead of the one in the backup.
+    pub fn config_name(mut self, config: &str) -> Self {
+        self.params
+            .push((String::from("collection.configName"), String::from(config)));
+        self
+    }
+
+    /// Set `replicationFactor` parameter.
+    pub fn replication_factor(mut self, num: u32) -> Self {
+        self.params
+            .push((String::from("replicationFactor"), num.to_string()));
+        self
+    }
+
+    /// Set `async` parameter to run the restoration in the background.
+    pub fn async_id(mut self, id: &str) -> Self {
+        self.params.push((String::from("async"), String::from(id)));
+        self
+    }
+
+    pub(crate) fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+/// Client of the Collections API of SolrCloud.
+#[derive(Debug)]
+pub struct SolrCloudClient {
+    client: SolrClient,
+}
+
+impl From<SolrClient> for SolrCloudClient {
+    fn from(client: SolrClient) -> Self {
+        Self { client }
+    }
+}
+
+/// Send the requests to the Collections API over the nodes of LBSolrClient.
+impl From<LBSolrClient> for SolrCloudClient {
+    fn from(client: LBSolrClient) -> Self {
+        Self {
+            client: client.client,
+        }
+    }
+}
+
+impl SolrCloudClient {
+    /// Of the URL given as argument, the schema, hostname and context path are extracted and used, the same as [`SolrClient::new`].
+    #[deprecated(since = "0.1.11", note = "use `SolrCloudClient::from_url` instead")]
+    #[allow(deprecated)]
+    pub fn new(url: &str, port: u32) -> Result<Self> {
+        Ok(Self::from(SolrClient::new(url, port)?))
+    }
+
+    /// Create the client with the URL of one of the Solr nodes, the same as [`SolrClient::from_url`].
+    pub fn from_url(url: &str) -> Result<Self> {
+        Ok(Self::from(SolrClient::from_url(url)?))
+    }
+
+    /// Set the version of the API to which the requests are sent.
+    ///
+    /// With the V2 API, listing, deleting and reloading the collections are sent to `/api/collections`,
+    /// and the other actions are still sent to the V1 Collections API.
+    pub fn api_version(mut self, api: ApiVersion) -> Self {
+        self.client = self.client.api_version(api);
+        self
+    }
+
+    /// Method to send the request to the Collections API with the action and the parameters.
+    async fn collections_api<T: DeserializeOwned>(
+        &self,
+        action: &str,
+        params: &[(String, String)],
+    ) -> Result<T> {
+        let request = self
+            .client
+            .transport
+            .client
+            .get(format!("{}/solr/admin/collections", self.client.url))
+            .query(&[("action", action)])
+            .query(params);
+        let idempotent = READ_ACTIONS.contains(&action);
+
+        self.send(request, idempotent).await
+    }
+
+    /// Method to send the request and deserialize the response, converting the error in the response to Err.
+    ///
+    /// Only the idempotent requests are retried by the retry policy.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<T> {
+        let content = if idempotent {
+            self.client.send_idempotent(request).await?
+        } else {
+            self.client.send(request).await?
+        };
+
+        let response: SolrSimpleResponse =
+            serde_json::from_slice(&content).map_err(SolrClientError::DeserializeError)?;
+        if let Some(error) = response.error {
+            return Err(SolrClientError::UnexpectedError((error.code, error.msg)));
+        }
+
+        serde_json::from_slice(&content).map_err(SolrClientError::DeserializeError)
+    }
+
+    /// Method to create a collection.
+    ///
+    /// ```ignore
+    /// let options = CreateCollection::new("example").num_shards(2).replication_factor(2).config_name("_default");
+    /// let response = cloud.create_collection(&options).await?;
+    /// ```
+    pub async fn create_collection(
+        &self,
+        options: &CreateCollection,
+    ) -> Result<SolrCollectionsResponse> {
+        self.collections_api("CREATE", options.params()).await
+    }
+
+    /// Method to delete a collection.
+    pub async fn delete_collection(&self, name: &str) -> Result<SolrCollectionsResponse> {
+        match self.client.api {
+            ApiVersion::V1 => {
+                self.collections_api("DELETE", &[(String::from("name"), String::from(name))])
+                    .await
+            }
+            ApiVersion::V2 => {
+                let request = self
+                    .client
+                    .transport
+                    .client
+                    .delete(format!("{}/api/collections/{}", self.client.url, name));
+                self.send(request, false).await
+            }
+        }
+    }
+
+    /// Method to reload a collection.
+    pub async fn reload_collection(&self, name: &str) -> Result<SolrCollectionsResponse> {
+        match self.client.api {
+            ApiVersion::V1 => {
+                self.collections_api("RELOAD", &[(String::from("name"), String::from(name))])
+                    .await
+            }
+            ApiVersion::V2 => {
+                let request = self
+                    .client
+                    .transport
+                    .client
+                    .post(format!("{}/api/collections/{}", self.client.url, name))
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(r#"{"reload": {}}"#);
+                self.send(request, false).await
+            }
+        }
+    }
+
+    /// Method to add a replica to a shard.
+    pub async fn add_replica(&self, options: &AddReplica) -> Result<SolrCollectionsResponse> {
+        self.collections_api("ADDREPLICA", options.params()).await
+    }
+
+    /// Method to delete replicas from a shard.
+    pub async fn delete_replica(&self, options: &DeleteReplica) -> Result<SolrCollectionsResponse> {
+        self.collections_api("DELETEREPLICA", options.params())
+            .await
+    }
+
+    /// Method to get the list of the collections.
+    pub async fn list_collections(&self) -> Result<SolrCollectionList> {
+        match self.client.api {
+            ApiVersion::V1 => self.collections_api("LIST", &[]).await,
+            ApiVersion::V2 => {
+                let request = self
+                    .client
+                    .transport
+                    .client
+                    .get(format!("{}/api/collections", self.client.url));
+                self.send(request, true).await
+            }
+        }
+    }
+
+    /// Method to get the status of the cluster.
+    pub async fn cluster_status(&self) -> Result<SolrClusterStatus> {
+        self.collections_api("CLUSTERSTATUS", &[]).await
+    }
+
+    /// Method to get the state of a collection from the cluster status.
+    async fn collection_state(&self, collection: &str) -> Result<SolrCollectionState> {
+        let status: SolrClusterStatus = self
+            .collections_api(
+                "CLUSTERSTATUS",
+                &[(String::from("collection"), String::from(collection))],
+            )
+            .await?;
+
+        status
+            .cluster
+            .and_then(|mut cluster| cluster.collections.remove(collection))
+            .ok_or(SolrClientError::SpecifiedCoreNotFoundError)
+    }
+
+    /// Method to create the router that sends the documents of the collection directly to the shard leaders
+    /// and distributes the queries across the active replicas.
+    pub async fn router(&self, collection: &str) -> Result<CloudRouter> {
+        let state = self.collection_state(collection).await?;
+
+        Ok(CloudRouter::new(
+            collection,
+            state,
+            self.client.transport.clone(),
+        ))
+    }
+
+    /// Method to reload the cluster state of the router, e.g. after the leader has changed.
+    pub async fn refresh_router(&self, router: &CloudRouter) -> Result<()> {
+        let state = self.collection_state(router.collection()).await?;
+        router.update(state);
+
+        Ok(())
+    }
+
+    /// Method to create an alias, or to replace the collections of the existing alias.
+    ///
+    /// ```ignore
+    /// // Flip the alias to the reindexed collection.
+    /// cloud.create_alias(&CreateAlias::new("products", &["products_v2"])).await?;
+    /// ```
+    pub async fn create_alias(&self, options: &CreateAlias) -> Result<SolrCollectionsResponse> {
+        self.collections_api("CREATEALIAS", options.params()).await
+    }
+
+    /// Method to set the properties of an alias. The property with the empty value is removed.
+    pub async fn set_alias_properties(
+        &self,
+        name: &str,
+        properties: &[(&str, &str)],
+    ) -> Result<SolrCollectionsResponse> {
+        let mut params = vec![(String::from("name"), String::from(name))];
+        params.extend(
+            properties
+                .iter()
+                .map(|(key, value)| (format!("property.{}", key), String::from(*value))),
+        );
+
+        self.collections_api("ALIASPROP", &params).await
+    }
+
+    /// Method to get the list of the aliases.
+    pub async fn list_aliases(&self) -> Result<SolrAliasList> {
+        self.collections_api("LISTALIASES", &[]).await
+    }
+
+    /// Method to delete an alias. The collections of the alias are not deleted.
+    pub async fn delete_alias(&self, name: &str) -> Result<SolrCollectionsResponse> {
+        self.collections_api("DELETEALIAS", &[(String::from("name"), String::from(name))])
+            .await
+    }
+
+    /// Method to back up a collection.
+    ///
+    /// ```ignore
+    /// let options = BackupCollection::new("example", "example_20230101", "/var/solr/backups").async_id("backup-1");
+    /// cloud.backup_collection(&options).await?;
+    /// let status = cloud.request_status("backup-1").await?;
+    /// ```
+    pub async fn backup_collection(
+        &self,
+        options: &BackupCollection,
+    ) -> Result<SolrCollectionsResponse> {
+        self.collections_api("BACKUP", options.params()).await
+    }
+
+    /// Method to restore a collection from the backup.
+    pub async fn restore_collection(
+        &self,
+        options: &RestoreCollection,
+    ) -> Result<SolrCollectionsResponse> {
+        self.collections_api("RESTORE", options.params()).await
+    }
+
+    /// Method to get the status of the request submitted with `async` parameter.
+    pub async fn request_status(&self, id: &str) -> Result<SolrRequestStatusResponse> {
+        self.collections_api(
+            "REQUESTSTATUS",
+            &[(String::from("requestid"), String::from(id))],
+        )
+        .await
+    }
+
+    /// Method to wait until the request submitted with `async` parameter is completed or failed,
+    /// polling its status with the increasing interval.
+    ///
+    /// The last status is returned, whose state is `completed`, `failed` or `notfound`.
+    ///
+    /// ```ignore
+    /// cloud.create_collection(&CreateCollection::new("example").async_id("create-1")).await?;
+    /// let status = cloud.wait_for_completion("create-1", Duration::from_secs(60)).await?;
+    /// ```
+    pub async fn wait_for_completion(
+        &self,
+        id: &str,
+        timeout: Duration,
+    ) -> Result<SolrRequestStatusResponse> {
+        let deadline = Instant::now() + timeout;
+        let mut interval = Duration::from_millis(100);
+
+        loop {
+            let response = self.request_status(id).await?;
+            match response.status.state {
+                SolrRequestState::Submitted | SolrRequestState::Running => {}
+                _ => return Ok(response),
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(SolrClientError::AsyncRequestTimeout(String::from(id)));
+            }
+            sleep(interval.min(deadline - now)).await;
+            interval = (interval * 2).min(MAX_POLLING_INTERVAL);
+        }
+    }
+
+    /// Method to create SolrCore struct to operate a collection.
+    pub fn collection(&self, name: &str) -> SolrCore {
+        SolrCore::with_transport(name, &self.client.url, self.client.transport.clone())
+            .api_version(self.client.api)
+            .for_collection()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::testing::{response, StubServer};
+
+    fn cloud_client(server: &StubServer) -> SolrCloudClient {
+        SolrCloudClient::from(LBSolrClient::new(&[&server.url]).unwrap())
+    }
+
+    #[test]
+    fn test_create_collection_params() {
+        let options = CreateCollection::new("example")
+            .num_shards(2)
+            .replication_factor(3)
+            .config_name("_default")
+            .property("foo", "bar");
+
+        let expected = vec![
+            (String::from("name"), String::from("example")),
+            (String::from("numShards"), String::from("2")),
+            (String::from("replicationFactor"), String::from("3")),
+            (
+                String::from("collection.configName"),
+                String::from("_default"),
+            ),
+            (String::from("property.foo"), String::from("bar")),
+        ];
+
+        assert_eq!(options.params(), expected.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_request() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":1500},"success":{"localhost:8983_solr":{"responseHeader":{"status":0,"QTime":1000},"core":"example_shard1_replica_n1"}}}"#,
+        )]);
+        let cloud = cloud_client(&server);
+
+        let options = CreateCollection::new("example").num_shards(1);
+        let response = cloud.create_collection(&options).await.unwrap();
+
+        assert!(!response.has_failure());
+        assert!(server.requests()[0]
+            .starts_with("GET /solr/admin/collections?action=CREATE&name=example&numShards=1 "));
+    }
+
+    #[tokio::test]
+    async fn test_collections_api_error() {
+        let server = StubServer::start(vec![response(
+            "400 Bad Request",
+            &[],
+            r#"{"responseHeader":{"status":400,"QTime":1},"error":{"metadata":["error-class","org.apache.solr.common.SolrException"],"msg":"Could not find collection : unknown","code":400}}"#,
+        )]);
+        let cloud = cloud_client(&server);
+
+        let response = cloud.delete_collection("unknown").await;
+
+        assert!(matches!(
+            response,
+            Err(SolrClientError::BadRequest {
+                info: SolrErrorInfo { code: 400, .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_routed_alias_params() {
+        let options = CreateAlias::time_routed("logs", "timestamp", "NOW/DAY", "+1DAY")
+            .router_param("maxFutureMs", "3600000")
+            .collection_param("numShards", "2");
+
+        let expected = vec![
+            (String::from("name"), String::from("logs")),
+            (String::from("router.name"), String::from("time")),
+            (String::from("router.field"), String::from("timestamp")),
+            (String::from("router.start"), String::from("NOW/DAY")),
+            (String::from("router.interval"), String::from("+1DAY")),
+            (String::from("router.maxFutureMs"), String::from("3600000")),
+            (
+                String::from("create-collection.numShards"),
+                String::from("2"),
+            ),
+        ];
+
+        assert_eq!(options.params(), expected.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_list_aliases() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"aliases":{"products":"products_v1,products_v2"},"properties":{"products":{"owner":"search"}}}"#,
+        )]);
+        let cloud = cloud_client(&server);
+
+        let aliases = cloud.list_aliases().await.unwrap();
+
+        assert_eq!(
+            aliases.collections("products"),
+            vec![String::from("products_v1"), String::from("products_v2")]
+        );
+        assert_eq!(aliases.properties["products"]["owner"], "search");
+        assert!(aliases.collections("unknown").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backup_collection_with_async_id() {
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":5},"requestid":"backup-1"}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":1},"status":{"state":"completed","msg":"found [backup-1] in completed tasks"}}"#,
+            ),
+        ]);
+        let cloud = cloud_client(&server);
+
+        let options = BackupCollection::new("example", "snapshot", "/backups")
+            .repository("local")
+            .async_id("backup-1");
+        let response = cloud.backup_collection(&options).await.unwrap();
+        let status = cloud.request_status("backup-1").await.unwrap();
+
+        assert_eq!(response.request_id.as_deref(), Some("backup-1"));
+        assert_eq!(status.status.state, SolrRequestState::Completed);
+        let requests = server.requests();
+        assert!(requests[0].starts_with(
+            "GET /solr/admin/collections?action=BACKUP&collection=example&name=snapshot&location=%2Fbackups&repository=local&async=backup-1 "
+        ));
+        assert!(requests[1]
+            .starts_with("GET /solr/admin/collections?action=REQUESTSTATUS&requestid=backup-1 "));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_completion() {
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":5},"requestid":"create-1"}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":1},"status":{"state":"running","msg":"found [create-1] in running tasks"}}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":1},"status":{"state":"failed","msg":"found [create-1] in failed tasks"},"failure":{"localhost:8983_solr":"Underlying core creation failed"}}"#,
+            ),
+        ]);
+        let cloud = cloud_client(&server);
+
+        let options = CreateCollection::new("example").async_id("create-1");
+        cloud.create_collection(&options).await.unwrap();
+        let status = cloud
+            .wait_for_completion("create-1", Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(status.status.state, SolrRequestState::Failed);
+        assert!(status.failure.is_some());
+        assert_eq!(server.requests().len(), 3);
+        assert!(server.requests()[0].contains("&async=create-1 "));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_completion_timeout() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":1},"status":{"state":"running","msg":"found [backup-1] in running tasks"}}"#,
+        )]);
+        let cloud = cloud_client(&server);
+
+        let result = cloud
+            .wait_for_completion("backup-1", Duration::from_millis(0))
+            .await;
+
+        assert!(
+            matches!(result, Err(SolrClientError::AsyncRequestTimeout(id)) if id == "backup-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_collections_with_v2_api() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"collections":["example"]}"#,
+        )]);
+        let cloud = cloud_client(&server).api_version(ApiVersion::V2);
+
+        let collections = cloud.list_collections().await.unwrap();
+
+        assert_eq!(collections.collections, vec![String::from("example")]);
+        assert!(server.requests()[0].starts_with("GET /api/collections "));
+    }
+
+    /// Normal system test of the Collections API.
+    ///
+    /// Run this test with the Docker container started in SolrCloud mode with the following command.
+    ///
+    /// ```ignore
+    /// docker run --rm -d -p 8983:8983 solr:9.1.0 solr -c -f
+    /// ```
+    #[tokio::test]
+    #[ignore]
+    async fn test_collections_scenario() {
+        let cloud = SolrCloudClient::from_url("http://localhost:8983").unwrap();
+
+        let options = CreateCollection::new("cloud_example")
+            .num_shards(1)
+            .replication_factor(1);
+        cloud.create_collection(&options).await.unwrap();
+        assert!(cloud
+            .list_collections()
+            .await
+            .unwrap()
+            .collections
+            .contains(&String::from("cloud_example")));
+
+        cloud.reload_collection("cloud_example").await.unwrap();
+        cloud
+            .add_replica(&AddReplica::new("cloud_example", "shard1"))
+            .await
+            .unwrap();
+        cloud
+            .delete_replica(&DeleteReplica::count("cloud_example", "shard1", 1))
+            .await
+            .unwrap();
+        cloud.cluster_status().await.unwrap();
+
+        cloud
+            .create_alias(&CreateAlias::new("cloud_alias", &["cloud_example"]))
+            .await
+            .unwrap();
+        cloud
+            .set_alias_properties("cloud_alias", &[("owner", "test")])
+            .await
+            .unwrap();
+        assert_eq!(
+            cloud
+                .list_aliases()
+                .await
+                .unwrap()
+                .collections("cloud_alias"),
+            vec![String::from("cloud_example")]
+        );
+        cloud.delete_alias("cloud_alias").await.unwrap();
+
+        cloud.delete_collection("cloud_example").await.unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+//! This module defines the configuration of SolrClient, which is read from the environment variables
+//! or deserialized from the configuration file of the application.
+
+use crate::client::auth::SolrAuth;
+use crate::client::core::SolrCore;
+use crate::client::solr::{SolrClient, SolrClientBuilder, SolrClientError};
+use core::time::Duration;
+use serde::Deserialize;
+use std::fmt::{Debug, Formatter};
+
+type Result<T> = std::result::Result<T, SolrClientError>;
+
+/// Configuration of SolrClient.
+///
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct AppConfig {
+///     solr: SolrConfig,
+/// }
+///
+/// let client = config.solr.client()?;
+/// ```
+#[derive(Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct SolrConfig {
+    /// URL of the Solr instance, e.g. `http://localhost:8983`.
+    pub url: String,
+    /// User name of Basic Authentication.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password of Basic Authentication.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Bearer token, used when the user name is not given.
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub connect_timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Name of the core used by default.
+    #[serde(default)]
+    pub core: Option<String>,
+}
+
+impl SolrConfig {
+    /// Read the configuration from the environment variables:
+    ///
+    /// - `SOLR_URL` (required)
+    /// - `SOLR_USERNAME` and `SOLR_PASSWORD`
+    /// - `SOLR_TOKEN`
+    /// - `SOLR_CONNECT_TIMEOUT_MS` and `SOLR_TIMEOUT_MS`
+    /// - `SOLR_CORE`
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the configuration from the variables given by the lookup function.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let millis = |name: &str| -> Result<Option<u64>> {
+            lookup(name)
+                .map(|value| {
+                    value.trim().parse::<u64>().map_err(|_| {
+                        SolrClientError::ConfigError(format!(
+                            "{} must be milliseconds, but got `{}`",
+                            name, value
+                        ))
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            url: lookup("SOLR_URL")
+                .ok_or_else(|| SolrClientError::ConfigError(String::from("SOLR_URL is not set")))?,
+            username: lookup("SOLR_USERNAME"),
+            password: lookup("SOLR_PASSWORD"),
+            token: lookup("SOLR_TOKEN"),
+            connect_timeout_ms: millis("SOLR_CONNECT_TIMEOUT_MS")?,
+            timeout_ms: millis("SOLR_TIMEOUT_MS")?,
+            core: lookup("SOLR_CORE"),
+        })
+    }
+
+    /// Create the builder of SolrClient with this configuration, to which the other settings can be added.
+    pub fn builder(&self) -> SolrClientBuilder {
+        let mut builder = SolrClientBuilder::from_url(&self.url);
+        if let Some(timeout) = self.connect_timeout_ms {
+            builder = builder.connect_timeout(Duration::from_millis(timeout));
+        }
+        if let Some(timeout) = self.timeout_ms {
+            builder = builder.timeout(Duration::from_millis(timeout));
+        }
+        match (&self.username, &self.token) {
+            (Some(username), _) => {
+                builder = builder.auth(SolrAuth::basic(username, self.password.as_deref()))
+            }
+            (None, Some(token)) => builder = builder.auth(SolrAuth::bearer(token)),
+            (None, None) => {}
+        }
+
+        builder
+    }
+
+    /// Create SolrClient with this configuration.
+    pub fn client(&self) -> Result<SolrClient> {
+        self.builder().build()
+    }
+
+    /// Create SolrCore of the default core, sharing the settings of this configuration.
+    ///
+    /// Unlike [`SolrClient::core`], the existence of the core is not checked.
+    pub fn default_core(&self) -> Result<SolrCore> {
+        let name = self
+            .core
+            .as_deref()
+            .ok_or_else(|| SolrClientError::ConfigError(String::from("default core is not set")))?;
+        let client = self.client()?;
+
+        Ok(
+            SolrCore::with_transport(name, &client.url, client.transport.clone())
+                .api_version(client.api),
+        )
+    }
+}
+
+/// Implementation of Debug that does not expose the secrets.
+impl Debug for SolrConfig {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("SolrConfig")
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("connect_timeout_ms", &self.connect_timeout_ms)
+            .field("timeout_ms", &self.timeout_ms)
+            .field("core", &self.core)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_from_lookup() {
+        let config = SolrConfig::from_lookup(lookup(&[
+            ("SOLR_URL", "http://localhost:8983"),
+            ("SOLR_USERNAME", "solr"),
+            ("SOLR_PASSWORD", "SolrRocks"),
+            ("SOLR_TIMEOUT_MS", "5000"),
+            ("SOLR_CORE", "example"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.url, "http://localhost:8983");
+        assert_eq!(config.timeout_ms, Some(5000));
+        assert!(!format!("{:?}", config).contains("SolrRocks"));
+
+        let core = config.default_core().unwrap();
+        assert_eq!(core.core_url, "http://localhost:8983/solr/example");
+    }
+
+    #[test]
+    fn test_from_lookup_with_invalid_values() {
+        assert!(matches!(
+            SolrConfig::from_lookup(lookup(&[])),
+            Err(SolrClientError::ConfigError(_))
+        ));
+        assert!(matches!(
+            SolrConfig::from_lookup(lookup(&[
+                ("SOLR_URL", "http://localhost:8983"),
+                ("SOLR_TIMEOUT_MS", "5s"),
+            ])),
+            Err(SolrClientError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let config: SolrConfig = serde_json::from_str(
+            r#"{"url": "https://example.com/solr", "token": "secret", "core": "products"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.token.as_deref(), Some("secret"));
+        assert_eq!(config.client().unwrap().url, "https://example.com:443");
+    }
+}
@@ -5,276 +5,2199 @@
 //! Operations such as obtaining core status, posting and searching documents,
 //! and reload core can be performed through this struct.
 
+use crate::client::api::{context_root, ApiVersion};
+use crate::client::auth::{SolrAuth, TokenError};
+use crate::client::circuit_breaker::CircuitBreaker;
+use crate::client::middleware::Middleware;
+use crate::client::observer::RequestObserver;
+use crate::client::retry::RetryPolicy;
+use crate::client::transport::{
+    impl_request_error, RequestContext, Transport, TransportOptions, TransportResponse,
+};
+use crate::querybuilder::common::{ResponseFormat, SolrCommonQueryBuilder};
+use crate::querybuilder::update::UpdateRequestBuilder;
 use crate::types::response::*;
+#[cfg(feature = "xml")]
+use crate::types::xml::{from_xml_str, XmlResponseError};
+use async_trait::async_trait;
+use bytes::Bytes;
 use core::time::Duration;
-use reqwest::header::CONTENT_TYPE;
-use reqwest::Client;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{Client, RequestBuilder};
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 use thiserror::Error;
 
 type Result<T> = std::result::Result<T, SolrCoreError>;
 
+const DEFAULT_CONFLICT_RETRIES: u32 = 3;
+
 #[derive(Debug, Error)]
 pub enum SolrCoreError {
-    #[error("Failed to request to solr core")]
-    RequestError(#[from] reqwest::Error),
+    #[error("Failed to build the request to solr core")]
+    InvalidRequestError(#[source] reqwest::Error),
+    #[error("Failed to request to solr core: {context}")]
+    RequestError {
+        context: Box<RequestContext>,
+        #[source]
+        source: reqwest::Error,
+    },
     #[error("Failed to deserialize JSON data")]
     DeserializeError(#[from] serde_json::Error),
+    #[error("Failed to get the authentication token: {context}")]
+    AuthenticationError {
+        context: Box<RequestContext>,
+        #[source]
+        source: TokenError,
+    },
+    #[error("Circuit breaker is open for node {node}: {context}")]
+    CircuitOpenError {
+        node: String,
+        context: Box<RequestContext>,
+    },
+    #[error("Middleware failed to process the request: {context}")]
+    MiddlewareError {
+        context: Box<RequestContext>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Response body exceeds the limit of {limit} bytes: {context}")]
+    ResponseTooLarge {
+        context: Box<RequestContext>,
+        limit: usize,
+    },
+    #[error("Request to solr core timed out: {context}")]
+    Timeout {
+        context: Box<RequestContext>,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Bad request: {}: {}", .info.msg, .context)]
+    BadRequest {
+        context: Box<RequestContext>,
+        info: SolrErrorInfo,
+    },
+    #[error("Authentication is required: {context}")]
+    Unauthorized { context: Box<RequestContext> },
+    #[error("Permission denied: {context}")]
+    Forbidden { context: Box<RequestContext> },
+    #[error("Requested resource is not found: {context}")]
+    NotFound { context: Box<RequestContext> },
+    #[error("Solr is unavailable: {context}")]
+    ServiceUnavailable { context: Box<RequestContext> },
+    #[error("Conflict: {}: {}", .info.msg, .context)]
+    Conflict {
+        context: Box<RequestContext>,
+        info: SolrErrorInfo,
+    },
+    #[error(
+        "Solr responded with status {status} and the body that is not JSON: {context}: {body}"
+    )]
+    UnparsableResponseError {
+        context: Box<RequestContext>,
+        status: u16,
+        content_type: Option<String>,
+        body: String,
+    },
+    #[error("Response format {0} can not be parsed, use select_raw instead")]
+    UnsupportedFormatError(ResponseFormat),
+    #[cfg(feature = "xml")]
+    #[error("Failed to deserialize XML data")]
+    XmlDeserializeError(#[from] XmlResponseError),
+    #[cfg(feature = "csv")]
+    #[error("Failed to deserialize CSV data")]
+    CsvDeserializeError(#[from] csv::Error),
+    #[error("Specified core name does not exist")]
+    SpecifiedCoreNotFoundError,
     #[error("Unexpected error")]
     UnexpectedError((u32, String)),
 }
 
+impl_request_error!(SolrCoreError);
+
 #[derive(Clone)]
 pub struct SolrCore {
     pub name: String,
     pub base_url: String,
     pub core_url: String,
-    client: Client,
+    transport: Transport,
     timeout: Option<Duration>,
+    /// Headers attached to every request to the core, in addition to the ones of the transport.
+    headers: HeaderMap,
+    api: ApiVersion,
+    /// Path segment of the V2 API, `cores` or `collections`.
+    scope: &'static str,
+    /// Number of the retries of [`SolrCore::update_with_retry`] on the version conflict.
+    conflict_retries: u32,
 }
 
 impl SolrCore {
+    /// Create SolrCore of the name on the Solr instance of the base URL, e.g. `http://localhost:8983`.
+    ///
+    /// The base URL may have the context path of Solr deployed behind the reverse proxy, with or without the trailing `/solr`,
+    /// e.g. `https://example.com/search-api/solr`.
     pub fn new(name: &str, base_url: &str) -> Self {
+        Self::with_client(name, base_url, reqwest::Client::new())
+    }
+
+    /// Create SolrCore that sends requests with the given HTTP client.
+    ///
+    /// The client is shared with SolrClient, so the connection pool and TLS settings are reused.
+    pub fn with_client(name: &str, base_url: &str, client: Client) -> Self {
+        Self::with_transport(name, base_url, Transport::new(client))
+    }
+
+    /// Create SolrCore that shares the transport, including the authentication and the retry policy, with SolrClient.
+    pub(crate) fn with_transport(name: &str, base_url: &str, transport: Transport) -> Self {
+        let base_url = context_root(base_url);
         let core_url = format!("{}/solr/{}", base_url, name);
 
-        SolrCore {
-            name: String::from(name),
-            base_url: String::from(base_url),
-            core_url: core_url,
-            client: reqwest::Client::new(),
-            timeout: None,
-        }
+        SolrCore {
+            name: String::from(name),
+            base_url: String::from(base_url),
+            core_url,
+            transport,
+            timeout: None,
+            headers: HeaderMap::new(),
+            api: ApiVersion::V1,
+            scope: "cores",
+            conflict_retries: DEFAULT_CONFLICT_RETRIES,
+        }
+    }
+
+    /// Treat this core as a SolrCloud collection, which is addressed as `/api/collections/<NAME>` in the V2 API.
+    pub(crate) fn for_collection(mut self) -> Self {
+        self.scope = "collections";
+        self
+    }
+
+    /// Set the number of the retries of [`SolrCore::update_with_retry`] when the document is updated concurrently.
+    pub fn conflict_retries(mut self, retries: u32) -> Self {
+        self.conflict_retries = retries;
+
+        self
+    }
+
+    /// Set the timeout applied to every request to the core.
+    ///
+    /// When the timeout elapses, the methods return [`SolrCoreError::Timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Return a copy of this core whose requests are applied the given timeout,
+    /// which is useful to set the timeout of a single operation.
+    ///
+    /// ```ignore
+    /// let response = core.with_timeout(Duration::from_millis(500)).select::<Value>(&params).await;
+    /// ```
+    ///
+    /// The timeout overrides the one of the underlying HTTP client. To cancel an operation without the timeout,
+    /// drop the future returned by the method.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        self.clone().timeout(timeout)
+    }
+
+    /// Set the headers attached to every request to the core, e.g. the API key of the gateway in front of Solr.
+    ///
+    /// The headers are sent in addition to the default headers of the client that created the core,
+    /// and take precedence over them of the same name.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.transport.headers.extend(headers);
+
+        self
+    }
+
+    /// Set the options on how the requests to the core are sent and how the responses are read,
+    /// which replace the ones of the client that created the core.
+    ///
+    /// The ID given with [`SolrCore::with_headers`] is used instead of the generated `X-Request-Id` if any.
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.transport.options = options;
+
+        self
+    }
+
+    /// Return a copy of this core whose requests are attached the given headers in addition,
+    /// which is useful to set the headers of a single operation.
+    ///
+    /// ```ignore
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("X-Request-Id", HeaderValue::from_static("42"));
+    /// let response = core.with_headers(headers).select::<Value>(&params).await;
+    /// ```
+    ///
+    /// The headers take precedence over the default headers of the same name.
+    pub fn with_headers(&self, headers: HeaderMap) -> Self {
+        let mut core = self.clone();
+        core.headers.extend(headers);
+
+        core
+    }
+
+    /// Set the authentication method attached to every request to the core.
+    pub fn auth(mut self, auth: SolrAuth) -> Self {
+        self.transport.auth = Some(auth);
+
+        self
+    }
+
+    /// Set the policy to retry the idempotent requests (e.g. ping, status and select) to the core.
+    ///
+    /// Requests that change the state of the core, such as post, commit, reload and the DIH imports, are never retried.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.transport.retry = Some(policy);
+
+        self
+    }
+
+    /// Set the circuit breaker that stops sending requests to the node after consecutive failures.
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.transport.circuit_breaker = Some(breaker);
+
+        self
+    }
+
+    /// Set the observer notified of every request to the core.
+    pub fn observer(mut self, observer: impl RequestObserver + 'static) -> Self {
+        self.transport.observer = Some(Arc::new(observer));
+
+        self
+    }
+
+    /// Add the middleware executed around every request to the core. The middlewares are executed in the order they are added.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.transport.middlewares.push(Arc::new(middleware));
+
+        self
+    }
+
+    /// Set the names of the parameters whose values are masked in the context of the errors,
+    /// such as the parameters containing personal information.
+    pub fn redact_params(mut self, names: &[&str]) -> Self {
+        self.transport.redacted_params = names.iter().map(|name| String::from(*name)).collect();
+
+        self
+    }
+
+    /// Set the version of the API to which the requests are sent.
+    pub fn api_version(mut self, api: ApiVersion) -> Self {
+        self.api = api;
+
+        self
+    }
+
+    /// Return the URL of the core in the API version, e.g. `http://localhost:8983/api/cores/example` in the V2 API.
+    fn api_url(&self) -> String {
+        match self.api {
+            ApiVersion::V1 => self.core_url.clone(),
+            ApiVersion::V2 => format!("{}/api/{}/{}", self.base_url, self.scope, self.name),
+        }
+    }
+
+    /// Return the URL of the request handler of the core, e.g. `select`.
+    fn handler_url(&self, handler: &str) -> String {
+        format!("{}/{}", self.api_url(), handler.trim_start_matches('/'))
+    }
+
+    /// Method to send the request with the timeout and the authentication of this core, and get the response body.
+    ///
+    /// The request is never retried, because it may change the state of the core.
+    async fn send(&self, request: RequestBuilder) -> Result<Bytes> {
+        Ok(self.send_response(request, false).await?.body)
+    }
+
+    /// Method to send the request that only reads the core, which is retried by the retry policy, and get the response body.
+    async fn send_idempotent(&self, request: RequestBuilder) -> Result<Bytes> {
+        Ok(self.send_response(request, true).await?.body)
+    }
+
+    /// Method to send the request with the timeout and the authentication of this core, and get the response.
+    async fn send_response(
+        &self,
+        mut request: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<TransportResponse> {
+        if let Some(timeout) = &self.timeout {
+            request = request.timeout(*timeout);
+        }
+        if !self.headers.is_empty() {
+            request = request.headers(self.headers.clone());
+        }
+
+        let mut request = request
+            .build()
+            .map_err(SolrCoreError::InvalidRequestError)?;
+        self.transport.prepare(&mut request);
+        let context = Box::new(self.transport.context(&request, Some(&self.name)));
+
+        let response = self
+            .transport
+            .execute(request, idempotent)
+            .await
+            .map_err(|e| SolrCoreError::from_transport_error(e, context.clone()))?;
+        if let Some(e) = SolrCoreError::from_response(&response, context) {
+            return Err(e);
+        }
+
+        Ok(response)
+    }
+
+    /// Method to ping the core.
+    pub async fn ping(&self) -> Result<SolrPingResponse> {
+        let request = self.transport.client.get(self.handler_url("admin/ping"));
+        let content = self.send_idempotent(request).await?;
+
+        let response: SolrPingResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+        Ok(response)
+    }
+
+    /// Method to get core status.
+    pub async fn status(&self) -> Result<SolrCoreStatus> {
+        self.status_with_index_info(true).await
+    }
+
+    /// Method to get core status, without the details of the index when `index_info` is false.
+    ///
+    /// Collecting the details of the index is slow on a huge core.
+    pub async fn status_with_index_info(&self, index_info: bool) -> Result<SolrCoreStatus> {
+        let index_info = index_info.to_string();
+        let request = match self.api {
+            ApiVersion::V1 => self
+                .transport
+                .client
+                .get(format!("{}/solr/admin/cores", self.base_url))
+                .query(&[
+                    ("action", "status"),
+                    ("core", &self.name),
+                    ("indexInfo", &index_info),
+                ]),
+            ApiVersion::V2 => self
+                .transport
+                .client
+                .get(self.api_url())
+                .query(&[("indexInfo", &index_info)]),
+        };
+        let content = self.send_idempotent(request).await?;
+
+        let core_list: SolrCoreList =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = core_list.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        // The core bound by name, or the collection whose cores have the other names, is not in the status.
+        core_list
+            .status
+            .and_then(|mut cores| cores.remove(&self.name))
+            .ok_or(SolrCoreError::SpecifiedCoreNotFoundError)
+    }
+
+    /// Method to request the core to reload.
+    pub async fn reload(&self) -> Result<u32> {
+        let request = match self.api {
+            ApiVersion::V1 => self
+                .transport
+                .client
+                .get(format!("{}/solr/admin/cores", self.base_url))
+                .query(&[("action", "reload"), ("core", &self.name)]),
+            ApiVersion::V2 => self
+                .transport
+                .client
+                .post(self.api_url())
+                .header(CONTENT_TYPE, "application/json")
+                .body(r#"{"reload": {}}"#),
+        };
+        let content = self.send(request).await?;
+
+        let response: SolrSimpleResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response.header.status)
+    }
+
+    /// Method to send the command to the Data Import Handler of the core, e.g. `dataimport`.
+    pub async fn dih_command(&self, handler: &str, command: DihCommand) -> Result<SolrDihResponse> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url(handler))
+            .query(&[("command", command.as_str()), ("wt", "json")]);
+        // Only the status command is retried, because the others start or stop the import.
+        let idempotent = matches!(command, DihCommand::Status);
+        let content = self.send_response(request, idempotent).await?.body;
+
+        let response: SolrDihResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response)
+    }
+
+    /// Method to build the dictionary of the suggester, e.g. after bulk indexing.
+    ///
+    /// The request is sent to the `suggest` handler, which must have the suggest component.
+    pub async fn suggest_build(&self, dictionary: &str) -> Result<SolrSuggestCommandResponse> {
+        self.suggest_command(dictionary, "build").await
+    }
+
+    /// Method to reload the dictionary of the suggester from the index or the file.
+    pub async fn suggest_reload(&self, dictionary: &str) -> Result<SolrSuggestCommandResponse> {
+        self.suggest_command(dictionary, "reload").await
+    }
+
+    async fn suggest_command(
+        &self,
+        dictionary: &str,
+        command: &str,
+    ) -> Result<SolrSuggestCommandResponse> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url("suggest"))
+            .query(&[
+                ("suggest", "true"),
+                ("suggest.dictionary", dictionary),
+                (&format!("suggest.{}", command), "true"),
+                ("wt", "json"),
+            ]);
+        let content = self.send(request).await?;
+
+        let response: SolrSuggestCommandResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+        // The handler without the suggest component ignores the parameters and responds successfully.
+        if response.command.as_deref() != Some(command) {
+            return Err(SolrCoreError::UnexpectedError((
+                response.header.status,
+                format!("suggester `{}` did not execute `{}`", dictionary, command),
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Method to send request to the Terms Component of the core, with the parameters such as `terms.fl`.
+    pub async fn terms(
+        &self,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<SolrTermsResponse> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url("terms"))
+            .query(params)
+            .query(&[("wt", "json"), ("json.nl", "flat")]);
+        let content = self.send_idempotent(request).await?;
+
+        let response: SolrTermsResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response)
+    }
+
+    /// Create the iterator of all the terms of the field in the index order, with their document frequencies.
+    ///
+    /// ```ignore
+    /// let mut terms = core.iter_terms("title").page_size(10000);
+    /// while let Some((term, count)) = terms.next().await.transpose()? {
+    ///     println!("{}\t{}", term, count);
+    /// }
+    /// ```
+    pub fn iter_terms(&self, field: &str) -> TermsIter<'_> {
+        TermsIter {
+            core: self,
+            field: field.to_string(),
+            page_size: TERMS_PAGE_SIZE,
+            lower: None,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Method to cancel the running query tagged with the UUID by [`SolrCommonQueryBuilder::query_uuid`],
+    /// with the [task management](https://solr.apache.org/guide/solr/latest/deployment-guide/task-management.html) of Solr 9.
+    pub async fn cancel_query(&self, uuid: &str) -> Result<SolrCancelQueryResponse> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url("tasks/cancel"))
+            .query(&[("queryUUID", uuid), ("wt", "json")]);
+
+        let content = self.send(request).await?;
+        let response: SolrCancelQueryResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response)
+    }
+
+    /// Method to list the running cancellable queries.
+    pub async fn list_tasks(&self) -> Result<SolrTaskListResponse> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url("tasks/list"))
+            .query(&[("wt", "json")]);
+
+        let content = self.send_idempotent(request).await?;
+        let response: SolrTaskListResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response)
+    }
+
+    /// Method to send request the core to search the document with some query parameters.
+    pub async fn select<D>(
+        &self,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<SolrSelectResponse<D>>
+    where
+        D: Serialize + DeserializeOwned,
+    {
+        self.select_with_handler("select", params).await
+    }
+
+    /// Method to send request the core to search the document with the parameters of the query builder.
+    ///
+    /// The builder is not consumed, so a preset of the query can be reused. Use [`SolrCore::select`] to send the raw parameters.
+    pub async fn select_with<D>(
+        &self,
+        builder: &impl SolrCommonQueryBuilder,
+    ) -> Result<SolrSelectResponse<D>>
+    where
+        D: Serialize + DeserializeOwned,
+    {
+        let params: Vec<(String, String)> = builder.to_params().into();
+        self.select(&params).await
+    }
+
+    /// Method to search the document with the query builder, and get the documents, the facets and the highlighting
+    /// extracted from the response.
+    pub async fn search<D>(&self, builder: &impl SolrCommonQueryBuilder) -> Result<SearchResult<D>>
+    where
+        D: Serialize + DeserializeOwned,
+    {
+        let response = self.select_with(builder).await?;
+        SearchResult::from_response(response).map_err(SolrCoreError::DeserializeError)
+    }
+
+    /// Method to send request to the specified search handler of the core (e.g. `query`, `browse`).
+    ///
+    /// The handler is given as a path relative to the core URL. A leading `/` is ignored.
+    pub async fn select_with_handler<D>(
+        &self,
+        handler: &str,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<SolrSelectResponse<D>>
+    where
+        D: Serialize + DeserializeOwned,
+    {
+        self.select_body(handler, params).await?.select()
+    }
+
+    /// Method to send request to the specified search handler of the core, and get the response body kept alive
+    /// so that the documents can be deserialized borrowing the strings from it. See [`SolrResponseBody::select`].
+    pub async fn select_body(
+        &self,
+        handler: &str,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<SolrResponseBody> {
+        match response_format(params) {
+            None | Some(ResponseFormat::Json) => {}
+            Some(format) => return Err(SolrCoreError::UnsupportedFormatError(format)),
+        }
+
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url(handler))
+            .query(params);
+        let body = self.send_idempotent(request).await?;
+
+        Ok(SolrResponseBody { body })
+    }
+
+    /// Method to send request to the specified search handler of the core with `wt=xml`, and parse the XML response
+    /// into the same structure as the JSON response.
+    ///
+    /// Use this method for the legacy request handlers that respond only in XML.
+    #[cfg(feature = "xml")]
+    pub async fn select_xml<D>(
+        &self,
+        handler: &str,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<SolrSelectResponse<D>>
+    where
+        D: Serialize + DeserializeOwned,
+    {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url(handler))
+            .query(params)
+            .query(&[("wt", "xml")]);
+        let content = self.send_response(request, true).await?.into_text();
+
+        let selection: SolrSelectResponse<D> = from_xml_str(&content)?;
+
+        if let Some(error) = selection.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(selection)
+    }
+
+    /// Method to send request to the specified search handler of the core with `wt=csv`, and deserialize each row
+    /// into the document.
+    ///
+    /// CSV is smaller and faster to parse than JSON for the flat documents, e.g. to export all of them.
+    /// The values of the multi-valued fields are joined with `csv.mv.separator` (`,` by default) into a string.
+    #[cfg(feature = "csv")]
+    pub async fn select_csv<D>(
+        &self,
+        handler: &str,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<Vec<D>>
+    where
+        D: DeserializeOwned,
+    {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url(handler))
+            .query(params)
+            .query(&[("wt", "csv")]);
+        let content = self.send_idempotent(request).await?;
+
+        let mut reader = csv::Reader::from_reader(content.as_ref());
+        let docs = reader
+            .deserialize()
+            .collect::<std::result::Result<Vec<D>, _>>()?;
+
+        Ok(docs)
+    }
+
+    /// Method to send request to the specified search handler of the core, and get the response body as it is.
+    ///
+    /// Use this method to get the response in the format other than JSON, given by `wt` parameter (e.g. XML, CSV).
+    pub async fn select_raw(
+        &self,
+        handler: &str,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<String> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url(handler))
+            .query(params);
+
+        Ok(self.send_response(request, true).await?.into_text())
+    }
+
+    // TODO: Method to request the core to analyze given word.
+    // pub async fn analyze(&self, word: &str, field: &str, analyzer: &str) -> Result<Vec<String>> {
+    //     todo!();
+    // let params = [("analysis.fieldvalue", word), ("analysis.fieldtype", field)];
+
+    // let response = self
+    //     .client
+    //     .get(format!("{}/analysis/field", self.core_url))
+    //     .query(&params)
+    //     .send()
+    //     .await
+    //     .map_err(|e| SolrCoreError::RequestError(e))?
+    //     .text()
+    //     .await
+    //     .map_err(|e| SolrCoreError::RequestError(e))?;
+
+    // let result: SolrAnalysisResponse =
+    //     serde_json::from_str(&response).map_err(|e| SolrCoreError::DeserializeError(e))?;
+
+    // let result = result.analysis.field_types.get(field).unwrap();
+    // let result = match analyzer {
+    //     "index" => result.index.as_ref().unwrap(),
+    //     "query" => result.query.as_ref().unwrap(),
+    //     _ => return Err(SolrCoreError::InvalidValueError),
+    // };
+    // let result = result.last().unwrap().clone();
+
+    // let result = match result {
+    //     Value::Array(array) => array
+    //         .iter()
+    //         .map(|e| e["text"].to_string().trim_matches('"').to_string())
+    //         .collect::<Vec<String>>(),
+    //     _ => Vec::new(),
+    // };
+
+    // Ok(result)
+    // }
+
+    /// Method to analyze the fields of the document with the document analysis handler, without indexing it.
+    ///
+    /// Return the analysis keyed by the field. The document must have the unique key.
+    pub async fn analyze_document(
+        &self,
+        doc: &impl Serialize,
+    ) -> Result<HashMap<String, SolrDocumentFieldAnalysis>> {
+        self.send_document_analysis(doc, None).await
+    }
+
+    /// Method to analyze the fields of the document and the query, to see why the document does not match the query.
+    ///
+    /// The tokens of the index analysis matching the tokens of the query analysis are marked with `matched`.
+    pub async fn analyze_document_with_query(
+        &self,
+        doc: &impl Serialize,
+        query: &str,
+    ) -> Result<HashMap<String, SolrDocumentFieldAnalysis>> {
+        self.send_document_analysis(doc, Some(query)).await
+    }
+
+    async fn send_document_analysis(
+        &self,
+        doc: &impl Serialize,
+        query: Option<&str>,
+    ) -> Result<HashMap<String, SolrDocumentFieldAnalysis>> {
+        // The document analysis handler accepts only the documents in XML.
+        let body = document_to_xml(&serde_json::to_value(doc)?)?;
+        let mut request = self
+            .transport
+            .client
+            .post(self.handler_url("analysis/document"))
+            .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+            .query(&[("wt", "json")])
+            .body(body);
+        if let Some(query) = query {
+            request = request.query(&[("analysis.query", query), ("analysis.showmatch", "true")]);
+        }
+        let content = self.send_idempotent(request).await?;
+
+        let response: SolrDocumentAnalysisResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response.analysis.into_values().next().unwrap_or_default())
+    }
+
+    /// Method to post the document to the core.
+    /// The document to be posted must be a JSON string.
+    pub async fn post(&self, body: Vec<u8>) -> Result<SolrSimpleResponse> {
+        self.post_with(body, &Vec::<(&str, &str)>::new()).await
+    }
+
+    /// Method to post the document to the core with the parameters of the update request,
+    /// e.g. `update.chain` to process the documents with the specific update processor chain.
+    pub async fn post_with(
+        &self,
+        body: Vec<u8>,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<SolrSimpleResponse> {
+        let request = self
+            .transport
+            .client
+            .post(self.handler_url("update"))
+            .query(params)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+
+        let content = self.send(request).await?;
+
+        let post_result: SolrSimpleResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        Ok(post_result)
+    }
+
+    /// Method to send the update request with the commands to the core.
+    pub async fn update(&self, request: &UpdateRequestBuilder) -> Result<SolrSimpleResponse> {
+        self.post_with(request.to_json().into_bytes(), request.params())
+            .await
+    }
+
+    /// Method to get the latest version of the document by the unique key with the [real-time get](https://solr.apache.org/guide/solr/latest/configuration-guide/realtime-get.html),
+    /// which returns the document even if it is not committed yet.
+    pub async fn get_by_id<D>(&self, id: &str) -> Result<Option<D>>
+    where
+        D: DeserializeOwned,
+    {
+        match self.get_latest(id).await? {
+            Some(doc) => Ok(Some(serde_json::from_value(doc)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Method to update the document by reading, modifying and writing it, with the [optimistic concurrency](https://solr.apache.org/guide/solr/latest/indexing-guide/partial-document-updates.html#optimistic-concurrency).
+    ///
+    /// The document is written with `_version_` of the read one, so Solr rejects it when the document is updated
+    /// by another client in the meantime. Then the document is read and modified again, up to the number of the retries
+    /// set with [`SolrCore::conflict_retries`], 3 by default.
+    ///
+    /// ```ignore
+    /// let book = core.update_with_retry("001", |book: &mut Book| book.stock -= 1).await?;
+    /// ```
+    ///
+    /// Return the written document, or None if the document does not exist.
+    /// The fields not in the document struct are removed, as the whole document is replaced.
+    pub async fn update_with_retry<D>(&self, id: &str, f: impl Fn(&mut D)) -> Result<Option<D>>
+    where
+        D: Serialize + DeserializeOwned,
+    {
+        let mut attempts = 0;
+        loop {
+            let doc = match self.get_latest(id).await? {
+                Some(doc) => doc,
+                None => return Ok(None),
+            };
+            let version = doc.get("_version_").cloned();
+
+            let mut document: D = serde_json::from_value(doc)?;
+            f(&mut document);
+
+            let mut doc = serde_json::to_value(&document)?;
+            if let (Some(fields), Some(version)) = (doc.as_object_mut(), version) {
+                fields.insert(String::from("_version_"), version);
+            }
+
+            let body = serde_json::to_vec(&vec![doc])?;
+            match self.post(body).await {
+                Ok(response) => {
+                    if let Some(error) = response.error {
+                        return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+                    }
+                    return Ok(Some(document));
+                }
+                Err(SolrCoreError::Conflict { .. }) if attempts < self.conflict_retries => {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Get the latest version of the document with the real-time get handler.
+    async fn get_latest(&self, id: &str) -> Result<Option<Value>> {
+        let request = self
+            .transport
+            .client
+            .get(self.handler_url("get"))
+            .query(&[("id", id), ("wt", "json")]);
+
+        let content = self.send_idempotent(request).await?;
+        let response: SolrRealTimeGetResponse =
+            serde_json::from_slice(&content).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+        }
+
+        Ok(response.doc)
+    }
+
+    /// Method to send request the core to commit the post.
+    ///
+    /// When optimize is true, this method request to commit with optimization.
+    /// The response reports the achieved replication factor in SolrCloud.
+    pub async fn commit(&self, optimize: bool) -> Result<SolrSimpleResponse> {
+        if optimize {
+            self.post(br#"{"optimize": {}}"#.to_vec()).await
+        } else {
+            self.post(br#"{"commit": {}}"#.to_vec()).await
+        }
+    }
+
+    /// Method to send request the core to rollback the post.
+    pub async fn rollback(&self) -> Result<()> {
+        self.post(br#"{"rollback": {}}"#.to_vec()).await?;
+
+        Ok(())
+    }
+
+    /// Method to send a request to the core to delete all existing documents.
+    pub async fn truncate(&self) -> Result<()> {
+        self.post(br#"{"delete":{"query": "*:*"}}"#.to_vec())
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Command of the Data Import Handler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DihCommand {
+    FullImport,
+    DeltaImport,
+    Status,
+    Abort,
+}
+
+impl DihCommand {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DihCommand::FullImport => "full-import",
+            DihCommand::DeltaImport => "delta-import",
+            DihCommand::Status => "status",
+            DihCommand::Abort => "abort",
+        }
+    }
+}
+
+/// Render the document as the body of the request to the document analysis handler.
+///
+/// The values of a multi-valued field are rendered as the repeated fields, and the null values are omitted.
+fn document_to_xml(doc: &Value) -> Result<String> {
+    let fields = doc.as_object().ok_or_else(|| {
+        SolrCoreError::UnexpectedError((400, String::from("document must be a JSON object")))
+    })?;
+
+    let mut xml = String::from("<docs><doc>");
+    for (name, value) in fields {
+        let values = match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            let value = match value {
+                Value::Null => continue,
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            xml.push_str(&format!(
+                r#"<field name="{}">{}</field>"#,
+                escape_xml(name),
+                escape_xml(&value)
+            ));
+        }
+    }
+    xml.push_str("</doc></docs>");
+
+    Ok(xml)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+const TERMS_PAGE_SIZE: u32 = 1000;
+
+/// Iterator of the terms of a field, which pages through the term dictionary with `terms.lower` and `terms.limit`.
+pub struct TermsIter<'a> {
+    core: &'a SolrCore,
+    field: String,
+    page_size: u32,
+    /// Last term of the previous page, from which the next page starts exclusively.
+    lower: Option<String>,
+    buffer: VecDeque<(String, u64)>,
+    done: bool,
+}
+
+impl TermsIter<'_> {
+    /// Set the number of the terms fetched by a request.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Return the next term and its document frequency, fetching the next page if needed.
+    ///
+    /// Return `None` when all the terms have been returned. The iteration ends after an error is returned.
+    pub async fn next(&mut self) -> Option<Result<(String, u64)>> {
+        if self.buffer.is_empty() && !self.done {
+            if let Err(e) = self.fetch().await {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+
+        self.buffer.pop_front().map(Ok)
+    }
+
+    async fn fetch(&mut self) -> Result<()> {
+        let mut params = vec![
+            (String::from("terms.fl"), self.field.clone()),
+            (String::from("terms.limit"), self.page_size.to_string()),
+            (String::from("terms.sort"), String::from("index")),
+        ];
+        if let Some(lower) = &self.lower {
+            params.push((String::from("terms.lower"), lower.clone()));
+            params.push((String::from("terms.lower.incl"), String::from("false")));
+        }
+
+        let mut response = self.core.terms(&params).await?;
+        let terms = response.terms.remove(&self.field).unwrap_or_default();
+
+        // A page shorter than the limit is the last one.
+        self.done = terms.len() < self.page_size as usize;
+        self.lower = terms.last().map(|(term, _)| term.clone());
+        self.buffer.extend(terms);
+
+        Ok(())
+    }
+}
+
+/// Body of the response of a search request, kept alive to deserialize the documents borrowing from it.
+#[derive(Clone, Debug)]
+pub struct SolrResponseBody {
+    body: Bytes,
+}
+
+impl SolrResponseBody {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Deserialize the response, in which the documents may borrow the strings from the body.
+    ///
+    /// Use `Cow<'a, str>` with `#[serde(borrow)]` for the string fields, since `&'a str` fails to deserialize
+    /// the strings containing escaped characters, which can not be borrowed as they are.
+    pub fn select<'a, D>(&'a self) -> Result<SolrSelectResponse<D>>
+    where
+        D: Deserialize<'a>,
+    {
+        let selection: SolrSelectResponse<D> =
+            serde_json::from_slice(&self.body).map_err(SolrCoreError::DeserializeError)?;
+
+        if let Some(error) = &selection.error {
+            return Err(SolrCoreError::UnexpectedError((
+                error.code,
+                error.msg.clone(),
+            )));
+        }
+
+        Ok(selection)
+    }
+}
+
+/// The operations on the Solr core, which is implemented by SolrCore.
+///
+/// The trait is object-safe, so that the application can depend on `Arc<dyn SolrCoreApi>`
+/// and replace it with a mock in the unit tests without the running Solr instance.
+///
+/// The documents of the search results are returned as JSON values, which can be converted with [`serde_json::from_value`].
+#[async_trait]
+pub trait SolrCoreApi: Send + Sync {
+    /// Return the name of the core.
+    fn name(&self) -> &str;
+    async fn ping(&self) -> Result<SolrPingResponse>;
+    async fn status(&self) -> Result<SolrCoreStatus>;
+    async fn reload(&self) -> Result<u32>;
+    async fn select(&self, params: &[(String, String)]) -> Result<SolrSelectResponse<Value>>;
+    async fn select_with_handler(
+        &self,
+        handler: &str,
+        params: &[(String, String)],
+    ) -> Result<SolrSelectResponse<Value>>;
+    async fn post(&self, body: Vec<u8>) -> Result<SolrSimpleResponse>;
+    async fn commit(&self, optimize: bool) -> Result<SolrSimpleResponse>;
+    async fn rollback(&self) -> Result<()>;
+    async fn truncate(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl SolrCoreApi for SolrCore {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn ping(&self) -> Result<SolrPingResponse> {
+        SolrCore::ping(self).await
+    }
+
+    async fn status(&self) -> Result<SolrCoreStatus> {
+        SolrCore::status(self).await
+    }
+
+    async fn reload(&self) -> Result<u32> {
+        SolrCore::reload(self).await
+    }
+
+    async fn select(&self, params: &[(String, String)]) -> Result<SolrSelectResponse<Value>> {
+        SolrCore::select(self, &params.to_vec()).await
+    }
+
+    async fn select_with_handler(
+        &self,
+        handler: &str,
+        params: &[(String, String)],
+    ) -> Result<SolrSelectResponse<Value>> {
+        SolrCore::select_with_handler(self, handler, &params.to_vec()).await
+    }
+
+    async fn post(&self, body: Vec<u8>) -> Result<SolrSimpleResponse> {
+        SolrCore::post(self, body).await
+    }
+
+    async fn commit(&self, optimize: bool) -> Result<SolrSimpleResponse> {
+        SolrCore::commit(self, optimize).await
+    }
+
+    async fn rollback(&self) -> Result<()> {
+        SolrCore::rollback(self).await
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        SolrCore::truncate(self).await
+    }
+}
+
+/// Return the response format given by `wt` parameter.
+fn response_format(params: &Vec<(impl Serialize, impl Serialize)>) -> Option<ResponseFormat> {
+    let params = serde_json::to_value(params).ok()?;
+    params
+        .as_array()?
+        .iter()
+        .find_map(|pair| match pair.as_array()?.as_slice() {
+            [key, value] if key == "wt" => value.as_str().and_then(ResponseFormat::from_wt),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::testing::{response, StubServer};
+    use crate::querybuilder::q::QueryOperand;
+    use crate::querybuilder::standard::{SolrStandardQueryBuilder, StandardQueryBuilder};
+    use chrono::{DateTime, Utc};
+    use serde_json::{self, Value};
+    use std::borrow::Cow;
+
+    /// Normal system test to get core status.
+    ///
+    /// Run this test with the Docker container started with the following command.
+    ///
+    /// ```ignore
+    /// docker run --rm -d -p 8983:8983 solr:9.1.0 solr-precreate example
+    /// ```
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_status() {
+        let core = SolrCore::new("example", "http://localhost:8983");
+        let status = core.status().await.unwrap();
+
+        assert_eq!(status.name, String::from("example"));
+    }
+
+    /// Anomaly system test of the timeout when the server does not respond.
+    #[tokio::test]
+    async fn test_timeout_without_response() {
+        // The listener never accepts the connection, so the request waits for the response until the timeout.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let core = SolrCore::new("example", &base_url);
+
+        let response = core.with_timeout(Duration::from_millis(100)).ping().await;
+
+        assert!(matches!(response, Err(SolrCoreError::Timeout { .. })));
+    }
+
+    /// Normal test to retry the idempotent request after `503 Service Unavailable`.
+    #[tokio::test]
+    async fn test_retry_on_service_unavailable() {
+        let server = StubServer::start(vec![
+            response("503 Service Unavailable", &[("Retry-After", "0")], "{}"),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"status":"OK"}"#,
+            ),
+        ]);
+        let core = SolrCore::new("example", &server.url).retry(RetryPolicy::new());
+
+        let response = core.ping().await.unwrap();
+
+        assert_eq!(response.status, "OK");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    /// Normal test that the requests modifying the index are not retried.
+    #[tokio::test]
+    async fn test_post_is_not_retried() {
+        let server = StubServer::start(vec![
+            response("503 Service Unavailable", &[], "{}"),
+            response("200 OK", &[], "{}"),
+        ]);
+        let core = SolrCore::new("example", &server.url).retry(RetryPolicy::new());
+
+        let _ = core.post(b"[]".to_vec()).await;
+
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    /// Normal test to send the update request with the commands in one body.
+    #[tokio::test]
+    async fn test_update() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":3}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let request = UpdateRequestBuilder::new().delete_by_id("001").commit();
+        let response = core.update(&request).await.unwrap();
+
+        assert_eq!(response.header.qtime, 3);
+        assert!(server.requests()[0].starts_with("POST /solr/example/update "));
+    }
+
+    /// Normal test to send the update request through the update processor chain.
+    #[tokio::test]
+    async fn test_update_with_chain() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":3}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let request = UpdateRequestBuilder::new()
+            .chain("langid")
+            .param("overwrite", "false")
+            .commit();
+        core.update(&request).await.unwrap();
+
+        assert!(server.requests()[0]
+            .starts_with("POST /solr/example/update?update.chain=langid&overwrite=false "));
+    }
+
+    /// Normal test to get the core status without the details of the index.
+    #[tokio::test]
+    async fn test_status_without_index_info() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{
+                "responseHeader":{"status":0,"QTime":0},
+                "initFailures":{},
+                "status":{
+                    "example":{
+                        "name":"example",
+                        "instanceDir":"/var/solr/data/example",
+                        "dataDir":"/var/solr/data/example/data/",
+                        "config":"solrconfig.xml",
+                        "schema":"managed-schema.xml",
+                        "startTime":"2023-01-01T00:00:00.000Z",
+                        "uptime":1000
+                    }
+                }
+            }"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let status = core.status_with_index_info(false).await.unwrap();
+
+        assert_eq!(status.name, "example");
+        assert!(status.index.is_none());
+        assert!(server.requests()[0].contains("&indexInfo=false "));
+    }
+
+    /// Abnormal test that the status of the name absent from the response is an error, not a panic.
+    #[tokio::test]
+    async fn test_status_of_collection() {
+        let empty = || {
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"initFailures":{},"status":{}}"#,
+            )
+        };
+        let server = StubServer::start(vec![empty(), empty()]);
+        let core = SolrCore::new("products", &server.url).for_collection();
+
+        assert!(matches!(
+            core.status().await,
+            Err(SolrCoreError::SpecifiedCoreNotFoundError)
+        ));
+        assert!(matches!(
+            core.api_version(ApiVersion::V2).status().await,
+            Err(SolrCoreError::SpecifiedCoreNotFoundError)
+        ));
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /solr/admin/cores?action=status&core=products&"));
+        assert!(requests[1].starts_with("GET /api/collections/products?indexInfo=true "));
+    }
+
+    /// Normal test to build and reload the dictionary of the suggester.
+    #[tokio::test]
+    async fn test_suggest_build_and_reload() {
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":25},"command":"build"}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":3},"command":"reload"}"#,
+            ),
+        ]);
+        let core = SolrCore::new("example", &server.url);
+
+        let response = core.suggest_build("titleSuggester").await.unwrap();
+        assert_eq!(response.header.qtime, 25);
+        core.suggest_reload("titleSuggester").await.unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with(
+            "GET /solr/example/suggest?suggest=true&suggest.dictionary=titleSuggester&suggest.build=true&wt=json "
+        ));
+        assert!(requests[1].starts_with(
+            "GET /solr/example/suggest?suggest=true&suggest.dictionary=titleSuggester&suggest.reload=true&wt=json "
+        ));
+    }
+
+    /// Abnormal test to build the dictionary with the handler without the suggest component.
+    #[tokio::test]
+    async fn test_suggest_build_without_suggest_component() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        assert!(matches!(
+            core.suggest_build("titleSuggester").await,
+            Err(SolrCoreError::UnexpectedError(_))
+        ));
+    }
+
+    /// Normal test to iterate the terms through the pages.
+    #[tokio::test]
+    async fn test_iter_terms() {
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"terms":{"title":["apple",3,"banana",1]}}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"terms":{"title":["cherry",2]}}"#,
+            ),
+        ]);
+        let core = SolrCore::new("example", &server.url);
+
+        let mut iter = core.iter_terms("title").page_size(2);
+        let mut terms = Vec::new();
+        while let Some(term) = iter.next().await {
+            terms.push(term.unwrap());
+        }
+
+        assert_eq!(
+            terms,
+            vec![
+                (String::from("apple"), 3),
+                (String::from("banana"), 1),
+                (String::from("cherry"), 2),
+            ]
+        );
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with(
+            "GET /solr/example/terms?terms.fl=title&terms.limit=2&terms.sort=index&wt=json&json.nl=flat "
+        ));
+        assert!(requests[1].starts_with(
+            "GET /solr/example/terms?terms.fl=title&terms.limit=2&terms.sort=index&terms.lower=banana&terms.lower.incl=false&wt=json&json.nl=flat "
+        ));
+    }
+
+    /// Normal test to analyze the document with the query.
+    #[tokio::test]
+    async fn test_analyze_document_with_query() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{
+                "responseHeader":{"status":0,"QTime":1},
+                "analysis":{"001":{"title":{
+                    "query":["org.apache.lucene.analysis.standard.StandardTokenizer",[{"text":"rust","start":0,"end":4,"position":1,"type":"<ALPHANUM>"}]],
+                    "index":{"Rust & Solr":["org.apache.lucene.analysis.standard.StandardTokenizer",[{"text":"Rust","start":0,"end":4,"position":1,"type":"<ALPHANUM>","match":true}]]}
+                }}}
+            }"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let analysis = core
+            .analyze_document_with_query(
+                &serde_json::json!({"id": "001", "title": "Rust & Solr", "tags": ["a", "b"], "price": null}),
+                "rust",
+            )
+            .await
+            .unwrap();
+
+        assert!(analysis["title"].index["Rust & Solr"][0].tokens[0].matched);
+        let request = &server.requests()[0];
+        assert!(request.starts_with(
+            "POST /solr/example/analysis/document?wt=json&analysis.query=rust&analysis.showmatch=true "
+        ));
+        assert!(request.ends_with(
+            r#"<docs><doc><field name="id">001</field><field name="tags">a</field><field name="tags">b</field><field name="title">Rust &amp; Solr</field></doc></docs>"#
+        ));
+    }
+
+    /// Normal test to list and cancel the running queries.
+    #[tokio::test]
+    async fn test_cancel_query() {
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"taskList":{"5d5c4c4e":"q=*:*&canCancel=true&queryUUID=5d5c4c4e"}}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"cancellationResult":"success"}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"cancellationResult":"not found"}"#,
+            ),
+        ]);
+        let core = SolrCore::new("example", &server.url);
+
+        let tasks = core.list_tasks().await.unwrap();
+        assert!(tasks.task_list.contains_key("5d5c4c4e"));
+        assert!(core.cancel_query("5d5c4c4e").await.unwrap().is_cancelled());
+        assert!(!core.cancel_query("5d5c4c4e").await.unwrap().is_cancelled());
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /solr/example/tasks/list?wt=json "));
+        assert!(
+            requests[1].starts_with("GET /solr/example/tasks/cancel?queryUUID=5d5c4c4e&wt=json ")
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Stock {
+        id: String,
+        count: i64,
+    }
+
+    /// Normal test to update the document, retrying on the version conflict.
+    #[tokio::test]
+    async fn test_update_with_retry() {
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"doc":{"id":"001","count":5,"_version_":100}}"#,
+            ),
+            response(
+                "409 Conflict",
+                &[],
+                r#"{"responseHeader":{"status":409,"QTime":0},"error":{"metadata":[],"msg":"version conflict for 001 expected=100 actual=101","code":409}}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"doc":{"id":"001","count":3,"_version_":101}}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":1}}"#,
+            ),
+        ]);
+        let core = SolrCore::new("example", &server.url);
+
+        let stock = core
+            .update_with_retry("001", |stock: &mut Stock| stock.count -= 1)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stock,
+            Some(Stock {
+                id: String::from("001"),
+                count: 2
+            })
+        );
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /solr/example/get?id=001&wt=json "));
+        assert!(requests[1].ends_with(r#"[{"_version_":100,"count":4,"id":"001"}]"#));
+        assert!(requests[3].ends_with(r#"[{"_version_":101,"count":2,"id":"001"}]"#));
     }
 
-    pub fn timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = Some(timeout);
+    /// Abnormal test that the conflict is returned when the retries are exhausted.
+    #[tokio::test]
+    async fn test_update_with_retry_exhausted() {
+        let conflict = response(
+            "409 Conflict",
+            &[],
+            r#"{"responseHeader":{"status":409,"QTime":0},"error":{"metadata":[],"msg":"version conflict","code":409}}"#,
+        );
+        let doc = response(
+            "200 OK",
+            &[],
+            r#"{"doc":{"id":"001","count":5,"_version_":100}}"#,
+        );
+        let server = StubServer::start(vec![doc.clone(), conflict.clone(), doc, conflict]);
+        let core = SolrCore::new("example", &server.url).conflict_retries(1);
 
-        self
+        let result = core
+            .update_with_retry("001", |stock: &mut Stock| stock.count -= 1)
+            .await;
+
+        assert!(matches!(result, Err(SolrCoreError::Conflict { .. })));
+        assert_eq!(server.requests().len(), 4);
     }
 
-    /// Method to ping the core.
-    pub async fn ping(&self) -> Result<SolrPingResponse> {
-        let mut request = self.client.get(format!("{}/admin/ping", self.core_url));
-        if let Some(timeout) = &self.timeout {
-            request = request.timeout(timeout.clone());
-        }
+    /// Normal test that the document that does not exist is not updated.
+    #[tokio::test]
+    async fn test_update_with_retry_without_document() {
+        let server = StubServer::start(vec![response("200 OK", &[], r#"{"doc":null}"#)]);
+        let core = SolrCore::new("example", &server.url);
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
-        let content = response
-            .text()
+        let stock = core
+            .update_with_retry("001", |stock: &mut Stock| stock.count -= 1)
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap();
 
-        let response: SolrPingResponse =
-            serde_json::from_str(&content).map_err(|e| SolrCoreError::DeserializeError(e))?;
-        Ok(response)
+        assert!(stock.is_none());
+        assert_eq!(server.requests().len(), 1);
     }
 
-    /// Method to get core status.
-    pub async fn status(&self) -> Result<SolrCoreStatus> {
-        let mut request = self
-            .client
-            .get(format!("{}/solr/admin/cores", self.base_url))
-            .query(&[("action", "status"), ("core", &self.name)]);
-        if let Some(timeout) = &self.timeout {
-            request = request.timeout(timeout.clone());
-        }
+    /// Normal test to get the status of the Data Import Handler.
+    #[tokio::test]
+    async fn test_dih_command() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{
+                "responseHeader":{"status":0,"QTime":0},
+                "initArgs":["defaults",["config","db-data-config.xml"]],
+                "command":"status",
+                "status":"idle",
+                "importResponse":"",
+                "statusMessages":{
+                    "Total Requests made to DataSource":"1",
+                    "Total Rows Fetched":"10",
+                    "Total Documents Processed":"10",
+                    "Total Documents Skipped":"0",
+                    "Time taken":"0:0:0.345"
+                }
+            }"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
 
-        let response = request
-            .send()
+        let status = core
+            .dih_command("dataimport", DihCommand::Status)
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap();
+
+        assert!(!status.is_busy());
+        assert_eq!(status.documents_processed(), Some(10));
+        assert!(server.requests()[0]
+            .starts_with("GET /solr/example/dataimport?command=status&wt=json "));
+    }
 
-        let content = response
-            .text()
+    /// Normal test that the headers of the operation are attached in addition to the default headers.
+    #[tokio::test]
+    async fn test_with_headers() {
+        use reqwest::header::HeaderValue;
+
+        let server = StubServer::start(vec![
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0}}"#,
+            ),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0}}"#,
+            ),
+        ]);
+        let mut defaults = HeaderMap::new();
+        defaults.insert("x-api-key", HeaderValue::from_static("secret"));
+        defaults.insert("x-tenant", HeaderValue::from_static("default"));
+        let core = SolrCore::new("example", &server.url).default_headers(defaults);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", HeaderValue::from_static("acme"));
+        core.with_headers(headers)
+            .post(b"[]".to_vec())
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap();
+        core.post(b"[]".to_vec()).await.unwrap();
 
-        let core_list: SolrCoreList =
-            serde_json::from_str(&content).map_err(|e| SolrCoreError::DeserializeError(e))?;
+        let requests = server.requests();
+        assert!(requests[0].contains("x-api-key: secret\r\n"));
+        assert!(requests[0].contains("x-tenant: acme\r\n"));
+        assert!(!requests[0].contains("x-tenant: default\r\n"));
+        assert!(requests[1].contains("x-tenant: default\r\n"));
+    }
 
-        if let Some(error) = core_list.error {
-            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
-        }
+    /// Normal test that the default headers of the core are sent with the ones of the client.
+    #[tokio::test]
+    async fn test_default_headers_with_client() {
+        use crate::client::solr::SolrClient;
+        use reqwest::header::HeaderValue;
+
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0}}"#,
+        )]);
+        let mut client_headers = HeaderMap::new();
+        client_headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        client_headers.insert("x-tenant", HeaderValue::from_static("default"));
+        let mut core_headers = HeaderMap::new();
+        core_headers.insert("x-tenant", HeaderValue::from_static("acme"));
+        let core = SolrClient::from_url(&server.url)
+            .unwrap()
+            .default_headers(client_headers)
+            .bind_core("example")
+            .default_headers(core_headers);
 
-        // Once the core object has been created,
-        // 1. the `status` field must be present in the response JSON
-        // 2. the key of the `status` field must contain this core
-        //
-        // is guaranteed, so `unwrap()` is used.
-        let status = core_list.status.unwrap().get(&self.name).unwrap().clone();
+        core.post(b"[]".to_vec()).await.unwrap();
 
-        Ok(status)
+        let requests = server.requests();
+        assert!(requests[0].contains("x-api-key: secret\r\n"));
+        assert!(requests[0].contains("x-tenant: acme\r\n"));
+        assert!(!requests[0].contains("x-tenant: default\r\n"));
     }
 
-    /// Method to request the core to reload.
-    pub async fn reload(&self) -> Result<u32> {
-        let mut request = self
-            .client
-            .get(format!("{}/solr/admin/cores", self.base_url))
-            .query(&[("action", "reload"), ("core", &self.name)]);
-        if let Some(timeout) = &self.timeout {
-            request = request.timeout(timeout.clone());
+    /// Abnormal test that the response larger than the limit is rejected, with or without `Content-Length` header.
+    #[tokio::test]
+    async fn test_max_response_size() {
+        let body = format!(
+            r#"{{"responseHeader":{{"status":0,"QTime":0}},"response":{{"numFound":1,"start":0,"numFoundExact":true,"docs":[{{"text":"{}"}}]}}}}"#,
+            "a".repeat(1000)
+        );
+        let server = StubServer::start(vec![
+            response("200 OK", &[], &body),
+            format!(
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/json\r\n\r\n{}",
+                body
+            ),
+            response("200 OK", &[], &body),
+        ]);
+        let core = SolrCore::new("example", &server.url)
+            .transport_options(TransportOptions::new().max_response_size(500));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                core.select::<Value>(&vec![("q", "*:*")]).await,
+                Err(SolrCoreError::ResponseTooLarge { limit: 500, .. })
+            ));
         }
+        let core = core.transport_options(TransportOptions::new().max_response_size(2000));
+        assert!(core.select::<Value>(&vec![("q", "*:*")]).await.is_ok());
+    }
 
-        let response = request
-            .send()
+    /// Normal test that the request with the long URL is sent as POST request with the form body.
+    #[tokio::test]
+    async fn test_post_if_url_too_long() {
+        let ok = || {
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":0,"start":0,"numFoundExact":true,"docs":[]}}"#,
+            )
+        };
+        let server = StubServer::start(vec![ok(), ok(), ok()]);
+        let core = SolrCore::new("example", &server.url)
+            .transport_options(TransportOptions::new().max_url_length(Some(100)));
+        let ids: Vec<String> = (0..20).map(|i| format!("{:03}", i)).collect();
+        let fq = format!("id:({})", ids.join(" "));
+
+        core.select::<Value>(&vec![("q", "*:*")]).await.unwrap();
+        core.select::<Value>(&vec![("q", "*:*"), ("fq", &fq)])
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
-
-        let content = response
-            .text()
+            .unwrap();
+        core.transport_options(TransportOptions::new().max_url_length(None))
+            .select::<Value>(&vec![("q", "*:*"), ("fq", &fq)])
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap();
 
-        let response: SolrSimpleResponse =
-            serde_json::from_str(&content).map_err(|e| SolrCoreError::DeserializeError(e))?;
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /solr/example/select?q=*%3A* "));
+        assert!(requests[1].starts_with("POST /solr/example/select "));
+        assert!(requests[1].contains("content-type: application/x-www-form-urlencoded\r\n"));
+        assert!(requests[1].ends_with("\r\n\r\nq=*%3A*&fq=id%3A%28000+001+002+003+004+005+006+007+008+009+010+011+012+013+014+015+016+017+018+019%29"));
+        assert!(requests[2].starts_with("GET /solr/example/select?q=*%3A*&fq="));
+    }
 
-        if let Some(error) = response.error {
-            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
-        }
+    /// Normal test that the long select sent as POST request is still retried after `503 Service Unavailable`.
+    #[tokio::test]
+    async fn test_retry_if_url_too_long() {
+        let server = StubServer::start(vec![
+            response("503 Service Unavailable", &[("Retry-After", "0")], "{}"),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":0,"start":0,"numFoundExact":true,"docs":[]}}"#,
+            ),
+        ]);
+        let core = SolrCore::new("example", &server.url)
+            .transport_options(TransportOptions::new().max_url_length(Some(100)))
+            .retry(RetryPolicy::new());
+        let fq = format!("id:({})", "0".repeat(100));
+
+        core.select::<Value>(&vec![("q", "*:*"), ("fq", &fq)])
+            .await
+            .unwrap();
 
-        Ok(response.header.status)
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|request| request.starts_with("POST /solr/example/select ")));
     }
 
-    /// Method to send request the core to search the document with some query parameters.
-    pub async fn select<D>(
-        &self,
-        params: &Vec<(impl Serialize, impl Serialize)>,
-    ) -> Result<SolrSelectResponse<D>>
-    where
-        D: Serialize + DeserializeOwned,
-    {
-        let mut request = self
-            .client
-            .get(format!("{}/select", self.core_url))
-            .query(params);
-        if let Some(timeout) = &self.timeout {
-            request = request.timeout(timeout.clone())
-        }
-
-        let response = request
-            .send()
+    /// Normal test that the generated request ID is sent and included in the error.
+    #[tokio::test]
+    async fn test_request_id() {
+        let server = StubServer::start(vec![response(
+            "400 Bad Request",
+            &[],
+            r#"{"responseHeader":{"status":400,"QTime":0},"error":{"metadata":[],"msg":"undefined field foo","code":400}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url)
+            .transport_options(TransportOptions::new().request_id(true));
+
+        let error = core
+            .select::<Value>(&vec![("q", "foo:bar")])
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap_err();
+
+        let id = error.context().unwrap().request_id.clone().unwrap();
+        assert_eq!(id.len(), 32);
+        assert!(server.requests()[0].contains(&format!("x-request-id: {}\r\n", id)));
+        assert!(error.to_string().contains(&id));
+    }
 
-        let content = response
-            .text()
+    /// Normal test that the request ID given in the headers is used instead of the generated one.
+    #[tokio::test]
+    async fn test_given_request_id() {
+        use reqwest::header::HeaderValue;
+
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url)
+            .transport_options(TransportOptions::new().request_id(true));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("trace-42"));
+        core.with_headers(headers)
+            .post(b"[]".to_vec())
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap();
 
-        let selection: SolrSelectResponse<D> =
-            serde_json::from_str(&content).map_err(|e| SolrCoreError::DeserializeError(e))?;
+        let request = &server.requests()[0];
+        assert!(request.contains("x-request-id: trace-42\r\n"));
+        assert_eq!(request.matches("x-request-id").count(), 1);
+    }
 
-        if let Some(error) = selection.error {
-            return Err(SolrCoreError::UnexpectedError((error.code, error.msg)));
+    /// Normal test to send the request to the core under the custom context path.
+    #[tokio::test]
+    async fn test_core_with_context_path() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"status":"OK"}"#,
+        )]);
+        let core = SolrCore::new("example", &format!("{}/search-api/solr/", server.url));
+
+        core.ping().await.unwrap();
+
+        assert_eq!(
+            core.core_url,
+            format!("{}/search-api/solr/example", server.url)
+        );
+        assert!(server.requests()[0].starts_with("GET /search-api/solr/example/admin/ping "));
+    }
+
+    /// Normal test to use SolrCore through the trait object.
+    #[tokio::test]
+    async fn test_core_as_trait_object() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":1,"start":0,"numFoundExact":true,"docs":[{"id":"1"}]}}"#,
+        )]);
+        let core: Arc<dyn SolrCoreApi> = Arc::new(SolrCore::new("example", &server.url));
+
+        let params = vec![("q".to_string(), "*:*".to_string())];
+        let response = core.select(&params).await.unwrap();
+
+        assert_eq!(core.name(), "example");
+        assert_eq!(response.response.docs[0]["id"], "1");
+    }
+
+    /// Normal test that the observer is notified of the request with the status and `QTime`.
+    #[tokio::test]
+    async fn test_observer() {
+        use crate::client::observer::{RequestInfo, RequestOutcome};
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Recorder {
+            events: Mutex<Vec<String>>,
         }
 
-        Ok(selection)
+        impl RequestObserver for Recorder {
+            fn on_request_start(&self, request: &RequestInfo) {
+                self.events.lock().unwrap().push(format!(
+                    "start {} {}",
+                    request.method,
+                    request.url.path()
+                ));
+            }
+
+            fn on_request_end(&self, _request: &RequestInfo, outcome: &RequestOutcome) {
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("end {:?} {:?}", outcome.status, outcome.qtime));
+            }
+        }
+
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":7},"status":"OK"}"#,
+        )]);
+        let recorder = Arc::new(Recorder::default());
+        let core = SolrCore::new("example", &server.url).observer(recorder.clone());
+
+        core.ping().await.unwrap();
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                String::from("start GET /solr/example/admin/ping"),
+                String::from("end Some(200) Some(7)"),
+            ]
+        );
     }
 
-    /// TODO: Method to request the core to analyze given word.
-    // pub async fn analyze(&self, word: &str, field: &str, analyzer: &str) -> Result<Vec<String>> {
-    //     todo!();
-    // let params = [("analysis.fieldvalue", word), ("analysis.fieldtype", field)];
+    /// Normal test to send the requests to the V2 API.
+    #[tokio::test]
+    async fn test_select_with_v2_api() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":0,"start":0,"numFoundExact":true,"docs":[]}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url).api_version(ApiVersion::V2);
 
-    // let response = self
-    //     .client
-    //     .get(format!("{}/analysis/field", self.core_url))
-    //     .query(&params)
-    //     .send()
-    //     .await
-    //     .map_err(|e| SolrCoreError::RequestError(e))?
-    //     .text()
-    //     .await
-    //     .map_err(|e| SolrCoreError::RequestError(e))?;
+        let params = vec![("q".to_string(), "*:*".to_string())];
+        core.select::<Value>(&params).await.unwrap();
 
-    // let result: SolrAnalysisResponse =
-    //     serde_json::from_str(&response).map_err(|e| SolrCoreError::DeserializeError(e))?;
+        assert!(server.requests()[0].starts_with("GET /api/cores/example/select?q=*%3A*"));
+    }
 
-    // let result = result.analysis.field_types.get(field).unwrap();
-    // let result = match analyzer {
-    //     "index" => result.index.as_ref().unwrap(),
-    //     "query" => result.query.as_ref().unwrap(),
-    //     _ => return Err(SolrCoreError::InvalidValueError),
-    // };
-    // let result = result.last().unwrap().clone();
+    /// Anomaly test that the requests to the node are rejected after the circuit is opened.
+    #[tokio::test]
+    async fn test_circuit_breaker_rejects_failing_node() {
+        let server = StubServer::start(vec![response("503 Service Unavailable", &[], "{}")]);
+        let breaker = CircuitBreaker::new().failure_threshold(1);
+        let core = SolrCore::new("example", &server.url).circuit_breaker(breaker);
+
+        let _ = core.ping().await;
+        let response = core.ping().await;
+
+        assert!(matches!(
+            response,
+            Err(SolrCoreError::CircuitOpenError { .. })
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
 
-    // let result = match result {
-    //     Value::Array(array) => array
-    //         .iter()
-    //         .map(|e| e["text"].to_string().trim_matches('"').to_string())
-    //         .collect::<Vec<String>>(),
-    //     _ => Vec::new(),
-    // };
+    /// Normal test to search with the query builder.
+    #[tokio::test]
+    async fn test_select_with_builder() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":0,"start":0,"numFoundExact":true,"docs":[]}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let builder = StandardQueryBuilder::new()
+            .q(&QueryOperand::from("name:alice"))
+            .rows(10);
+        let response = core.select_with::<Value>(&builder).await.unwrap();
+
+        assert_eq!(response.response.num_found, 0);
+        assert!(
+            server.requests()[0].starts_with("GET /solr/example/select?q=name%3Aalice&rows=10 ")
+        );
+    }
 
-    // Ok(result)
-    // }
+    /// Normal test to search and get the documents and the highlighting.
+    #[tokio::test]
+    async fn test_search() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":1,"start":0,"numFoundExact":true,"docs":[{"id":"001"}]},"highlighting":{"001":{"title":["<em>alice</em>"]}}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let builder = StandardQueryBuilder::new().q(&QueryOperand::from("title:alice"));
+        let result = core.search::<Value>(&builder).await.unwrap();
+
+        assert_eq!(result.num_found, 1);
+        assert_eq!(result.docs.len(), 1);
+        assert_eq!(
+            result.highlights("001", "title"),
+            Some(&vec![String::from("<em>alice</em>")])
+        );
+    }
 
-    /// Method to post the document to the core.
-    /// The document to be posted must be a JSON string.
-    pub async fn post(&self, body: Vec<u8>) -> Result<SolrSimpleResponse> {
-        let response = self
-            .client
-            .post(format!("{}/update", self.core_url))
-            .header(CONTENT_TYPE, "application/json")
-            .body(body)
-            .send()
-            .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+    /// Normal test to deserialize the documents borrowing from the response body.
+    #[tokio::test]
+    async fn test_select_body_with_borrowed_documents() {
+        #[derive(Deserialize)]
+        struct Document<'a> {
+            id: &'a str,
+            #[serde(borrow)]
+            name: Cow<'a, str>,
+        }
+
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"response":{"numFound":2,"start":0,"numFoundExact":true,"docs":[{"id":"001","name":"alice"},{"id":"002","name":"\"bob\""}]}}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
 
-        let content = response
-            .text()
+        let body = core
+            .select_body("select", &vec![("q", "*:*")])
             .await
-            .map_err(|e| SolrCoreError::RequestError(e))?;
+            .unwrap();
+        let response = body.select::<Document>().unwrap();
+        let docs = response.response.docs;
 
-        let post_result: SolrSimpleResponse =
-            serde_json::from_str(&content).map_err(|e| SolrCoreError::DeserializeError(e))?;
+        assert_eq!(docs[0].id, "001");
+        assert!(matches!(docs[0].name, Cow::Borrowed("alice")));
+        assert!(matches!(&docs[1].name, Cow::Owned(name) if name == "\"bob\""));
+    }
 
-        Ok(post_result)
+    /// Normal test to parse the XML response.
+    #[cfg(feature = "xml")]
+    #[tokio::test]
+    async fn test_select_xml() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[("Content-Type", "application/xml")],
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<response>
+<lst name="responseHeader"><int name="status">0</int><int name="QTime">0</int></lst>
+<result name="response" numFound="1" start="0" numFoundExact="true">
+<doc><str name="id">001</str></doc>
+</result>
+</response>"#,
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let response = core
+            .select_xml::<Value>("select", &vec![("q", "*:*")])
+            .await
+            .unwrap();
+
+        assert_eq!(response.response.num_found, 1);
+        assert_eq!(response.response.docs[0]["id"], "001");
+        assert!(server.requests()[0].starts_with("GET /solr/example/select?q=*%3A*&wt=xml "));
     }
 
-    /// Method to send request the core to commit the post.
-    ///
-    /// When optimize is true, this method request to commit with optimization.
-    pub async fn commit(&self, optimize: bool) -> Result<()> {
-        if optimize {
-            self.post(br#"{"optimize": {}}"#.to_vec()).await?;
-        } else {
-            self.post(br#"{"commit": {}}"#.to_vec()).await?;
+    /// Normal test to deserialize the CSV response into the documents.
+    #[cfg(feature = "csv")]
+    #[tokio::test]
+    async fn test_select_csv() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Document {
+            id: String,
+            name: String,
+            age: u32,
         }
 
-        Ok(())
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[("Content-Type", "text/plain")],
+            "id,name,age\n001,alice,24\n002,\"bob, jr.\",3\n",
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let docs: Vec<Document> = core
+            .select_csv("select", &vec![("q", "*:*"), ("fl", "id,name,age")])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            docs,
+            vec![
+                Document {
+                    id: String::from("001"),
+                    name: String::from("alice"),
+                    age: 24
+                },
+                Document {
+                    id: String::from("002"),
+                    name: String::from("bob, jr."),
+                    age: 3
+                },
+            ]
+        );
+        assert!(server.requests()[0].contains("&wt=csv "));
     }
 
-    /// Method to send request the core to rollback the post.
-    pub async fn rollback(&self) -> Result<()> {
-        self.post(br#"{"rollback": {}}"#.to_vec()).await?;
+    /// Normal test to get the response in the format other than JSON.
+    #[tokio::test]
+    async fn test_select_raw_with_csv() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[("Content-Type", "text/plain")],
+            "id,name\n1,alice\n",
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let params = vec![("q", "*:*"), ("wt", "csv")];
+        assert!(matches!(
+            core.select::<Value>(&params).await,
+            Err(SolrCoreError::UnsupportedFormatError(ResponseFormat::Csv))
+        ));
+
+        let response = core.select_raw("select", &params).await.unwrap();
+        assert_eq!(response, "id,name\n1,alice\n");
+        assert_eq!(server.requests().len(), 1);
+    }
 
-        Ok(())
+    /// Anomaly test that the HTTP status is mapped to the error before parsing the body.
+    #[tokio::test]
+    async fn test_error_from_status() {
+        let server = StubServer::start(vec![
+            response(
+                "404 Not Found",
+                &[("Content-Type", "text/html")],
+                "<html></html>",
+            ),
+            response(
+                "400 Bad Request",
+                &[],
+                r#"{"responseHeader":{"status":400,"QTime":0},"error":{"metadata":[],"msg":"undefined field text_hoge","code":400}}"#,
+            ),
+            response("503 Service Unavailable", &[], ""),
+        ]);
+        let core = SolrCore::new("example", &server.url);
+
+        assert!(matches!(
+            core.ping().await,
+            Err(SolrCoreError::NotFound { .. })
+        ));
+        match core.ping().await {
+            Err(SolrCoreError::BadRequest { info, .. }) => {
+                assert_eq!(info.msg, "undefined field text_hoge")
+            }
+            response => panic!("unexpected response: {:?}", response),
+        }
+        assert!(matches!(
+            core.ping().await,
+            Err(SolrCoreError::ServiceUnavailable { .. })
+        ));
     }
 
-    /// Method to send a request to the core to delete all existing documents.
-    pub async fn truncate(&self) -> Result<()> {
-        self.post(br#"{"delete":{"query": "*:*"}}"#.to_vec())
-            .await?;
+    /// Anomaly test that the error has the context of the request with the redacted parameters.
+    #[tokio::test]
+    async fn test_error_context() {
+        let server = StubServer::start(vec![response("404 Not Found", &[], "{}")]);
+        let core = SolrCore::new("example", &server.url).redact_params(&["q"]);
 
-        Ok(())
+        let params = vec![
+            ("q".to_string(), "name:secret".to_string()),
+            ("rows".to_string(), "10".to_string()),
+        ];
+        let error = core.select::<Value>(&params).await.unwrap_err();
+        let context = error.context().unwrap();
+
+        assert_eq!(context.core.as_deref(), Some("example"));
+        assert_eq!(context.url.path(), "/solr/example/select");
+        assert_eq!(
+            context.params(),
+            vec![
+                ("q".to_string(), "<redacted>".to_string()),
+                ("rows".to_string(), "10".to_string())
+            ]
+        );
+        assert!(!error.to_string().contains("secret"));
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use chrono::{DateTime, Utc};
-    use serde::Deserialize;
-    use serde_json::{self, Value};
+    /// Anomaly test that the error response that is not JSON is returned with the status and the body.
+    #[tokio::test]
+    async fn test_unparsable_error_response() {
+        let server = StubServer::start(vec![response(
+            "502 Bad Gateway",
+            &[("Content-Type", "text/html")],
+            "<html><body>Bad Gateway</body></html>",
+        )]);
+        let core = SolrCore::new("example", &server.url);
+
+        let response = core.ping().await;
+
+        match response {
+            Err(SolrCoreError::UnparsableResponseError {
+                status,
+                content_type,
+                body,
+                ..
+            }) => {
+                assert_eq!(status, 502);
+                assert_eq!(content_type.as_deref(), Some("text/html"));
+                assert_eq!(body, "<html><body>Bad Gateway</body></html>");
+            }
+            _ => panic!("unexpected response: {:?}", response),
+        }
+    }
 
-    /// Normal system test to get core status.
+    /// Anomaly system test of the timeout of a single operation.
     ///
     /// Run this test with the Docker container started with the following command.
     ///
@@ -283,11 +2206,16 @@ mod test {
     /// ```
     #[tokio::test]
     #[ignore]
-    async fn test_get_status() {
+    async fn test_select_with_timeout() {
         let core = SolrCore::new("example", "http://localhost:8983");
-        let status = core.status().await.unwrap();
 
-        assert_eq!(status.name, String::from("example"));
+        let params = vec![("q".to_string(), "*:*".to_string())];
+        let response = core
+            .with_timeout(Duration::from_nanos(1))
+            .select::<Document>(&params)
+            .await;
+
+        assert!(matches!(response, Err(SolrCoreError::Timeout { .. })));
     }
 
     /// Normal system test of reload of the core.
@@ -360,6 +2288,27 @@ mod test {
         assert_eq!(response.header.status, 0);
     }
 
+    /// Normal system test of the function to search documents with another request handler.
+    ///
+    /// Run this test with the Docker container started with the following command.
+    ///
+    /// ```ignore
+    /// docker run --rm -d -p 8983:8983 solr:9.1.0 solr-precreate example
+    /// ```
+    #[tokio::test]
+    #[ignore]
+    async fn test_select_with_handler() {
+        let core = SolrCore::new("example", "http://localhost:8983");
+
+        let params = vec![("q".to_string(), "*:*".to_string())];
+        let response = core
+            .select_with_handler::<Document>("/query", &params)
+            .await
+            .unwrap();
+
+        assert_eq!(response.header.status, 0);
+    }
+
     /// Anomaly system test of the function to search documents.
     ///
     /// If nonexistent field was specified, select() method will return error.
@@ -374,13 +2323,13 @@ mod test {
         assert!(response.is_err());
     }
 
-    /// Normal system test of the function to analyze the word.
-    ///
-    /// Run this test with the Docker container started with the following command.
-    ///
-    /// ```ignore
-    /// docker run --rm -d -p 8983:8983 solr:9.1.0 solr-precreate example
-    /// ```
+    // Normal system test of the function to analyze the word.
+    //
+    // Run this test with the Docker container started with the following command.
+    //
+    // ```ignore
+    // docker run --rm -d -p 8983:8983 solr:9.1.0 solr-precreate example
+    // ```
     // #[tokio::test]
     // #[ignore]
     // async fn test_analyze() {
@@ -480,7 +2429,7 @@ mod test {
         let status = core.status().await.unwrap();
 
         // Verify that 3 documents are registered.
-        assert_eq!(status.index.num_docs, 3);
+        assert_eq!(status.index.unwrap().num_docs, 3);
 
         // Test to search document
         let params = vec![
@@ -499,6 +2448,6 @@ mod test {
         core.commit(true).await.unwrap();
         let status = core.status().await.unwrap();
         // Verify that no documents in index.
-        assert_eq!(status.index.num_docs, 0);
+        assert_eq!(status.index.unwrap().num_docs, 0);
     }
 }
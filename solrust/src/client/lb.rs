@@ -0,0 +1,279 @@
+//! This module defines the LBSolrClient struct.
+//!
+//! LBSolrClient distributes the requests over multiple Solr nodes in round-robin,
+//! and sends the request to the next node when the connection to a node failed.
+
+use crate::client::api::context_root;
+use crate::client::auth::SolrAuth;
+use crate::client::circuit_breaker::CircuitBreaker;
+use crate::client::core::SolrCore;
+use crate::client::middleware::Middleware;
+use crate::client::observer::RequestObserver;
+use crate::client::retry::RetryPolicy;
+use crate::client::solr::{SolrClient, SolrClientError};
+use crate::client::transport::{Transport, TransportOptions};
+use crate::types::response::*;
+use core::time::Duration;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Request};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use url::Url;
+
+type Result<T> = std::result::Result<T, SolrClientError>;
+
+/// Selector of the node to which the request is sent, shared by the clones of the transport.
+#[derive(Clone, Debug)]
+pub(crate) struct LoadBalancer {
+    nodes: Vec<Url>,
+    next: Arc<AtomicUsize>,
+    /// Index of the dead nodes and the time when they failed.
+    dead: Arc<Mutex<HashMap<usize, Instant>>>,
+    revival_interval: Duration,
+}
+
+impl LoadBalancer {
+    pub(crate) fn new(nodes: Vec<Url>) -> Self {
+        Self {
+            nodes,
+            next: Arc::new(AtomicUsize::new(0)),
+            dead: Arc::new(Mutex::new(HashMap::new())),
+            revival_interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Return the index of the nodes in the order to try.
+    ///
+    /// The alive nodes are ordered in round-robin. The dead nodes whose revival interval has elapsed are tried first
+    /// to check whether they have recovered, and the other dead nodes are tried last as the last resort.
+    pub(crate) fn candidates(&self) -> Vec<usize> {
+        let n = self.nodes.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % n;
+        let dead = self.dead.lock().unwrap();
+
+        let mut revived = Vec::new();
+        let mut alive = Vec::new();
+        let mut rest = Vec::new();
+        for node in (0..n).map(|i| (start + i) % n) {
+            match dead.get(&node) {
+                None => alive.push(node),
+                Some(failed_at) if failed_at.elapsed() >= self.revival_interval => {
+                    revived.push(node)
+                }
+                Some(_) => rest.push(node),
+            }
+        }
+
+        revived.into_iter().chain(alive).chain(rest).collect()
+    }
+
+    /// Rewrite the request to be sent to the node.
+    pub(crate) fn route(&self, request: &mut Request, node: usize) {
+        let target = &self.nodes[node];
+        let url = request.url_mut();
+        let _ = url.set_scheme(target.scheme());
+        let _ = url.set_host(target.host_str());
+        let _ = url.set_port(target.port());
+    }
+
+    pub(crate) fn mark_dead(&self, node: usize) {
+        self.dead.lock().unwrap().insert(node, Instant::now());
+    }
+
+    pub(crate) fn mark_alive(&self, node: usize) {
+        self.dead.lock().unwrap().remove(&node);
+    }
+
+    /// Return the URLs of the nodes that are currently considered dead.
+    fn dead_nodes(&self) -> Vec<String> {
+        let dead = self.dead.lock().unwrap();
+        let mut nodes: Vec<String> = dead
+            .keys()
+            .map(|node| self.nodes[*node].origin().ascii_serialization())
+            .collect();
+        nodes.sort();
+        nodes
+    }
+}
+
+/// Client that load-balances the requests over multiple Solr nodes, the equivalent of SolrJ's LBHttpSolrClient.
+///
+/// When the connection to a node failed, the node is marked as dead and the request is sent to the next node.
+/// A dead node is tried again as a revival check once the revival interval (60 seconds by default) has elapsed,
+/// and it is marked as alive again when the request succeeds.
+///
+/// The cores created by this client share the nodes and the state of them.
+#[derive(Debug)]
+pub struct LBSolrClient {
+    pub(crate) client: SolrClient,
+}
+
+impl LBSolrClient {
+    /// Create the client with the base URLs of the nodes, e.g. `http://localhost:8983`.
+    ///
+    /// Of the URLs, the schema, hostname and port are used, and the context path of the first URL is shared by all nodes.
+    pub fn new(urls: &[&str]) -> Result<Self> {
+        Self::with_client(urls, reqwest::Client::new())
+    }
+
+    /// Create the client that sends requests with the given HTTP client.
+    pub fn with_client(urls: &[&str], client: Client) -> Result<Self> {
+        let nodes = urls
+            .iter()
+            .map(|url| {
+                let url = Url::parse(url)?;
+                match url.host_str() {
+                    Some(_) => Ok(url),
+                    None => Err(SolrClientError::InvalidHostError),
+                }
+            })
+            .collect::<Result<Vec<Url>>>()?;
+
+        let first = nodes.first().ok_or(SolrClientError::EmptyNodesError)?;
+        let url = format!(
+            "{}{}",
+            first.origin().ascii_serialization(),
+            context_root(first.path())
+        );
+
+        let mut transport = Transport::new(client);
+        transport.load_balancer = Some(LoadBalancer::new(nodes));
+
+        Ok(Self {
+            client: SolrClient::with_transport(&url, transport),
+        })
+    }
+
+    /// Set the interval after which a dead node is tried again.
+    pub fn revival_interval(mut self, interval: Duration) -> Self {
+        if let Some(lb) = self.client.transport.load_balancer.as_mut() {
+            lb.revival_interval = interval;
+        }
+        self
+    }
+
+    /// Set the authentication method attached to every request.
+    pub fn auth(mut self, auth: SolrAuth) -> Self {
+        self.client = self.client.auth(auth);
+        self
+    }
+
+    /// Set the policy to retry the idempotent requests.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.client = self.client.retry(policy);
+        self
+    }
+
+    /// Set the observer notified of every request.
+    pub fn observer(mut self, observer: impl RequestObserver + 'static) -> Self {
+        self.client = self.client.observer(observer);
+        self
+    }
+
+    /// Add the middleware executed around every request.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.client = self.client.middleware(middleware);
+        self
+    }
+
+    /// Set the names of the parameters whose values are masked in the context of the errors.
+    pub fn redact_params(mut self, names: &[&str]) -> Self {
+        self.client = self.client.redact_params(names);
+        self
+    }
+
+    /// Set the headers attached to every request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.client = self.client.default_headers(headers);
+        self
+    }
+
+    /// Set the options on how the requests are sent and how the responses are read.
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.client = self.client.transport_options(options);
+        self
+    }
+
+    /// Set the circuit breaker, which skips the nodes whose circuit is open.
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.client = self.client.circuit_breaker(breaker);
+        self
+    }
+
+    /// Return the URLs of the nodes that are currently considered dead.
+    pub fn dead_nodes(&self) -> Vec<String> {
+        match &self.client.transport.load_balancer {
+            Some(lb) => lb.dead_nodes(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Method to get the status of one of the Solr nodes.
+    pub async fn status(&self) -> Result<SolrSystemInfo> {
+        self.client.status().await
+    }
+
+    /// Method to get the list of the cores of one of the Solr nodes.
+    pub async fn cores(&self) -> Result<SolrCoreList> {
+        self.client.cores().await
+    }
+
+    /// Method to create SolrCore struct whose requests are load-balanced over the nodes.
+    pub async fn core(&self, name: &str) -> Result<SolrCore> {
+        self.client.core(name).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::testing::{response, StubServer};
+
+    const PING: &str = r#"{"responseHeader":{"status":0,"QTime":0},"status":"OK"}"#;
+
+    fn lb_core(urls: &[&str]) -> (LBSolrClient, SolrCore) {
+        let client = LBSolrClient::new(urls).unwrap();
+        let core = SolrCore::with_transport("example", urls[0], client.client.transport.clone());
+        (client, core)
+    }
+
+    #[test]
+    fn test_create_lb_client_without_nodes() {
+        let client = LBSolrClient::new(&[]);
+        assert!(matches!(client, Err(SolrClientError::EmptyNodesError)));
+    }
+
+    #[tokio::test]
+    async fn test_round_robin() {
+        let node1 = StubServer::start(vec![response("200 OK", &[], PING); 2]);
+        let node2 = StubServer::start(vec![response("200 OK", &[], PING); 2]);
+        let (_, core) = lb_core(&[&node1.url, &node2.url]);
+
+        for _ in 0..4 {
+            core.ping().await.unwrap();
+        }
+
+        assert_eq!(node1.requests().len(), 2);
+        assert_eq!(node2.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failover_on_connection_failure() {
+        // Bind and drop the listener to get the address on which the connection is refused.
+        let dead = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let node = StubServer::start(vec![response("200 OK", &[], PING); 2]);
+        let (client, core) = lb_core(&[&dead, &node.url]);
+
+        core.ping().await.unwrap();
+        assert_eq!(client.dead_nodes(), vec![dead]);
+
+        // The dead node is skipped until the revival interval elapses.
+        core.ping().await.unwrap();
+        assert_eq!(node.requests().len(), 2);
+    }
+}
@@ -0,0 +1,34 @@
+//! This module defines the log levels used by the [Logging API](https://solr.apache.org/guide/solr/latest/deployment-guide/configuring-logging.html).
+
+use std::fmt::{Display, Formatter};
+
+/// Log level of the logger of Solr.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogLevel {
+    All,
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+    Off,
+    /// Remove the level set to the logger, so that it inherits the level of the parent logger.
+    Unset,
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            LogLevel::All => write!(f, "ALL"),
+            LogLevel::Trace => write!(f, "TRACE"),
+            LogLevel::Debug => write!(f, "DEBUG"),
+            LogLevel::Info => write!(f, "INFO"),
+            LogLevel::Warn => write!(f, "WARN"),
+            LogLevel::Error => write!(f, "ERROR"),
+            LogLevel::Fatal => write!(f, "FATAL"),
+            LogLevel::Off => write!(f, "OFF"),
+            LogLevel::Unset => write!(f, "null"),
+        }
+    }
+}
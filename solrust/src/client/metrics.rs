@@ -0,0 +1,164 @@
+//! This module defines the filters of the [Metrics API](https://solr.apache.org/guide/solr/latest/deployment-guide/metrics-reporting.html#metrics-api).
+
+use std::fmt::{Display, Formatter};
+
+/// Group of the metric registries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricGroup {
+    All,
+    Jvm,
+    Jetty,
+    Node,
+    Core,
+}
+
+impl Display for MetricGroup {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            MetricGroup::All => write!(f, "all"),
+            MetricGroup::Jvm => write!(f, "jvm"),
+            MetricGroup::Jetty => write!(f, "jetty"),
+            MetricGroup::Node => write!(f, "node"),
+            MetricGroup::Core => write!(f, "core"),
+        }
+    }
+}
+
+/// Type of the metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricType {
+    All,
+    Counter,
+    Gauge,
+    Histogram,
+    Meter,
+    Timer,
+}
+
+impl Display for MetricType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            MetricType::All => write!(f, "all"),
+            MetricType::Counter => write!(f, "counter"),
+            MetricType::Gauge => write!(f, "gauge"),
+            MetricType::Histogram => write!(f, "histogram"),
+            MetricType::Meter => write!(f, "meter"),
+            MetricType::Timer => write!(f, "timer"),
+        }
+    }
+}
+
+/// Filter of the metrics returned by the Metrics API.
+///
+/// ```ignore
+/// let filter = MetricsFilter::new().group(MetricGroup::Core).prefix("CACHE.searcher");
+/// let metrics = client.metrics(&filter).await?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MetricsFilter {
+    groups: Vec<MetricGroup>,
+    types: Vec<MetricType>,
+    prefixes: Vec<String>,
+    regex: Vec<String>,
+    keys: Vec<String>,
+}
+
+impl MetricsFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the group of the registries, which is sent as `group` parameter.
+    pub fn group(mut self, group: MetricGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Add the type of the metrics, which is sent as `type` parameter.
+    pub fn metric_type(mut self, metric_type: MetricType) -> Self {
+        self.types.push(metric_type);
+        self
+    }
+
+    /// Add the prefix of the metric names, which is sent as `prefix` parameter.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefixes.push(String::from(prefix));
+        self
+    }
+
+    /// Add the regular expression matching the metric names, which is sent as `regex` parameter.
+    pub fn regex(mut self, regex: &str) -> Self {
+        self.regex.push(String::from(regex));
+        self
+    }
+
+    /// Add the fully-qualified key of the metric, e.g. `solr.jvm:memory.heap.used`, which is sent as `key` parameter.
+    ///
+    /// When the keys are given, the other filters are ignored by Solr.
+    pub fn key(mut self, key: &str) -> Self {
+        self.keys.push(String::from(key));
+        self
+    }
+
+    pub(crate) fn params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+
+        if !self.groups.is_empty() {
+            params.push((
+                String::from("group"),
+                self.groups
+                    .iter()
+                    .map(|g| g.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ));
+        }
+        if !self.types.is_empty() {
+            params.push((
+                String::from("type"),
+                self.types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ));
+        }
+        if !self.prefixes.is_empty() {
+            params.push((String::from("prefix"), self.prefixes.join(",")));
+        }
+        for regex in &self.regex {
+            params.push((String::from("regex"), regex.clone()));
+        }
+        for key in &self.keys {
+            params.push((String::from("key"), key.clone()));
+        }
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics_filter_params() {
+        let filter = MetricsFilter::new()
+            .group(MetricGroup::Jvm)
+            .group(MetricGroup::Core)
+            .metric_type(MetricType::Timer)
+            .prefix("CACHE.searcher")
+            .prefix("QUERY./select");
+
+        let expected = vec![
+            (String::from("group"), String::from("jvm,core")),
+            (String::from("type"), String::from("timer")),
+            (
+                String::from("prefix"),
+                String::from("CACHE.searcher,QUERY./select"),
+            ),
+        ];
+
+        assert_eq!(filter.params(), expected);
+    }
+}
@@ -0,0 +1,119 @@
+//! This module defines the middleware executed around each request to Solr.
+//!
+//! The middleware can inspect and modify the request before it is sent, e.g. to add headers, sign the request
+//! or change the parameters, and inspect the response before it is returned.
+
+use crate::client::transport::TransportError;
+use reqwest::{Client, Request, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Future returned by the middleware.
+pub type MiddlewareFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Response, TransportError>> + Send + 'a>>;
+
+/// Middleware executed around each request.
+///
+/// The middleware is called for every attempt of the request, after the node has been chosen and the credentials
+/// have been attached, and calls [`Next::run`] to pass the request to the next middleware or to send it.
+///
+/// ```ignore
+/// struct RequestIdHeader;
+///
+/// impl Middleware for RequestIdHeader {
+///     fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+///         request.headers_mut().insert("X-Request-Id", HeaderValue::from_static("42"));
+///         next.run(request)
+///     }
+/// }
+///
+/// let core = SolrCore::new("example", "http://localhost:8983").middleware(RequestIdHeader);
+/// ```
+pub trait Middleware: Send + Sync {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// The rest of the middleware chain.
+pub struct Next<'a> {
+    client: &'a Client,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a Client, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    /// Pass the request to the next middleware, or send it if this is the end of the chain.
+    pub fn run(self, request: Request) -> MiddlewareFuture<'a> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    client: self.client,
+                    middlewares: rest,
+                },
+            ),
+            None => {
+                let client = self.client;
+                Box::pin(async move { Ok(client.execute(request).await?) })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::core::SolrCore;
+    use crate::client::testing::{response, StubServer};
+    use reqwest::header::HeaderValue;
+
+    struct Header(&'static str);
+
+    impl Middleware for Header {
+        fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+            request
+                .headers_mut()
+                .append("x-trace", HeaderValue::from_static(self.0));
+            next.run(request)
+        }
+    }
+
+    struct Param;
+
+    impl Middleware for Param {
+        fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .append_pair("wt", "json");
+            next.run(request)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_chain() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"status":"OK"}"#,
+        )]);
+        let core = SolrCore::new("example", &server.url)
+            .middleware(Header("first"))
+            .middleware(Param)
+            .middleware(Header("second"));
+
+        core.ping().await.unwrap();
+
+        let request = &server.requests()[0];
+        assert!(request.starts_with("GET /solr/example/admin/ping?wt=json "));
+        let first = request.find("x-trace: first").unwrap();
+        let second = request.find("x-trace: second").unwrap();
+        assert!(first < second);
+    }
+}
@@ -0,0 +1,55 @@
+//! This module defines the observer notified of the requests to Solr.
+//!
+//! The observer is the hook to export the metrics of the requests, e.g. to Prometheus, or to log them.
+
+use reqwest::Method;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Request notified to the observer.
+#[derive(Clone, Debug)]
+pub struct RequestInfo {
+    pub method: Method,
+    pub url: Url,
+}
+
+/// Outcome of the request notified to the observer.
+#[derive(Clone, Debug)]
+pub struct RequestOutcome {
+    /// Time elapsed from the start of the request until the response body is read, including the retries.
+    pub duration: Duration,
+    /// HTTP status code of the response. None if no response was received.
+    pub status: Option<u16>,
+    /// `QTime` in the response header, the time Solr spent to process the request in milliseconds.
+    pub qtime: Option<u32>,
+}
+
+/// Observer notified at the start and the end of each request.
+///
+/// ```ignore
+/// struct LatencyLogger;
+///
+/// impl RequestObserver for LatencyLogger {
+///     fn on_request_end(&self, request: &RequestInfo, outcome: &RequestOutcome) {
+///         tracing::info!("{} {} took {:?}", request.method, request.url, outcome.duration);
+///     }
+/// }
+///
+/// let client = SolrClient::from_url("http://localhost:8983")?.observer(LatencyLogger);
+/// ```
+pub trait RequestObserver: Send + Sync {
+    fn on_request_start(&self, _request: &RequestInfo) {}
+    fn on_request_end(&self, _request: &RequestInfo, _outcome: &RequestOutcome) {}
+}
+
+/// The observer shared with the caller, e.g. to read the metrics collected by it.
+impl<T: RequestObserver + ?Sized> RequestObserver for Arc<T> {
+    fn on_request_start(&self, request: &RequestInfo) {
+        (**self).on_request_start(request)
+    }
+
+    fn on_request_end(&self, request: &RequestInfo, outcome: &RequestOutcome) {
+        (**self).on_request_end(request, outcome)
+    }
+}
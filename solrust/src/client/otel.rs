@@ -0,0 +1,263 @@
+//! This module defines the middleware that propagates the [W3C Trace Context](https://www.w3.org/TR/trace-context/)
+//! to Solr with `traceparent` header, so that the spans of Solr's OpenTelemetry module join the trace of the application.
+//!
+//! The context is read from the current span with [`tracing_opentelemetry`], so the application needs
+//! `tracing_opentelemetry::layer()` in its subscriber.
+//!
+//! With this feature, the `solr_request` span also records the status code and `QTime` of the response,
+//! as `http.response.status_code` and `solr.qtime` fields.
+
+use crate::client::middleware::{Middleware, MiddlewareFuture, Next};
+use opentelemetry::trace::TraceContextExt;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Request;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Name of the header of the trace context.
+pub const TRACEPARENT_HEADER: HeaderName = HeaderName::from_static("traceparent");
+
+/// Trace context in the `traceparent` header, e.g. `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    /// ID of the span of the caller, which becomes the parent of the span of Solr.
+    pub parent_id: u64,
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Return the context of the current span of [`tracing`], or None if it is not in an OpenTelemetry trace.
+    pub fn current() -> Option<Self> {
+        let context = tracing::Span::current().context();
+        let span = context.span();
+        let span_context = span.span_context();
+        if !span_context.is_valid() {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: u128::from_be_bytes(span_context.trace_id().to_bytes()),
+            parent_id: u64::from_be_bytes(span_context.span_id().to_bytes()),
+            sampled: span_context.is_sampled(),
+        })
+    }
+
+    /// Parse the value of `traceparent` header. Return None if it is not valid, e.g. the IDs are all zero.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let parts: Vec<&str> = traceparent.trim().split('-').collect();
+        let (version, trace_id, parent_id, flags) = match parts.as_slice() {
+            [version, trace_id, parent_id, flags, ..] => (*version, *trace_id, *parent_id, *flags),
+            _ => return None,
+        };
+        // Version ff is invalid, and the version 00 must not have the extra fields.
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.len() != 4) {
+            return None;
+        }
+        if trace_id.len() != 32 || parent_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+
+        let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+        let parent_id = u64::from_str_radix(parent_id, 16).ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        if trace_id == 0 || parent_id == 0 {
+            return None;
+        }
+
+        Some(Self {
+            trace_id,
+            parent_id,
+            sampled: flags & 1 == 1,
+        })
+    }
+
+    /// Return the trace ID in the hexadecimal format.
+    pub fn trace_id(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+}
+
+impl Display for TraceContext {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.parent_id, self.sampled as u8
+        )
+    }
+}
+
+type TraceparentProvider = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+/// Middleware that attaches `traceparent` header to every request without the header, e.g.
+///
+/// ```ignore
+/// let core = SolrCore::new("example", "http://localhost:8983").middleware(TraceContextPropagator::new());
+/// ```
+///
+/// The context is taken from the current span, which is the child of the span of the application
+/// with `tracing_opentelemetry::layer()`, or from the provider if it is given.
+/// When there is no context, the header is not sent, so that Solr neither samples the request nor starts an orphan trace.
+/// The trace ID is recorded in the `trace_id` field of the `solr_request` span to correlate the logs.
+#[derive(Clone, Default)]
+pub struct TraceContextPropagator {
+    provider: Option<TraceparentProvider>,
+}
+
+impl TraceContextPropagator {
+    /// Create the propagator that propagates the context of the current span.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the propagator with the provider of `traceparent`, e.g. for the application that does not use [`tracing`].
+    pub fn with_provider(provider: impl Fn() -> Option<String> + Send + Sync + 'static) -> Self {
+        Self {
+            provider: Some(Arc::new(provider)),
+        }
+    }
+
+    /// Return the context of the request, from the provider or the current span.
+    fn context(&self) -> Option<TraceContext> {
+        match &self.provider {
+            Some(provider) => provider().and_then(|traceparent| TraceContext::parse(&traceparent)),
+            None => TraceContext::current(),
+        }
+    }
+}
+
+impl Middleware for TraceContextPropagator {
+    fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+        let context = match request
+            .headers()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(traceparent) => TraceContext::parse(traceparent),
+            None => {
+                let context = self.context();
+                if let Some(value) =
+                    context.and_then(|c| HeaderValue::from_str(&c.to_string()).ok())
+                {
+                    request.headers_mut().insert(TRACEPARENT_HEADER, value);
+                }
+                context
+            }
+        };
+        if let Some(context) = context {
+            tracing::Span::current().record("trace_id", context.trace_id().as_str());
+        }
+
+        next.run(request)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::core::SolrCore;
+    use crate::client::testing::{response, StubServer};
+
+    #[test]
+    fn test_parse_traceparent() {
+        let context =
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+
+        assert_eq!(context.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(context.parent_id, 0x00f067aa0ba902b7);
+        assert!(context.sampled);
+        assert_eq!(
+            context.to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_traceparent() {
+        for traceparent in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e473g-00f067aa0ba902b7-01",
+        ] {
+            assert!(
+                TraceContext::parse(traceparent).is_none(),
+                "{}",
+                traceparent
+            );
+        }
+    }
+
+    fn ok() -> String {
+        response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"status":"OK"}"#,
+        )
+    }
+
+    fn traceparent(request: &str) -> Option<&str> {
+        request
+            .lines()
+            .find_map(|line| line.strip_prefix("traceparent: "))
+    }
+
+    #[tokio::test]
+    async fn test_propagate_trace_context_from_provider() {
+        let server = StubServer::start(vec![ok(), ok()]);
+
+        SolrCore::new("example", &server.url)
+            .middleware(TraceContextPropagator::with_provider(|| {
+                Some(String::from(
+                    "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                ))
+            }))
+            .ping()
+            .await
+            .unwrap();
+        SolrCore::new("example", &server.url)
+            .middleware(TraceContextPropagator::with_provider(|| None))
+            .ping()
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(
+            traceparent(&requests[0]),
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        );
+        assert_eq!(traceparent(&requests[1]), None);
+    }
+
+    #[tokio::test]
+    async fn test_propagate_trace_context_of_current_span() {
+        use opentelemetry::trace::TracerProvider as _;
+        use tracing::Instrument;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let server = StubServer::start(vec![ok(), ok()]);
+        let core = SolrCore::new("example", &server.url).middleware(TraceContextPropagator::new());
+
+        // Without the OpenTelemetry layer, there is no trace to join.
+        core.ping().await.unwrap();
+
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let span = tracing::info_span!("search");
+        let trace_id = span.context().span().span_context().trace_id();
+        core.ping().instrument(span).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(traceparent(&requests[0]), None);
+        let context = TraceContext::parse(traceparent(&requests[1]).unwrap()).unwrap();
+        assert_eq!(context.trace_id(), trace_id.to_string());
+        assert!(context.sampled);
+    }
+}
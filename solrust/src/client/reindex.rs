@@ -0,0 +1,592 @@
+//! This module defines the helper of the blue/green reindexing, which indexes the documents into the shadow core or collection
+//! and switches the live one to it after the validation, so that the searches are never served by the half-built index.
+
+use crate::client::cloud::{CreateAlias, CreateCollection, SolrCloudClient};
+use crate::client::core::{SolrCore, SolrCoreError};
+use crate::client::solr::{SolrClient, SolrClientError};
+use crate::client::updater::{BufferedUpdater, BufferedUpdaterError};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use thiserror::Error;
+
+type Result<T> = std::result::Result<T, ReindexError>;
+
+#[derive(Debug, Error)]
+pub enum ReindexError {
+    #[error("Failed to operate the cores or the collections")]
+    ClientError(#[from] SolrClientError),
+    #[error("Failed to operate the shadow core")]
+    CoreError(#[from] SolrCoreError),
+    #[error("Failed to index the documents")]
+    UpdaterError(#[from] BufferedUpdaterError),
+    #[error("Shadow has {actual} documents, but {expected} documents are added")]
+    CountMismatch { expected: u64, actual: u64 },
+    #[error("Shadow has {shadow} documents, which is too few compared to {live} documents of the live one")]
+    TooFewDocuments { live: u64, shadow: u64 },
+    #[error("Shadow {shadow} is the live one, which must not be truncated")]
+    ShadowIsLive { shadow: String },
+}
+
+/// Progress of the reindexing notified to the callback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReindexProgress {
+    /// The shadow is created or truncated.
+    Prepared,
+    /// The documents are queued to be indexed, notified every batch.
+    Added { documents: u64 },
+    /// The documents are committed and counted in the shadow.
+    Committed { documents: u64 },
+    /// The live one is switched to the shadow.
+    Switched,
+}
+
+type ProgressCallback = Arc<dyn Fn(&ReindexProgress) + Send + Sync>;
+
+/// How the shadow is prepared and switched to.
+enum Target<'a> {
+    /// Swap the standalone cores with the CoreAdmin API.
+    Cores {
+        client: &'a SolrClient,
+        live: String,
+        shadow: String,
+    },
+    /// Flip the alias of SolrCloud to the shadow collection.
+    Collections {
+        client: &'a SolrCloudClient,
+        alias: String,
+        shadow: CreateCollection,
+    },
+}
+
+impl Target<'_> {
+    fn live_core(&self) -> SolrCore {
+        match self {
+            Target::Cores { client, live, .. } => client.bind_core(live),
+            Target::Collections { client, alias, .. } => client.collection(alias),
+        }
+    }
+
+    fn shadow_core(&self) -> SolrCore {
+        match self {
+            Target::Cores { client, shadow, .. } => client.bind_core(shadow),
+            Target::Collections { client, shadow, .. } => client.collection(shadow.name()),
+        }
+    }
+
+    /// Create the shadow collection if it does not exist, or delete all documents of the existing shadow.
+    ///
+    /// The shadow served as the live one, e.g. by the second run with the same shadow collection, is refused.
+    async fn prepare(&self) -> Result<SolrCore> {
+        let core = match self {
+            Target::Cores {
+                client,
+                live,
+                shadow,
+            } => {
+                if live == shadow {
+                    return Err(ReindexError::ShadowIsLive {
+                        shadow: shadow.clone(),
+                    });
+                }
+                client.core(shadow).await?
+            }
+            Target::Collections {
+                client,
+                alias,
+                shadow,
+            } => {
+                let aliases = client.list_aliases().await?;
+                if alias == shadow.name()
+                    || aliases
+                        .collections(alias)
+                        .iter()
+                        .any(|name| name == shadow.name())
+                {
+                    return Err(ReindexError::ShadowIsLive {
+                        shadow: String::from(shadow.name()),
+                    });
+                }
+
+                let collections = client.list_collections().await?;
+                if !collections
+                    .collections
+                    .iter()
+                    .any(|name| name == shadow.name())
+                {
+                    client.create_collection(shadow).await?;
+                    return Ok(self.shadow_core());
+                }
+                self.shadow_core()
+            }
+        };
+        core.truncate().await?;
+
+        Ok(core)
+    }
+
+    async fn switch(&self) -> Result<()> {
+        match self {
+            Target::Cores {
+                client,
+                live,
+                shadow,
+            } => {
+                client.swap_cores(live, shadow).await?;
+            }
+            Target::Collections {
+                client,
+                alias,
+                shadow,
+            } => {
+                client
+                    .create_alias(&CreateAlias::new(alias, &[shadow.name()]))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder of the blue/green reindexing, e.g.
+///
+/// ```ignore
+/// let mut reindex = ReindexBuilder::collections(&cloud, "products", &CreateCollection::new("products_v2").config_name("products"))
+///     .min_ratio(0.9)
+///     .on_progress(|progress| tracing::info!("{:?}", progress))
+///     .start()
+///     .await?;
+/// for document in documents {
+///     reindex.add(&document).await?;
+/// }
+/// reindex.finish().await?;
+/// ```
+pub struct ReindexBuilder<'a> {
+    target: Target<'a>,
+    batch_size: usize,
+    min_ratio: Option<f64>,
+    exact_count: bool,
+    progress: Option<ProgressCallback>,
+}
+
+impl<'a> ReindexBuilder<'a> {
+    /// Reindex into the existing `shadow` core, which is truncated first, and swap it with the `live` core.
+    ///
+    /// After the swap, `shadow` serves the previous index, which is kept for the rollback.
+    pub fn cores(client: &'a SolrClient, live: &str, shadow: &str) -> Self {
+        Self::new(Target::Cores {
+            client,
+            live: String::from(live),
+            shadow: String::from(shadow),
+        })
+    }
+
+    /// Reindex into the `shadow` collection, which is created if it does not exist or truncated otherwise,
+    /// and point the `alias` to it.
+    ///
+    /// The collection previously pointed to by the alias is not deleted.
+    pub fn collections(
+        client: &'a SolrCloudClient,
+        alias: &str,
+        shadow: &CreateCollection,
+    ) -> Self {
+        Self::new(Target::Collections {
+            client,
+            alias: String::from(alias),
+            shadow: shadow.clone(),
+        })
+    }
+
+    fn new(target: Target<'a>) -> Self {
+        Self {
+            target,
+            batch_size: 1000,
+            min_ratio: None,
+            exact_count: false,
+            progress: None,
+        }
+    }
+
+    /// Set the number of the documents posted in a request.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Refuse to switch when the shadow has fewer documents than the ratio of those of the live one,
+    /// e.g. 0.9 rejects the index that lost more than 10% of the documents.
+    ///
+    /// The check is skipped when the live one does not exist yet.
+    pub fn min_ratio(mut self, ratio: f64) -> Self {
+        self.min_ratio = Some(ratio);
+        self
+    }
+
+    /// Refuse to switch unless the shadow has exactly as many documents as added.
+    ///
+    /// By default, the shadow may have fewer documents, because the documents with the same unique key overwrite each other.
+    /// Enable it only when the unique keys of the documents are known to be distinct.
+    pub fn exact_count(mut self, exact: bool) -> Self {
+        self.exact_count = exact;
+        self
+    }
+
+    /// Set the callback notified of the progress.
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(&ReindexProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Prepare the shadow and start indexing into it.
+    pub async fn start(self) -> Result<Reindex<'a>> {
+        let shadow = self.target.prepare().await?;
+        let updater = BufferedUpdater::builder(shadow.clone())
+            .max_documents(self.batch_size)
+            .flush_interval(None)
+            .build();
+
+        let reindex = Reindex {
+            target: self.target,
+            shadow,
+            updater,
+            batch_size: self.batch_size as u64,
+            min_ratio: self.min_ratio,
+            exact_count: self.exact_count,
+            progress: self.progress,
+            added: 0,
+        };
+        reindex.notify(ReindexProgress::Prepared);
+
+        Ok(reindex)
+    }
+}
+
+/// Reindexing in progress, started by [`ReindexBuilder::start`].
+///
+/// Dropping it without calling [`Reindex::finish`] leaves the live one as it is.
+pub struct Reindex<'a> {
+    target: Target<'a>,
+    shadow: SolrCore,
+    updater: BufferedUpdater,
+    batch_size: u64,
+    min_ratio: Option<f64>,
+    exact_count: bool,
+    progress: Option<ProgressCallback>,
+    added: u64,
+}
+
+impl Reindex<'_> {
+    /// Queue the document to be indexed into the shadow.
+    pub async fn add(&mut self, document: &impl Serialize) -> Result<()> {
+        self.updater.add(document).await?;
+        self.added += 1;
+        if self.added.is_multiple_of(self.batch_size) {
+            self.notify(ReindexProgress::Added {
+                documents: self.added,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Index the remaining documents, commit and validate the shadow, and switch the live one to it.
+    ///
+    /// Return the number of the documents in the new index.
+    pub async fn finish(self) -> Result<u64> {
+        let Reindex {
+            target,
+            shadow,
+            updater,
+            batch_size,
+            min_ratio,
+            exact_count,
+            progress,
+            added,
+        } = self;
+        let notify = |p: ReindexProgress| {
+            if let Some(callback) = &progress {
+                callback(&p);
+            }
+        };
+
+        updater.close().await?;
+        if !added.is_multiple_of(batch_size) {
+            notify(ReindexProgress::Added { documents: added });
+        }
+
+        shadow.commit(false).await?;
+        let documents = count(&shadow).await?;
+        notify(ReindexProgress::Committed { documents });
+
+        // The shadow having more documents than added means that it is updated by others.
+        if documents > added || (exact_count && documents != added) {
+            return Err(ReindexError::CountMismatch {
+                expected: added,
+                actual: documents,
+            });
+        }
+        if let Some(ratio) = min_ratio {
+            match count(&target.live_core()).await {
+                Ok(live) if (documents as f64) < live as f64 * ratio => {
+                    return Err(ReindexError::TooFewDocuments {
+                        live,
+                        shadow: documents,
+                    });
+                }
+                Ok(_) | Err(SolrCoreError::NotFound { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        target.switch().await?;
+        notify(ReindexProgress::Switched);
+
+        Ok(documents)
+    }
+
+    fn notify(&self, progress: ReindexProgress) {
+        if let Some(callback) = &self.progress {
+            callback(&progress);
+        }
+    }
+}
+
+/// Count the documents of the core.
+async fn count(core: &SolrCore) -> std::result::Result<u64, SolrCoreError> {
+    let response = core
+        .select::<Value>(&vec![("q", "*:*"), ("rows", "0")])
+        .await?;
+
+    Ok(response.response.num_found)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::testing::{core_list, response, StubServer};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn ok() -> String {
+        response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":1}}"#,
+        )
+    }
+
+    fn num_found(n: u64) -> String {
+        response(
+            "200 OK",
+            &[],
+            &format!(
+                r#"{{"responseHeader":{{"status":0,"QTime":1}},"response":{{"numFound":{},"start":0,"numFoundExact":true,"docs":[]}}}}"#,
+                n
+            ),
+        )
+    }
+
+    fn aliases() -> String {
+        response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":1},"aliases":{"products":"products_v1"}}"#,
+        )
+    }
+
+    /// Normal test to reindex into the shadow core and swap it with the live core.
+    #[tokio::test]
+    async fn test_reindex_cores() {
+        let server = StubServer::start(vec![
+            core_list(&["books", "books_shadow"]),
+            ok(),
+            ok(),
+            ok(),
+            num_found(2),
+            num_found(2),
+            ok(),
+        ]);
+        let client = SolrClient::from_url(&server.url).unwrap();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorded = progress.clone();
+
+        let mut reindex = ReindexBuilder::cores(&client, "books", "books_shadow")
+            .min_ratio(0.9)
+            .on_progress(move |p| recorded.lock().unwrap().push(p.clone()))
+            .start()
+            .await
+            .unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+        reindex.add(&json!({"id": "002"})).await.unwrap();
+        assert_eq!(reindex.finish().await.unwrap(), 2);
+
+        let requests = server.requests();
+        assert!(requests[1].starts_with("POST /solr/books_shadow/update "));
+        assert!(requests[1].ends_with(r#"{"delete":{"query": "*:*"}}"#));
+        assert!(requests[2].ends_with(r#"[{"id":"001"},{"id":"002"}]"#));
+        assert!(requests[3].ends_with(r#"{"commit": {}}"#));
+        assert!(requests[4].starts_with("GET /solr/books_shadow/select?"));
+        assert!(requests[5].starts_with("GET /solr/books/select?"));
+        assert!(requests[6].starts_with(
+            "GET /solr/admin/cores?action=SWAP&wt=json&core=books&other=books_shadow "
+        ));
+        assert_eq!(
+            *progress.lock().unwrap(),
+            vec![
+                ReindexProgress::Prepared,
+                ReindexProgress::Added { documents: 2 },
+                ReindexProgress::Committed { documents: 2 },
+                ReindexProgress::Switched,
+            ]
+        );
+    }
+
+    /// Normal test to reindex into the new collection and flip the alias to it.
+    #[tokio::test]
+    async fn test_reindex_collections() {
+        let server = StubServer::start(vec![
+            aliases(),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":1},"collections":["products_v1"]}"#,
+            ),
+            ok(),
+            ok(),
+            ok(),
+            num_found(1),
+            ok(),
+        ]);
+        let cloud = SolrCloudClient::from_url(&server.url).unwrap();
+
+        let mut reindex = ReindexBuilder::collections(
+            &cloud,
+            "products",
+            &CreateCollection::new("products_v2").config_name("products"),
+        )
+        .start()
+        .await
+        .unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+        assert_eq!(reindex.finish().await.unwrap(), 1);
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /solr/admin/collections?action=LISTALIASES "));
+        assert!(requests[2].starts_with(
+            "GET /solr/admin/collections?action=CREATE&name=products_v2&collection.configName=products "
+        ));
+        assert!(requests[3].starts_with("POST /solr/products_v2/update "));
+        assert!(requests[6].starts_with(
+            "GET /solr/admin/collections?action=CREATEALIAS&name=products&collections=products_v2 "
+        ));
+    }
+
+    /// Abnormal test that the collection pointed to by the alias is not truncated as the shadow.
+    #[tokio::test]
+    async fn test_reindex_into_live_collection() {
+        let server = StubServer::start(vec![aliases()]);
+        let cloud = SolrCloudClient::from_url(&server.url).unwrap();
+
+        let result = ReindexBuilder::collections(
+            &cloud,
+            "products",
+            &CreateCollection::new("products_v1").config_name("products"),
+        )
+        .start()
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ReindexError::ShadowIsLive { shadow }) if shadow == "products_v1"
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    /// Normal test that the documents with the duplicate unique keys do not prevent the switch.
+    #[tokio::test]
+    async fn test_reindex_with_duplicate_keys() {
+        let server = StubServer::start(vec![
+            core_list(&["books", "books_shadow"]),
+            ok(),
+            ok(),
+            ok(),
+            num_found(1),
+            ok(),
+        ]);
+        let client = SolrClient::from_url(&server.url).unwrap();
+
+        let mut reindex = ReindexBuilder::cores(&client, "books", "books_shadow")
+            .start()
+            .await
+            .unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+
+        assert_eq!(reindex.finish().await.unwrap(), 1);
+        assert_eq!(server.requests().len(), 6);
+    }
+
+    /// Abnormal test that the live one is not switched when the count differs with the exact count check.
+    #[tokio::test]
+    async fn test_reindex_with_exact_count() {
+        let server = StubServer::start(vec![
+            core_list(&["books", "books_shadow"]),
+            ok(),
+            ok(),
+            ok(),
+            num_found(1),
+        ]);
+        let client = SolrClient::from_url(&server.url).unwrap();
+
+        let mut reindex = ReindexBuilder::cores(&client, "books", "books_shadow")
+            .exact_count(true)
+            .start()
+            .await
+            .unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+
+        assert!(matches!(
+            reindex.finish().await,
+            Err(ReindexError::CountMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    /// Abnormal test that the live one is not switched when the shadow has too few documents.
+    #[tokio::test]
+    async fn test_reindex_with_too_few_documents() {
+        let server = StubServer::start(vec![
+            core_list(&["books", "books_shadow"]),
+            ok(),
+            ok(),
+            ok(),
+            num_found(1),
+            num_found(100),
+        ]);
+        let client = SolrClient::from_url(&server.url).unwrap();
+
+        let mut reindex = ReindexBuilder::cores(&client, "books", "books_shadow")
+            .min_ratio(0.9)
+            .start()
+            .await
+            .unwrap();
+        reindex.add(&json!({"id": "001"})).await.unwrap();
+
+        assert!(matches!(
+            reindex.finish().await,
+            Err(ReindexError::TooFewDocuments {
+                live: 100,
+                shadow: 1
+            })
+        ));
+        assert_eq!(server.requests().len(), 6);
+    }
+}
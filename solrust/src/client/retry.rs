@@ -0,0 +1,216 @@
+//! This module defines the retry policy of the requests to Solr.
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use std::time::Duration;
+
+/// Policy to retry the idempotent requests, which only read the state of Solr (e.g. select, ping and status), with exponential backoff and jitter.
+///
+/// A request is retried when the connection failed, or when Solr responded with one of the retryable status codes.
+/// If the response has `Retry-After` header, the delay given by the header is used instead of the backoff.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    retry_on_connect: bool,
+    retry_on_timeout: bool,
+    retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Create the policy with 3 attempts, 100 milliseconds of initial backoff, and retries on connection errors and 502/503/504.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retry_on_connect: true,
+            retry_on_timeout: false,
+            retry_statuses: vec![
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+
+    /// Set the maximum number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the backoff before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper limit of the backoff, which also limits the delay given by `Retry-After` header.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Set the factor by which the backoff is multiplied on each retry.
+    ///
+    /// The factor that is not finite or is less than 1.0 is replaced with 1.0, the constant backoff.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = if multiplier.is_finite() {
+            multiplier.max(1.0)
+        } else {
+            1.0
+        };
+        self
+    }
+
+    /// Set whether to randomize the backoff between zero and the computed value (full jitter).
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set whether to retry when the connection to Solr failed.
+    pub fn retry_on_connect(mut self, retry: bool) -> Self {
+        self.retry_on_connect = retry;
+        self
+    }
+
+    /// Set whether to retry when the request timed out.
+    pub fn retry_on_timeout(mut self, retry: bool) -> Self {
+        self.retry_on_timeout = retry;
+        self
+    }
+
+    /// Set the status codes of the responses to retry.
+    pub fn retry_statuses(mut self, statuses: &[StatusCode]) -> Self {
+        self.retry_statuses = statuses.to_vec();
+        self
+    }
+
+    /// Return true if another attempt is allowed after the given number of attempts.
+    pub(crate) fn has_attempts_left(&self, attempts: u32) -> bool {
+        attempts < self.max_attempts
+    }
+
+    /// Return true if the request should be retried after the error.
+    pub(crate) fn should_retry_error(&self, error: &reqwest::Error) -> bool {
+        (self.retry_on_connect && error.is_connect())
+            || (self.retry_on_timeout && error.is_timeout())
+    }
+
+    /// Return true if the request should be retried after the response.
+    pub(crate) fn should_retry_response(&self, response: &Response) -> bool {
+        self.retry_statuses.contains(&response.status())
+    }
+
+    /// Return the delay before the next attempt, following the given number of attempts.
+    pub(crate) fn backoff(&self, attempts: u32) -> Duration {
+        // The backoff is computed in f64 and clamped before it is converted, because it overflows Duration
+        // after dozens of attempts.
+        let exponent = attempts.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let factor = self.multiplier.powi(exponent).min(f64::MAX);
+        let backoff = self.initial_backoff.as_secs_f64() * factor;
+        let backoff = if backoff < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(backoff)
+        } else {
+            self.max_backoff
+        };
+
+        if self.jitter {
+            backoff.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+        } else {
+            backoff
+        }
+    }
+
+    /// Return the delay given by `Retry-After` header of the response, in seconds or in HTTP date.
+    pub(crate) fn retry_after(&self, response: &Response) -> Option<Duration> {
+        let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+
+        let delay = match value.trim().parse::<u64>() {
+            Ok(seconds) => Duration::from_secs(seconds),
+            Err(_) => {
+                let date = DateTime::parse_from_rfc2822(value).ok()?;
+                (date.with_timezone(&Utc) - Utc::now())
+                    .to_std()
+                    .unwrap_or(Duration::ZERO)
+            }
+        };
+
+        Some(delay.min(self.max_backoff))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(300))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_backoff_with_jitter() {
+        let policy = RetryPolicy::new().initial_backoff(Duration::from_millis(100));
+
+        for attempts in 1..10 {
+            assert!(
+                policy.backoff(attempts) <= Duration::from_millis(100 * 2u64.pow(attempts - 1))
+            );
+        }
+    }
+
+    #[test]
+    fn test_backoff_after_many_attempts() {
+        let policy = RetryPolicy::new()
+            .max_attempts(100)
+            .max_backoff(Duration::from_secs(10))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(68), Duration::from_secs(10));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_invalid_multiplier() {
+        for multiplier in [-2.0, 0.5, f64::NAN, f64::INFINITY] {
+            let policy = RetryPolicy::new()
+                .initial_backoff(Duration::from_millis(100))
+                .multiplier(multiplier)
+                .jitter(false);
+
+            assert_eq!(policy.backoff(1), Duration::from_millis(100));
+            assert_eq!(policy.backoff(5), Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let policy = RetryPolicy::new().max_attempts(2);
+
+        assert!(policy.has_attempts_left(1));
+        assert!(!policy.has_attempts_left(2));
+    }
+}
@@ -0,0 +1,414 @@
+//! This module defines the CloudRouter struct.
+//!
+//! CloudRouter uses the cluster state of SolrCloud to send the documents directly to the leader of the shard
+//! to which they belong, and to distribute the queries across the active replicas of the collection.
+
+use crate::client::core::{SolrCore, SolrCoreError};
+use crate::client::transport::Transport;
+use crate::types::response::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Compute MurmurHash3 (x86, 32 bit) of the data, which Solr uses to route the documents.
+pub fn murmur3_x86_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut hash = seed;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut k = 0u32;
+        for (i, byte) in tail.iter().enumerate() {
+            k |= (*byte as u32) << (8 * i);
+        }
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+    }
+
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+    hash
+}
+
+/// Return the mask of the given number of the upper bits.
+fn upper_mask(bits: u32) -> u32 {
+    match bits.min(32) {
+        0 => 0,
+        bits => u32::MAX << (32 - bits),
+    }
+}
+
+/// Compute the hash of the route key in the same way as the `compositeId` router.
+///
+/// The key may have up to two prefixes separated by `!`, e.g. `tenant!id` or `tenant!user!id`,
+/// and each prefix may specify the number of the bits taken from it, e.g. `tenant/4!id`.
+pub fn composite_id_hash(key: &str) -> i32 {
+    let parts: Vec<&str> = key.splitn(3, '!').collect();
+    if parts.len() == 1 {
+        return murmur3_x86_32(key.as_bytes(), 0) as i32;
+    }
+
+    let split = |part: &str, default: u32| -> (u32, u32) {
+        let (prefix, bits) = match part.rsplit_once('/') {
+            Some((prefix, bits)) => (prefix, bits.parse::<u32>().unwrap_or(default)),
+            None => (part, default),
+        };
+        (murmur3_x86_32(prefix.as_bytes(), 0), bits)
+    };
+
+    let hash = if parts.len() == 2 {
+        let (first, bits) = split(parts[0], 16);
+        let mask = upper_mask(bits);
+        (first & mask) | (murmur3_x86_32(parts[1].as_bytes(), 0) & !mask)
+    } else {
+        let (first, bits1) = split(parts[0], 8);
+        let (second, bits2) = split(parts[1], 8);
+        let mask1 = upper_mask(bits1);
+        let mask2 = upper_mask(bits2) >> bits1.min(31);
+        let mask3 = !(mask1 | mask2);
+        (first & mask1) | (second & mask2) | (murmur3_x86_32(parts[2].as_bytes(), 0) & mask3)
+    };
+
+    hash as i32
+}
+
+/// Return true if the hash is in the range of the shard, e.g. `80000000-ffffffff`.
+fn in_range(range: &str, hash: i32) -> bool {
+    let parse = |value: &str| {
+        u32::from_str_radix(value, 16)
+            .ok()
+            .map(|value| value as i32)
+    };
+
+    match range.split_once('-') {
+        Some((min, max)) => match (parse(min), parse(max)) {
+            (Some(min), Some(max)) => min <= hash && hash <= max,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+/// Router that sends the requests of a collection directly to the replicas in charge of them.
+///
+/// The cluster state is fetched when the router is created by
+/// [`SolrCloudClient::router`](crate::client::cloud::SolrCloudClient::router), and can be reloaded with
+/// [`SolrCloudClient::refresh_router`](crate::client::cloud::SolrCloudClient::refresh_router) after the cluster has changed.
+#[derive(Clone, Debug)]
+pub struct CloudRouter {
+    collection: String,
+    state: Arc<RwLock<SolrCollectionState>>,
+    transport: Transport,
+    next: Arc<AtomicUsize>,
+}
+
+impl CloudRouter {
+    pub(crate) fn new(
+        collection: &str,
+        state: SolrCollectionState,
+        mut transport: Transport,
+    ) -> Self {
+        // The requests are sent to the nodes chosen by the router, not by the load balancer.
+        transport.load_balancer = None;
+
+        Self {
+            collection: String::from(collection),
+            state: Arc::new(RwLock::new(state)),
+            transport,
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub(crate) fn update(&self, state: SolrCollectionState) {
+        *self.state.write().unwrap() = state;
+    }
+
+    /// Return the name of the collection.
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    /// Return the name of the shard to which the document with the route key belongs.
+    ///
+    /// With the `implicit` router, the route key is the name of the shard itself.
+    pub fn shard_for(&self, route_key: &str) -> Option<String> {
+        let state = self.state.read().unwrap();
+
+        let implicit = state
+            .router
+            .as_ref()
+            .map(|router| router.name == "implicit")
+            .unwrap_or(false);
+        if implicit {
+            return state
+                .shards
+                .contains_key(route_key)
+                .then(|| String::from(route_key));
+        }
+
+        let hash = composite_id_hash(route_key);
+        state
+            .shards
+            .iter()
+            .find(|(_, shard)| {
+                shard.state == "active"
+                    && shard
+                        .range
+                        .as_ref()
+                        .map(|range| in_range(range, hash))
+                        .unwrap_or(false)
+            })
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Return SolrCore of the leader of the shard to which the document with the route key belongs.
+    pub fn leader_core(&self, route_key: &str) -> Option<SolrCore> {
+        let shard = self.shard_for(route_key)?;
+        let state = self.state.read().unwrap();
+        let leader = state.leader(&shard)?;
+
+        Some(self.replica_core(leader))
+    }
+
+    /// Return SolrCore of one of the active replicas of the collection in round-robin.
+    ///
+    /// The queries sent to the replica are still distributed to the whole collection by Solr.
+    pub fn query_core(&self) -> Option<SolrCore> {
+        let state = self.state.read().unwrap();
+        let mut replicas: Vec<&SolrReplicaState> = state
+            .shards
+            .values()
+            .flat_map(|shard| shard.active_replicas())
+            .collect();
+        if replicas.is_empty() {
+            return None;
+        }
+        replicas.sort_by(|a, b| a.core.cmp(&b.core));
+
+        let next = self.next.fetch_add(1, Ordering::Relaxed) % replicas.len();
+        Some(self.replica_core(replicas[next]))
+    }
+
+    /// Post the documents to the leaders of the shards to which they belong.
+    ///
+    /// The route key of each document is the value of `id_field`. The documents whose shard can not be determined
+    /// are posted to one of the active replicas, which forwards them to the leader.
+    pub async fn post<T: Serialize>(
+        &self,
+        documents: &[T],
+        id_field: &str,
+    ) -> Result<Vec<SolrSimpleResponse>, SolrCoreError> {
+        self.post_with(documents, id_field, &Vec::<(&str, &str)>::new())
+            .await
+    }
+
+    /// Post the documents to the leaders of the shards with the parameters of the update request, e.g. `update.chain`.
+    ///
+    /// The response of each shard has its name in [`SolrSimpleResponse::shard`], so that the achieved replication factor
+    /// can be checked per shard.
+    pub async fn post_with<T: Serialize>(
+        &self,
+        documents: &[T],
+        id_field: &str,
+        params: &Vec<(impl Serialize, impl Serialize)>,
+    ) -> Result<Vec<SolrSimpleResponse>, SolrCoreError> {
+        let mut groups: HashMap<Option<String>, Vec<Value>> = HashMap::new();
+        for document in documents {
+            let document = serde_json::to_value(document)?;
+            let shard = match document.get(id_field) {
+                Some(Value::String(id)) => self.shard_for(id),
+                Some(Value::Number(id)) => self.shard_for(&id.to_string()),
+                _ => None,
+            };
+            groups.entry(shard).or_default().push(document);
+        }
+
+        let mut responses = Vec::new();
+        for (shard, documents) in groups {
+            let core = shard
+                .as_ref()
+                .and_then(|shard| {
+                    let state = self.state.read().unwrap();
+                    state.leader(shard).map(|leader| self.replica_core(leader))
+                })
+                .or_else(|| self.query_core())
+                .ok_or_else(|| {
+                    SolrCoreError::UnexpectedError((
+                        503,
+                        format!("No active replica of collection {}", self.collection),
+                    ))
+                })?;
+            let body = serde_json::to_vec(&documents)?;
+            let mut response = core.post_with(body, params).await?;
+            response.shard = shard;
+            responses.push(response);
+        }
+
+        Ok(responses)
+    }
+
+    /// Create SolrCore that sends the requests to the core of the replica.
+    fn replica_core(&self, replica: &SolrReplicaState) -> SolrCore {
+        SolrCore::with_transport(&replica.core, &replica.base_url, self.transport.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::testing::{response, StubServer};
+
+    #[test]
+    fn test_murmur3_x86_32() {
+        assert_eq!(murmur3_x86_32(b"", 0), 0);
+        assert_eq!(murmur3_x86_32(b"hello", 0), 0x248b_fa47);
+        assert_eq!(
+            murmur3_x86_32(b"The quick brown fox jumps over the lazy dog", 0),
+            0x2e4f_f723
+        );
+    }
+
+    #[test]
+    fn test_composite_id_hash() {
+        let tenant = murmur3_x86_32(b"IBM", 0);
+        let id = murmur3_x86_32(b"12345", 0);
+
+        assert_eq!(composite_id_hash("12345"), id as i32);
+        assert_eq!(
+            composite_id_hash("IBM!12345"),
+            ((tenant & 0xffff_0000) | (id & 0x0000_ffff)) as i32
+        );
+        assert_eq!(
+            composite_id_hash("IBM/4!12345"),
+            ((tenant & 0xf000_0000) | (id & 0x0fff_ffff)) as i32
+        );
+    }
+
+    #[test]
+    fn test_in_range() {
+        assert!(in_range("80000000-ffffffff", -1));
+        assert!(in_range("0-7fffffff", 0));
+        assert!(!in_range("0-7fffffff", -1));
+    }
+
+    fn state(base_url: &str) -> SolrCollectionState {
+        let raw = format!(
+            r#"{{
+                "shards": {{
+                    "shard1": {{
+                        "range": "80000000-ffffffff",
+                        "state": "active",
+                        "replicas": {{
+                            "core_node1": {{"core": "example_shard1_replica_n1", "base_url": "{0}/solr", "node_name": "n1", "state": "active", "type": "NRT", "leader": "true"}}
+                        }}
+                    }},
+                    "shard2": {{
+                        "range": "0-7fffffff",
+                        "state": "active",
+                        "replicas": {{
+                            "core_node2": {{"core": "example_shard2_replica_n2", "base_url": "{0}/solr", "node_name": "n1", "state": "active", "type": "NRT", "leader": "true"}}
+                        }}
+                    }}
+                }},
+                "router": {{"name": "compositeId"}}
+            }}"#,
+            base_url
+        );
+        serde_json::from_str(&raw).unwrap()
+    }
+
+    #[test]
+    fn test_route_to_shard_leader() {
+        let router = CloudRouter::new(
+            "example",
+            state("http://localhost:8983"),
+            Transport::new(reqwest::Client::new()),
+        );
+
+        let key = "doc1";
+        let expected = if composite_id_hash(key) < 0 {
+            "example_shard1_replica_n1"
+        } else {
+            "example_shard2_replica_n2"
+        };
+
+        let core = router.leader_core(key).unwrap();
+        assert_eq!(core.name, expected);
+        assert_eq!(
+            core.core_url,
+            format!("http://localhost:8983/solr/{}", expected)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_to_shard_leaders() {
+        let ok = r#"{"responseHeader":{"status":0,"QTime":0,"rf":2}}"#;
+        let server = StubServer::start(vec![response("200 OK", &[], ok); 2]);
+        let router = CloudRouter::new(
+            "example",
+            state(&server.url),
+            Transport::new(reqwest::Client::new()),
+        );
+
+        // Find the documents that belong to the different shards.
+        let ids: Vec<String> = (0..)
+            .map(|i| format!("doc{}", i))
+            .filter(|id| composite_id_hash(id) < 0)
+            .take(1)
+            .chain(
+                (0..)
+                    .map(|i| format!("doc{}", i))
+                    .filter(|id| composite_id_hash(id) >= 0)
+                    .take(1),
+            )
+            .collect();
+        let documents: Vec<Value> = ids
+            .iter()
+            .map(|id| serde_json::json!({ "id": id }))
+            .collect();
+
+        let responses = router.post(&documents, "id").await.unwrap();
+
+        assert_eq!(responses.len(), 2);
+        let mut shards: Vec<&str> = responses
+            .iter()
+            .map(|response| response.shard.as_deref().unwrap())
+            .collect();
+        shards.sort();
+        assert_eq!(shards, vec!["shard1", "shard2"]);
+        assert!(responses
+            .iter()
+            .all(|response| response.header.is_replicated(2)));
+        let mut paths: Vec<String> = server
+            .requests()
+            .iter()
+            .map(|request| request.split(' ').nth(1).unwrap().to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "/solr/example_shard1_replica_n1/update",
+                "/solr/example_shard2_replica_n2/update"
+            ]
+        );
+    }
+}
@@ -3,10 +3,24 @@
 //! SolrClient struct is responsible for connecting to a running Solr instance
 //! and creating a SolrCore struct, which represents a single Solr core.
 
+use crate::client::api::{context_root, ApiVersion};
+use crate::client::auth::{SolrAuth, TokenError};
+use crate::client::circuit_breaker::CircuitBreaker;
+use crate::client::config::SolrConfig;
 use crate::client::core::SolrCore;
+use crate::client::logging::LogLevel;
+use crate::client::metrics::MetricsFilter;
+use crate::client::middleware::Middleware;
+use crate::client::observer::RequestObserver;
+use crate::client::retry::RetryPolicy;
+use crate::client::transport::{impl_request_error, RequestContext, Transport, TransportOptions};
 use crate::types::response::*;
+use bytes::Bytes;
 use core::time::Duration;
-use reqwest::Client;
+pub use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+pub use reqwest::{Certificate, Identity, Proxy};
+use reqwest::{Client, RequestBuilder};
+use std::sync::Arc;
 use thiserror::Error;
 use url::Url;
 
@@ -14,67 +28,617 @@ type Result<T> = std::result::Result<T, SolrClientError>;
 
 #[derive(Debug, Error)]
 pub enum SolrClientError {
-    #[error("Failed to request to solr")]
-    RequestError(#[from] reqwest::Error),
+    #[error("Failed to build HTTP client with given configuration")]
+    HttpClientBuildError(#[source] reqwest::Error),
     #[error("Failed to parse given URL")]
     UrlParseError(#[from] url::ParseError),
     #[error("Given URL host is invalid")]
     InvalidHostError,
+    #[error("No Solr node is given")]
+    EmptyNodesError,
     #[error("Specified core name does not exist")]
     SpecifiedCoreNotFoundError,
+    #[error("Failed to build the request to solr")]
+    InvalidRequestError(#[source] reqwest::Error),
+    #[error("Failed to request to solr: {context}")]
+    RequestError {
+        context: Box<RequestContext>,
+        #[source]
+        source: reqwest::Error,
+    },
     #[error("Failed to deserialize JSON data")]
     DeserializeError(#[from] serde_json::Error),
+    #[error("Failed to get the authentication token: {context}")]
+    AuthenticationError {
+        context: Box<RequestContext>,
+        #[source]
+        source: TokenError,
+    },
+    #[error("Circuit breaker is open for node {node}: {context}")]
+    CircuitOpenError {
+        node: String,
+        context: Box<RequestContext>,
+    },
+    #[error("Middleware failed to process the request: {context}")]
+    MiddlewareError {
+        context: Box<RequestContext>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Response body exceeds the limit of {limit} bytes: {context}")]
+    ResponseTooLarge {
+        context: Box<RequestContext>,
+        limit: usize,
+    },
+    #[error("Request to solr timed out: {context}")]
+    Timeout {
+        context: Box<RequestContext>,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Bad request: {}: {}", .info.msg, .context)]
+    BadRequest {
+        context: Box<RequestContext>,
+        info: SolrErrorInfo,
+    },
+    #[error("Authentication is required: {context}")]
+    Unauthorized { context: Box<RequestContext> },
+    #[error("Permission denied: {context}")]
+    Forbidden { context: Box<RequestContext> },
+    #[error("Requested resource is not found: {context}")]
+    NotFound { context: Box<RequestContext> },
+    #[error("Solr is unavailable: {context}")]
+    ServiceUnavailable { context: Box<RequestContext> },
+    #[error("Conflict: {}: {}", .info.msg, .context)]
+    Conflict {
+        context: Box<RequestContext>,
+        info: SolrErrorInfo,
+    },
+    #[error(
+        "Solr responded with status {status} and the body that is not JSON: {context}: {body}"
+    )]
+    UnparsableResponseError {
+        context: Box<RequestContext>,
+        status: u16,
+        content_type: Option<String>,
+        body: String,
+    },
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+    #[error("Asynchronous request {0} did not complete within the timeout")]
+    AsyncRequestTimeout(String),
     #[error("Unexpected error")]
     UnexpectedError((u32, String)),
 }
 
+impl_request_error!(SolrClientError);
+
 #[derive(Debug)]
 pub struct SolrClient {
     /// Host URL of the Solr instance. e.g.) http://localhost:8983
+    pub(crate) url: String,
+    /// HTTP client, authentication method, retry policy and circuit breaker, which are also passed on to the cores.
+    pub(crate) transport: Transport,
+    /// Version of the API to which the requests are sent, which is also passed on to the cores.
+    pub(crate) api: ApiVersion,
+}
+
+/// Builder of SolrClient that configures the underlying HTTP client.
+pub struct SolrClientBuilder {
     url: String,
-    /// reqwest HTTP client
-    client: Client,
+    port: Option<u32>,
+    auth: Option<SolrAuth>,
+    retry: Option<RetryPolicy>,
+    circuit_breaker: Option<CircuitBreaker>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    redacted_params: Vec<String>,
+    headers: HeaderMap,
+    options: TransportOptions,
+    client: Option<Client>,
+    connect_timeout: Duration,
+    timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    pool_max_idle_per_host: Option<usize>,
+    user_agent: Option<String>,
+    root_certificates: Vec<Certificate>,
+    built_in_root_certificates: bool,
+    identity: Option<Identity>,
+    accept_invalid_certs: bool,
+    #[cfg(feature = "rustls-tls")]
+    use_rustls: bool,
+    #[cfg(feature = "gzip")]
+    gzip: Option<bool>,
+    #[cfg(feature = "deflate")]
+    deflate: Option<bool>,
+    #[cfg(feature = "brotli")]
+    brotli: Option<bool>,
 }
 
-impl SolrClient {
-    /// Of the URL given as argument, only the schema and hostname are extracted and used.
-    /// For example, if http://localhost:8983/solr is given, all port numbers and paths are ignored.
-    pub fn new(url: &str, port: u32) -> Result<Self> {
-        let url = Url::parse(url).map_err(|e| SolrClientError::UrlParseError(e))?;
+impl SolrClientBuilder {
+    pub fn new(url: &str, port: u32) -> Self {
+        Self {
+            port: Some(port),
+            ..Self::from_url(url)
+        }
+    }
+
+    /// Create the builder with the URL, whose port is used, or the default port of the scheme if omitted.
+    pub fn from_url(url: &str) -> Self {
+        Self {
+            url: String::from(url),
+            port: None,
+            auth: None,
+            retry: None,
+            circuit_breaker: None,
+            observer: None,
+            middlewares: Vec::new(),
+            redacted_params: Vec::new(),
+            headers: HeaderMap::new(),
+            options: TransportOptions::new(),
+            client: None,
+            connect_timeout: Duration::from_secs(3),
+            timeout: None,
+            proxies: Vec::new(),
+            pool_max_idle_per_host: None,
+            user_agent: None,
+            root_certificates: Vec::new(),
+            built_in_root_certificates: true,
+            identity: None,
+            accept_invalid_certs: false,
+            #[cfg(feature = "rustls-tls")]
+            use_rustls: false,
+            #[cfg(feature = "gzip")]
+            gzip: None,
+            #[cfg(feature = "deflate")]
+            deflate: None,
+            #[cfg(feature = "brotli")]
+            brotli: None,
+        }
+    }
+
+    /// Set the authentication method attached to every request.
+    pub fn auth(mut self, auth: SolrAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Set the policy to retry the idempotent requests.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Set the circuit breaker that stops sending requests to the node after consecutive failures.
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Set the observer notified of every request.
+    pub fn observer(mut self, observer: impl RequestObserver + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Add the middleware executed around every request. The middlewares are executed in the order they are added.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Set the names of the parameters whose values are masked in the context of the errors.
+    pub fn redact_params(mut self, names: &[&str]) -> Self {
+        self.redacted_params = names.iter().map(|name| String::from(*name)).collect();
+        self
+    }
+
+    /// Set the headers attached to every request, e.g. the API key of the gateway in front of Solr.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Set the options on how the requests are sent and how the responses are read.
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Use the given preconfigured HTTP client.
+    ///
+    /// When the client is given, the other HTTP and TLS settings of this builder are ignored.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Set the timeout for establishing a connection. Defaults to 3 seconds.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout applied to every request, from the start of connecting until the response body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add a proxy used to request to Solr.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Set the maximum number of idle connections kept per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(String::from(user_agent));
+        self
+    }
+
+    /// Add a custom CA certificate to trust, e.g. the certificate of a private CA.
+    ///
+    /// This method can be called multiple times to add multiple certificates.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Set whether to trust the built-in root certificates of the system. Defaults to `true`.
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.built_in_root_certificates = enabled;
+        self
+    }
+
+    /// Set the client certificate and key used for mutual TLS.
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Accept invalid server certificates, including expired or self-signed ones.
+    ///
+    /// This disables the verification of the server, so use it only for development.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Use rustls as the TLS backend instead of native-tls.
+    #[cfg(feature = "rustls-tls")]
+    pub fn use_rustls_tls(mut self) -> Self {
+        self.use_rustls = true;
+        self
+    }
+
+    /// Enable or disable gzip decompression of the responses, which is enabled by default with the `gzip` feature.
+    ///
+    /// When enabled, `Accept-Encoding: gzip` is sent and the compressed response is decompressed transparently.
+    #[cfg(feature = "gzip")]
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.gzip = Some(enable);
+        self
+    }
+
+    /// Enable or disable deflate decompression of the responses, which is enabled by default with the `deflate` feature.
+    #[cfg(feature = "deflate")]
+    pub fn deflate(mut self, enable: bool) -> Self {
+        self.deflate = Some(enable);
+        self
+    }
+
+    /// Enable or disable brotli decompression of the responses, which is enabled by default with the `brotli` feature.
+    #[cfg(feature = "brotli")]
+    pub fn brotli(mut self, enable: bool) -> Self {
+        self.brotli = Some(enable);
+        self
+    }
+
+    /// Build SolrClient.
+    ///
+    /// Of the URL given to the builder, the schema, hostname, port and path are used. The port given to [`SolrClientBuilder::new`] takes precedence.
+    /// The path is the context path of Solr deployed behind the reverse proxy, e.g. `/search-api` of `https://example.com/search-api/solr`.
+    pub fn build(mut self) -> Result<SolrClient> {
+        let url = Url::parse(&self.url).map_err(SolrClientError::UrlParseError)?;
 
         let scheme = url.scheme();
         let host = url
             .host_str()
             .ok_or_else(|| SolrClientError::InvalidHostError)?;
 
-        Ok(SolrClient {
-            url: format!("{}://{}:{}", scheme, host, port),
-            client: reqwest::Client::builder()
-                .connect_timeout(Duration::from_secs(3))
-                .build()?,
-        })
+        let port = self
+            .port
+            .or_else(|| url.port_or_known_default().map(u32::from))
+            .ok_or_else(|| SolrClientError::InvalidHostError)?;
+
+        let url = format!("{}://{}:{}{}", scheme, host, port, context_root(url.path()));
+        let auth = self.auth.take();
+        let retry = self.retry.take();
+        let circuit_breaker = self.circuit_breaker.take();
+        let observer = self.observer.take();
+        let middlewares = std::mem::take(&mut self.middlewares);
+        let redacted_params = std::mem::take(&mut self.redacted_params);
+        let headers = std::mem::take(&mut self.headers);
+        let options = self.options;
+        let client = match self.client.take() {
+            Some(client) => client,
+            None => self.build_http_client()?,
+        };
+
+        let mut transport = Transport::new(client);
+        transport.auth = auth;
+        transport.retry = retry;
+        transport.circuit_breaker = circuit_breaker;
+        transport.observer = observer;
+        transport.middlewares = middlewares;
+        transport.redacted_params = redacted_params;
+        transport.headers = headers;
+        transport.options = options;
+
+        Ok(SolrClient::with_transport(&url, transport))
+    }
+
+    /// Build the HTTP client with the settings of this builder.
+    fn build_http_client(self) -> Result<Client> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .tls_built_in_root_certs(self.built_in_root_certificates)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        for proxy in self.proxies {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        for certificate in self.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(identity) = self.identity {
+            builder = builder.identity(identity);
+        }
+        #[cfg(feature = "rustls-tls")]
+        if self.use_rustls {
+            builder = builder.use_rustls_tls();
+        }
+        #[cfg(feature = "gzip")]
+        if let Some(enable) = self.gzip {
+            builder = builder.gzip(enable);
+        }
+        #[cfg(feature = "deflate")]
+        if let Some(enable) = self.deflate {
+            builder = builder.deflate(enable);
+        }
+        #[cfg(feature = "brotli")]
+        if let Some(enable) = self.brotli {
+            builder = builder.brotli(enable);
+        }
+
+        builder
+            .build()
+            .map_err(SolrClientError::HttpClientBuildError)
+    }
+}
+
+impl SolrClient {
+    /// Of the URL given as argument, the schema, hostname and context path are extracted and used.
+    /// For example, if http://localhost:8983/solr is given, the port number is ignored and the requests are sent under `/solr`.
+    /// If https://example.com/search-api/solr is given, the requests are sent under `/search-api/solr`.
+    #[deprecated(since = "0.1.11", note = "use `SolrClient::from_url` instead")]
+    pub fn new(url: &str, port: u32) -> Result<Self> {
+        SolrClientBuilder::new(url, port).build()
+    }
+
+    /// Create SolrClient with the URL of the Solr instance, e.g. `http://localhost:8983`.
+    ///
+    /// The port of the URL is used, or the default port of the scheme if omitted.
+    /// The path of the URL is the context path, e.g. `https://example.com/search-api/solr` to send the requests under `/search-api/solr`.
+    pub fn from_url(url: &str) -> Result<Self> {
+        SolrClientBuilder::from_url(url).build()
+    }
+
+    /// Create SolrClient with the configuration read from the environment variables, `SOLR_URL` and so on.
+    ///
+    /// See [`SolrConfig::from_env`] for the variables.
+    pub fn from_env() -> Result<Self> {
+        SolrConfig::from_env()?.client()
+    }
+
+    /// Create SolrClient that sends requests through the given transport.
+    pub(crate) fn with_transport(url: &str, transport: Transport) -> Self {
+        SolrClient {
+            url: String::from(url),
+            transport,
+            api: ApiVersion::V1,
+        }
+    }
+
+    /// Create the builder of SolrClient to configure TLS and other settings.
+    pub fn builder(url: &str, port: u32) -> SolrClientBuilder {
+        SolrClientBuilder::new(url, port)
+    }
+
+    /// Create the builder of SolrClient with the URL, the same as [`SolrClient::from_url`].
+    pub fn builder_from_url(url: &str) -> SolrClientBuilder {
+        SolrClientBuilder::from_url(url)
+    }
+
+    /// Set the authentication method attached to every request to the Solr instance and to the cores created by this client.
+    pub fn auth(mut self, auth: SolrAuth) -> Self {
+        self.transport.auth = Some(auth);
+        self
+    }
+
+    /// Set the policy to retry the idempotent requests to the Solr instance and to the cores created by this client.
+    ///
+    /// Requests that change the state of Solr, such as post, commit and CoreAdmin `CREATE` and `SWAP`, are never retried.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.transport.retry = Some(policy);
+        self
+    }
+
+    /// Set the circuit breaker shared by the requests to the Solr instance and to the cores created by this client.
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.transport.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Set the observer notified of the requests to the Solr instance and to the cores created by this client.
+    pub fn observer(mut self, observer: impl RequestObserver + 'static) -> Self {
+        self.transport.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Add the middleware executed around the requests to the Solr instance and to the cores created by this client.
+    ///
+    /// The middlewares are executed in the order they are added.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.transport.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Set the names of the parameters whose values are masked in the context of the errors,
+    /// such as the parameters containing personal information.
+    pub fn redact_params(mut self, names: &[&str]) -> Self {
+        self.transport.redacted_params = names.iter().map(|name| String::from(*name)).collect();
+        self
+    }
+
+    /// Set the headers attached to every request to the Solr instance and to the cores created by this client.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.transport.headers = headers;
+        self
+    }
+
+    /// Set the options on how the requests are sent and how the responses are read,
+    /// of the Solr instance and of the cores created by this client.
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.transport.options = options;
+        self
+    }
+
+    /// Set the version of the API to which the requests to the Solr instance and to the cores created by this client are sent.
+    ///
+    /// The metrics, the logging and the CoreAdmin actions, such as creating and swapping the cores, are still sent to the V1 API.
+    pub fn api_version(mut self, api: ApiVersion) -> Self {
+        self.api = api;
+        self
+    }
+
+    /// Set the credentials for [Basic Authentication Plugin](https://solr.apache.org/guide/solr/latest/deployment-guide/basic-authentication-plugin.html).
+    pub fn basic_auth(self, username: &str, password: Option<&str>) -> Self {
+        self.auth(SolrAuth::basic(username, password))
+    }
+
+    /// Set the static token for [JWT Authentication Plugin](https://solr.apache.org/guide/solr/latest/deployment-guide/jwt-authentication-plugin.html).
+    ///
+    /// Use [`SolrAuth::bearer_with`] with [`SolrClient::auth`] to refresh the token when it expires.
+    pub fn bearer_auth(self, token: &str) -> Self {
+        self.auth(SolrAuth::bearer(token))
+    }
+
+    /// Method to send the request with the authentication of this client, and get the response body.
+    ///
+    /// The request is never retried, because it may change the state of Solr.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Bytes> {
+        self.execute(request, false).await
+    }
+
+    /// Method to send the request that only reads the state of Solr, which is retried by the retry policy.
+    pub(crate) async fn send_idempotent(&self, request: RequestBuilder) -> Result<Bytes> {
+        self.execute(request, true).await
+    }
+
+    async fn execute(&self, request: RequestBuilder, idempotent: bool) -> Result<Bytes> {
+        let mut request = request
+            .build()
+            .map_err(SolrClientError::InvalidRequestError)?;
+        self.transport.prepare(&mut request);
+        let context = Box::new(self.transport.context(&request, None));
+
+        let response = self
+            .transport
+            .execute(request, idempotent)
+            .await
+            .map_err(|e| SolrClientError::from_transport_error(e, context.clone()))?;
+        if let Some(e) = SolrClientError::from_response(&response, context) {
+            return Err(e);
+        }
+
+        Ok(response.body)
     }
 
     /// Methods to get the status of a Solr instance
     pub async fn status(&self) -> Result<SolrSystemInfo> {
-        let path = "solr/admin/info/system";
+        let path = match self.api {
+            ApiVersion::V1 => "solr/admin/info/system",
+            ApiVersion::V2 => "api/node/system",
+        };
 
-        let response = self
+        let request = self
+            .transport
             .client
             .get(format!("{}/{}", self.url, path))
-            .timeout(Duration::from_secs(3))
-            .send()
-            .await
-            .map_err(|e| SolrClientError::RequestError(e))?
-            .text()
-            .await
-            .map_err(|e| SolrClientError::RequestError(e))?;
+            .timeout(Duration::from_secs(3));
+        let response = self.send_idempotent(request).await?;
 
         let response: SolrSystemInfo =
-            serde_json::from_str(&response).map_err(|e| SolrClientError::DeserializeError(e))?;
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Method to get the thread dump of the Solr instance.
+    pub async fn threads(&self) -> Result<SolrThreadsResponse> {
+        let path = match self.api {
+            ApiVersion::V1 => "solr/admin/info/threads",
+            ApiVersion::V2 => "api/node/threads",
+        };
+
+        let request = self.transport.client.get(format!("{}/{}", self.url, path));
+        let response = self.send_idempotent(request).await?;
+
+        let response: SolrThreadsResponse =
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Method to get the Java system properties of the Solr instance.
+    pub async fn properties(&self) -> Result<SolrPropertiesResponse> {
+        let path = match self.api {
+            ApiVersion::V1 => "solr/admin/info/properties",
+            ApiVersion::V2 => "api/node/properties",
+        };
+
+        let request = self.transport.client.get(format!("{}/{}", self.url, path));
+        let response = self.send_idempotent(request).await?;
+
+        let response: SolrPropertiesResponse =
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
 
         if let Some(error) = response.error {
-            return Err(SolrClientError::UnexpectedError((error.code, error.msg)));
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
         } else {
             Ok(response)
         }
@@ -82,28 +646,165 @@ impl SolrClient {
 
     ///  Method to get a list of cores present in the Solr instance
     pub async fn cores(&self) -> Result<SolrCoreList> {
-        let path = "solr/admin/cores";
+        let path = match self.api {
+            ApiVersion::V1 => "solr/admin/cores",
+            ApiVersion::V2 => "api/cores",
+        };
 
-        let response = self
-            .client
-            .get(format!("{}/{}", self.url, path))
-            .send()
+        let request = self.transport.client.get(format!("{}/{}", self.url, path));
+        let response = self.send_idempotent(request).await?;
+
+        let response: SolrCoreList =
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Method to check whether the core exists in the Solr instance.
+    pub async fn core_exists(&self, name: &str) -> Result<bool> {
+        let cores = self.cores().await?;
+
+        Ok(cores.get(name).is_some())
+    }
+
+    /// Method to create SolrCore struct of the core, creating the core with the configset if it does not exist.
+    ///
+    /// When the core is created by another process between the check and the creation, the creation fails,
+    /// but the existing core is returned.
+    pub async fn core_or_create(&self, name: &str, configset: &str) -> Result<SolrCore> {
+        if self.core_exists(name).await? {
+            return Ok(self.bind_core(name));
+        }
+
+        match self
+            .core_admin("CREATE", &[("name", name), ("configSet", configset)])
             .await
-            .map_err(|e| SolrClientError::RequestError(e))?
-            .text()
+        {
+            Ok(_) => Ok(self.bind_core(name)),
+            Err(e) => {
+                if self.core_exists(name).await? {
+                    Ok(self.bind_core(name))
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Method to swap the names of the two cores, which switches the index served by the name atomically
+    /// in blue/green reindexing.
+    ///
+    /// The request is never retried by the retry policy, because the retried swap swaps the cores back.
+    pub async fn swap_cores(&self, core: &str, other: &str) -> Result<SolrSimpleResponse> {
+        self.core_admin("SWAP", &[("core", core), ("other", other)])
             .await
-            .map_err(|e| SolrClientError::RequestError(e))?;
+    }
 
-        let response: SolrCoreList =
-            serde_json::from_str(&response).map_err(|e| SolrClientError::DeserializeError(e))?;
+    /// Method to send the request to the CoreAdmin API with the action and the parameters.
+    async fn core_admin(
+        &self,
+        action: &str,
+        params: &[(&str, &str)],
+    ) -> Result<SolrSimpleResponse> {
+        let request = self
+            .transport
+            .client
+            .get(format!("{}/solr/admin/cores", self.url))
+            .query(&[("action", action), ("wt", "json")])
+            .query(params);
+        let response = self.send(request).await?;
+
+        let response: SolrSimpleResponse =
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
 
         if let Some(error) = response.error {
-            return Err(SolrClientError::UnexpectedError((error.code, error.msg)));
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
         } else {
             Ok(response)
         }
     }
 
+    /// Method to get the metrics of the Solr instance, such as the cache hit ratios, the query latencies and the JVM stats.
+    pub async fn metrics(&self, filter: &MetricsFilter) -> Result<SolrMetricsResponse> {
+        let request = self
+            .transport
+            .client
+            .get(format!("{}/solr/admin/metrics", self.url))
+            .query(&filter.params());
+        let response = self.send_idempotent(request).await?;
+
+        let response: SolrMetricsResponse =
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Method to get the levels of the loggers of the Solr instance.
+    pub async fn log_levels(&self) -> Result<SolrLoggingResponse> {
+        self.logging(&[]).await
+    }
+
+    /// Method to set the level of the logger, e.g. `org.apache.solr.core`, which is useful to raise the verbosity temporarily.
+    ///
+    /// The level is not persisted and is reset when Solr restarts. Use [`LogLevel::Unset`] to restore the inherited level.
+    pub async fn set_log_level(
+        &self,
+        logger: &str,
+        level: LogLevel,
+    ) -> Result<SolrLoggingResponse> {
+        self.logging(&[("set", format!("{}:{}", logger, level))])
+            .await
+    }
+
+    /// Method to send the request to `/solr/admin/info/logging` with the parameters.
+    async fn logging(&self, params: &[(&str, String)]) -> Result<SolrLoggingResponse> {
+        let request = self
+            .transport
+            .client
+            .get(format!("{}/solr/admin/info/logging", self.url))
+            .query(params);
+        // Only getting the levels is retried, setting them is not.
+        let response = if params.is_empty() {
+            self.send_idempotent(request).await?
+        } else {
+            self.send(request).await?
+        };
+
+        let response: SolrLoggingResponse =
+            serde_json::from_slice(&response).map_err(SolrClientError::DeserializeError)?;
+
+        if let Some(error) = response.error {
+            Err(SolrClientError::UnexpectedError((error.code, error.msg)))
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Method to create SolrCore structs of all cores present in the Solr instance, in the order of the name.
+    pub async fn all_cores(&self) -> Result<Vec<SolrCore>> {
+        let cores = self.cores().await?;
+
+        Ok(self.bind_cores(&cores))
+    }
+
+    /// Create SolrCore structs bound to this client of the cores in the list, in the order of the name.
+    pub fn bind_cores(&self, cores: &SolrCoreList) -> Vec<SolrCore> {
+        cores.iter().map(|(name, _)| self.bind_core(name)).collect()
+    }
+
+    /// Create SolrCore struct bound to this client, without checking the existence of the core.
+    pub fn bind_core(&self, name: &str) -> SolrCore {
+        SolrCore::with_transport(name, &self.url, self.transport.clone()).api_version(self.api)
+    }
+
     /// Method to create SolrCore struct
     pub async fn core(&self, name: &str) -> Result<SolrCore> {
         let cores = self
@@ -116,37 +817,309 @@ impl SolrClient {
             return Err(SolrClientError::SpecifiedCoreNotFoundError);
         }
 
-        Ok(SolrCore::new(name, &self.url))
+        Ok(SolrCore::with_transport(name, &self.url, self.transport.clone()).api_version(self.api))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::testing::{core_list, response, StubServer};
+
+    /// Normal test to check the existence of the core.
+    #[tokio::test]
+    async fn test_core_exists() {
+        let server = StubServer::start(vec![core_list(&["example"]), core_list(&["example"])]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        assert!(client.core_exists("example").await.unwrap());
+        assert!(!client.core_exists("books").await.unwrap());
+    }
+
+    /// Normal test to create the core that does not exist.
+    #[tokio::test]
+    async fn test_core_or_create() {
+        let server = StubServer::start(vec![
+            core_list(&[]),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":120},"core":"books"}"#,
+            ),
+        ]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        let core = client.core_or_create("books", "_default").await.unwrap();
+
+        assert_eq!(core.name, "books");
+        assert!(server.requests()[1].starts_with(
+            "GET /solr/admin/cores?action=CREATE&wt=json&name=books&configSet=_default "
+        ));
+    }
+
+    /// Normal test that the core created by another process between the check and the creation is returned.
+    #[tokio::test]
+    async fn test_core_or_create_with_race() {
+        let server = StubServer::start(vec![
+            core_list(&[]),
+            response(
+                "500 Server Error",
+                &[],
+                r#"{"responseHeader":{"status":500,"QTime":2},"error":{"metadata":[],"msg":"Core with name 'books' already exists.","code":500}}"#,
+            ),
+            core_list(&["books"]),
+        ]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        let core = client.core_or_create("books", "_default").await.unwrap();
+
+        assert_eq!(core.name, "books");
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    /// Abnormal test that the failure of the creation is returned when the core still does not exist.
+    #[tokio::test]
+    async fn test_core_or_create_with_unknown_configset() {
+        let server = StubServer::start(vec![
+            core_list(&[]),
+            response(
+                "500 Server Error",
+                &[],
+                r#"{"responseHeader":{"status":500,"QTime":2},"error":{"metadata":[],"msg":"Could not load configuration from directory /var/solr/data/configsets/unknown","code":500}}"#,
+            ),
+            core_list(&[]),
+        ]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        assert!(matches!(
+            client.core_or_create("books", "unknown").await,
+            Err(SolrClientError::UnexpectedError((500, _)))
+        ));
+    }
+
+    /// Normal test that the read request to the CoreAdmin API is retried after `503 Service Unavailable`.
+    #[tokio::test]
+    async fn test_cores_is_retried() {
+        let server = StubServer::start(vec![
+            response("503 Service Unavailable", &[("Retry-After", "0")], "{}"),
+            core_list(&["example"]),
+        ]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()))
+            .retry(RetryPolicy::new());
+
+        assert!(client.core_exists("example").await.unwrap());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    /// Normal test that the swap is not retried after `503 Service Unavailable`, which would swap the cores back.
+    #[tokio::test]
+    async fn test_swap_cores_is_not_retried() {
+        let server = StubServer::start(vec![
+            response("503 Service Unavailable", &[("Retry-After", "0")], "{}"),
+            response(
+                "200 OK",
+                &[],
+                r#"{"responseHeader":{"status":0,"QTime":0}}"#,
+            ),
+        ]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()))
+            .retry(RetryPolicy::new());
+
+        assert!(client.swap_cores("live", "shadow").await.is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    /// Normal test to set the level of the logger.
+    #[tokio::test]
+    async fn test_set_log_level() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"levels":[],"loggers":[{"name":"org.apache.solr.core","level":"DEBUG","set":true}]}"#,
+        )]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        let response = client
+            .set_log_level("org.apache.solr.core", LogLevel::Debug)
+            .await
+            .unwrap();
+
+        assert!(response.logger("org.apache.solr.core").unwrap().set);
+        assert!(server.requests()[0]
+            .starts_with("GET /solr/admin/info/logging?set=org.apache.solr.core%3ADEBUG "));
+    }
+
+    /// Normal test to get the thread dump.
+    #[tokio::test]
+    async fn test_threads() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{
+                "responseHeader":{"status":0,"QTime":5},
+                "system":{
+                    "threadCount":{"current":43,"peak":47,"daemon":22},
+                    "threadDump":[
+                        "RUNNABLE",
+                        {"id":1,"name":"main","state":"RUNNABLE","cpuTime":"123.45ms","userTime":"100.00ms","stackTrace":["java.lang.Thread.run(Thread.java:829)"]},
+                        "BLOCKED",
+                        {"id":42,"name":"qtp-42","state":"BLOCKED","lock":"java.lang.Object@1a2b3c","cpuTime":"1.00ms","userTime":"1.00ms","stackTrace":[]}
+                    ]
+                }
+            }"#,
+        )]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        let response = client.threads().await.unwrap();
+
+        assert_eq!(response.system.thread_count.current, 43);
+        assert_eq!(response.system.thread_dump.len(), 2);
+        assert_eq!(response.system.threads_in("BLOCKED")[0].name, "qtp-42");
+        assert!(server.requests()[0].starts_with("GET /solr/admin/info/threads "));
+    }
+
+    /// Normal test to get the system properties.
+    #[tokio::test]
+    async fn test_properties() {
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"system.properties":{"java.version":"17.0.6","solr.solr.home":"/var/solr/data"}}"#,
+        )]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        let response = client.properties().await.unwrap();
+
+        assert_eq!(response.properties["java.version"], "17.0.6");
+        assert!(server.requests()[0].starts_with("GET /solr/admin/info/properties "));
+    }
+
+    /// Normal test to iterate the cores and to create SolrCore structs of them.
+    #[tokio::test]
+    async fn test_all_cores() {
+        let status = |name: &str| {
+            format!(
+                r#""{0}":{{"name":"{0}","instanceDir":"","dataDir":"","config":"solrconfig.xml","schema":"managed-schema.xml","startTime":"","uptime":0}}"#,
+                name
+            )
+        };
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            &format!(
+                r#"{{"responseHeader":{{"status":0,"QTime":0}},"initFailures":{{}},"status":{{{},{}}}}}"#,
+                status("products"),
+                status("articles")
+            ),
+        )]);
+        let client = SolrClient::with_transport(&server.url, Transport::new(Client::new()));
+
+        let cores = client.all_cores().await.unwrap();
+
+        let names: Vec<&str> = cores.iter().map(|core| core.name.as_str()).collect();
+        assert_eq!(names, vec!["articles", "products"]);
+        assert_eq!(cores[0].core_url, format!("{}/solr/articles", server.url));
+    }
 
     /// Normal system test of SolrClient creation
     #[test]
+    #[allow(deprecated)]
     fn test_create_solr_client() {
         let client = SolrClient::new("http://localhost", 8983).unwrap();
         assert_eq!(client.url, "http://localhost:8983");
     }
 
+    /// Normal system test of SolrClient creation with the URL only.
+    #[test]
+    fn test_create_solr_client_from_url() {
+        let client = SolrClient::from_url("http://localhost:8983").unwrap();
+        assert_eq!(client.url, "http://localhost:8983");
+
+        let client = SolrClient::from_url("https://example.com/solr").unwrap();
+        assert_eq!(client.url, "https://example.com:443");
+    }
+
     /// Normal system test of SolrClient creation.
     ///
     /// Check the behavior when given a redundant URL.
     /// Only the schema and host of the given URL are extracted. So even if a URL with additional
     /// information beyond the schema and host is provided, it will be ignored.
     #[test]
+    #[allow(deprecated)]
     fn test_create_solr_client_with_redundant_url() {
         let client = SolrClient::new("http://localhost:8983/solr", 8983).unwrap();
         assert_eq!(client.url, "http://localhost:8983");
     }
 
+    /// Normal system test of SolrClient creation with the custom context path.
+    #[tokio::test]
+    async fn test_create_solr_client_with_context_path() {
+        let client = SolrClient::from_url("https://example.com/search-api/solr/").unwrap();
+        assert_eq!(client.url, "https://example.com:443/search-api");
+
+        let server = StubServer::start(vec![response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":0},"initFailures":{},"status":{}}"#,
+        )]);
+        let client = SolrClient::from_url(&format!("{}/search-api/solr", server.url)).unwrap();
+        client.cores().await.unwrap();
+
+        assert!(server.requests()[0].starts_with("GET /search-api/solr/admin/cores "));
+    }
+
+    /// Normal system test of SolrClient creation with Basic Authentication credentials.
+    #[test]
+    fn test_create_solr_client_with_basic_auth() {
+        let client = SolrClient::from_url("http://localhost:8983")
+            .unwrap()
+            .basic_auth("solr", Some("SolrRocks"));
+        assert!(matches!(
+            client.transport.auth,
+            Some(SolrAuth::Basic { .. })
+        ));
+    }
+
+    /// Normal system test of SolrClient creation with TLS configuration.
+    #[test]
+    fn test_create_solr_client_with_builder() {
+        let client = SolrClient::builder("https://localhost", 8984)
+            .danger_accept_invalid_certs(true)
+            .tls_built_in_root_certs(false)
+            .build()
+            .unwrap();
+        assert_eq!(client.url, "https://localhost:8984");
+    }
+
+    /// Normal system test of SolrClient creation with HTTP options.
+    #[test]
+    fn test_create_solr_client_with_http_options() {
+        let client = SolrClient::builder("http://localhost", 8983)
+            .connect_timeout(Duration::from_secs(1))
+            .timeout(Duration::from_secs(10))
+            .proxy(Proxy::http("http://localhost:3128").unwrap())
+            .pool_max_idle_per_host(8)
+            .user_agent("solrust-test")
+            .build()
+            .unwrap();
+        assert_eq!(client.url, "http://localhost:8983");
+    }
+
+    /// Normal system test of SolrClient creation with preconfigured HTTP client.
+    #[test]
+    fn test_create_solr_client_with_injected_client() {
+        let client = SolrClient::builder("http://localhost", 8983)
+            .client(reqwest::Client::new())
+            .build()
+            .unwrap();
+        assert_eq!(client.url, "http://localhost:8983");
+    }
+
     /// Anomaly system test of SolrClient creation.
     /// Creation fails if an invalid URL is given.
     #[test]
     fn test_create_solr_client_with_invalid_url() {
-        let client = SolrClient::new("hogehoge", 3000);
+        let client = SolrClient::from_url("hogehoge");
         assert!(client.is_err());
     }
 
@@ -160,7 +1133,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_status() {
-        let client = SolrClient::new("http://localhost", 8983).unwrap();
+        let client = SolrClient::from_url("http://localhost:8983").unwrap();
 
         let response = client.status().await.unwrap();
         assert_eq!(response.header.status, 0);
@@ -176,7 +1149,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_cores() {
-        let client = SolrClient::new("http://localhost", 8983).unwrap();
+        let client = SolrClient::from_url("http://localhost:8983").unwrap();
 
         let response = client.cores().await.unwrap();
         assert!(response.status.unwrap().contains_key("example"));
@@ -192,7 +1165,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_cores_as_vec() {
-        let client = SolrClient::new("http://localhost", 8983).unwrap();
+        let client = SolrClient::from_url("http://localhost:8983").unwrap();
 
         let response = client.cores().await.unwrap();
         let cores = response.as_vec().unwrap();
@@ -209,7 +1182,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_core() {
-        let client = SolrClient::new("http://localhost", 8983).unwrap();
+        let client = SolrClient::from_url("http://localhost:8983").unwrap();
 
         let core = client.core("example").await.unwrap();
         assert_eq!(core.name, String::from("example"));
@@ -226,7 +1199,7 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_non_existent_core() {
-        let client = SolrClient::new("http://localhost", 8983).unwrap();
+        let client = SolrClient::from_url("http://localhost:8983").unwrap();
 
         let core = client.core("hoge").await;
         assert!(core.is_err());
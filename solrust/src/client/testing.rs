@@ -0,0 +1,116 @@
+//! Stub HTTP server used to test the client without the running Solr instance.
+
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// HTTP server that returns the given responses in order, one per connection, and records the received requests.
+pub(crate) struct StubServer {
+    pub(crate) url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StubServer {
+    pub(crate) fn start(responses: Vec<String>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = match listener.accept() {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let request = read_request(&mut stream);
+                received.lock().unwrap().push(request);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Return the received requests, each of which consists of the request line, the headers and the body.
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Build the raw HTTP response, whose content type is JSON unless given in the headers.
+pub(crate) fn response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+    let mut headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    if !headers.to_lowercase().contains("content-type:") {
+        headers.push_str("Content-Type: application/json\r\n");
+    }
+
+    format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+        status,
+        body.len(),
+        headers,
+        body
+    )
+}
+
+/// Build the response of `/solr/admin/cores` that lists the cores of the names.
+pub(crate) fn core_list(names: &[&str]) -> String {
+    let status: serde_json::Map<String, Value> = names
+        .iter()
+        .map(|name| {
+            (
+                name.to_string(),
+                json!({
+                    "name": name,
+                    "instanceDir": format!("/var/solr/data/{}", name),
+                    "dataDir": format!("/var/solr/data/{}/data/", name),
+                    "config": "solrconfig.xml",
+                    "schema": "managed-schema",
+                    "startTime": "2024-01-01T00:00:00.000Z",
+                    "uptime": 1000
+                }),
+            )
+        })
+        .collect();
+
+    response(
+        "200 OK",
+        &[],
+        &json!({"responseHeader": {"status": 0, "QTime": 1}, "initFailures": {}, "status": status})
+            .to_string(),
+    )
+}
+
+fn read_request(stream: &mut impl Read) -> String {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let n = match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        buffer.extend_from_slice(&chunk[..n]);
+
+        let request = String::from_utf8_lossy(&buffer);
+        if let Some(end) = request.find("\r\n\r\n") {
+            let length = request[..end]
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if buffer.len() >= end + 4 + length {
+                break;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&buffer).into_owned()
+}
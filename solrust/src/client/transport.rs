@@ -0,0 +1,743 @@
+//! This module defines the transport that sends the HTTP requests to Solr.
+//!
+//! The transport is shared by SolrClient and the SolrCore structs created from it,
+//! so that the HTTP client, the authentication, the retry policy, the circuit breaker and the load balancer are configured in one place.
+
+use crate::client::auth::{SolrAuth, TokenError};
+use crate::client::circuit_breaker::CircuitBreaker;
+use crate::client::lb::LoadBalancer;
+use crate::client::middleware::{Middleware, Next};
+use crate::client::observer::{RequestInfo, RequestObserver, RequestOutcome};
+use crate::client::retry::RetryPolicy;
+use crate::types::response::SolrErrorInfo;
+use bytes::{Bytes, BytesMut};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Body, Client, Method, Request, RequestBuilder, Response, StatusCode};
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+use std::time::Instant;
+use thiserror::Error;
+use tracing::Instrument;
+use url::Url;
+
+/// Value of the redacted parameters in the errors.
+const REDACTED: &str = "<redacted>";
+
+/// Default maximum length of the URL of GET requests, which leaves room for the headers
+/// in the 8KB request header buffer of Jetty.
+pub(crate) const DEFAULT_MAX_URL_LENGTH: usize = 7 * 1024;
+
+/// Header of the ID of the request, which correlates the logs of Solr with the errors of the client.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error("Failed to request to solr")]
+    RequestError(#[from] reqwest::Error),
+    #[error("Failed to get the authentication token")]
+    TokenError(#[source] TokenError),
+    #[error("Circuit breaker is open for node {0}")]
+    CircuitOpenError(String),
+    #[error("Middleware failed to process the request")]
+    MiddlewareError(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Response body exceeds the limit of {0} bytes")]
+    ResponseTooLarge(usize),
+}
+
+/// Implement the conversions from the errors of the transport and from the error responses,
+/// and the accessor of the request context, on the error type of the client.
+///
+/// The error types of the clients share the variants of the failed request, and this macro keeps their mapping in one place.
+macro_rules! impl_request_error {
+    ($error:ident) => {
+        impl $error {
+            /// Return the context of the failed request, if the error occurred in the request.
+            pub fn context(&self) -> Option<&$crate::client::transport::RequestContext> {
+                match self {
+                    $error::RequestError { context, .. }
+                    | $error::AuthenticationError { context, .. }
+                    | $error::CircuitOpenError { context, .. }
+                    | $error::MiddlewareError { context, .. }
+                    | $error::Timeout { context, .. }
+                    | $error::ResponseTooLarge { context, .. }
+                    | $error::BadRequest { context, .. }
+                    | $error::Unauthorized { context }
+                    | $error::Forbidden { context }
+                    | $error::NotFound { context }
+                    | $error::ServiceUnavailable { context }
+                    | $error::Conflict { context, .. }
+                    | $error::UnparsableResponseError { context, .. } => Some(context.as_ref()),
+                    _ => None,
+                }
+            }
+
+            /// Convert the error of the transport, distinguishing timeouts from other failures.
+            fn from_transport_error(
+                e: $crate::client::transport::TransportError,
+                context: Box<$crate::client::transport::RequestContext>,
+            ) -> Self {
+                use $crate::client::transport::TransportError;
+
+                match e {
+                    TransportError::RequestError(source) if source.is_timeout() => {
+                        $error::Timeout { context, source }
+                    }
+                    TransportError::RequestError(source) => {
+                        $error::RequestError { context, source }
+                    }
+                    TransportError::TokenError(source) => {
+                        $error::AuthenticationError { context, source }
+                    }
+                    TransportError::CircuitOpenError(node) => {
+                        $error::CircuitOpenError { node, context }
+                    }
+                    TransportError::MiddlewareError(source) => {
+                        $error::MiddlewareError { context, source }
+                    }
+                    TransportError::ResponseTooLarge(limit) => {
+                        $error::ResponseTooLarge { context, limit }
+                    }
+                }
+            }
+
+            /// Convert the error response to the error, determined from the HTTP status before parsing the body.
+            fn from_response(
+                response: &$crate::client::transport::TransportResponse,
+                context: Box<$crate::client::transport::RequestContext>,
+            ) -> Option<Self> {
+                use reqwest::StatusCode;
+
+                let e = match response.status {
+                    StatusCode::BAD_REQUEST => $error::BadRequest {
+                        context,
+                        info: response.error_info(),
+                    },
+                    StatusCode::UNAUTHORIZED => $error::Unauthorized { context },
+                    StatusCode::FORBIDDEN => $error::Forbidden { context },
+                    StatusCode::NOT_FOUND => $error::NotFound { context },
+                    StatusCode::SERVICE_UNAVAILABLE => $error::ServiceUnavailable { context },
+                    StatusCode::CONFLICT => $error::Conflict {
+                        context,
+                        info: response.error_info(),
+                    },
+                    _ if response.is_unparsable_error() => $error::UnparsableResponseError {
+                        context,
+                        status: response.status.as_u16(),
+                        content_type: response.content_type.clone(),
+                        body: response.body_snippet(),
+                    },
+                    _ => return None,
+                };
+
+                Some(e)
+            }
+        }
+    };
+}
+pub(crate) use impl_request_error;
+
+/// Context of the request attached to the errors, to tell which request failed.
+#[derive(Clone, Debug)]
+pub struct RequestContext {
+    pub method: Method,
+    /// URL of the request, in which the values of the redacted parameters are masked.
+    pub url: Url,
+    /// Name of the core or the collection to which the request is sent.
+    pub core: Option<String>,
+    /// Value of the `X-Request-Id` header of the request.
+    pub request_id: Option<String>,
+}
+
+impl RequestContext {
+    /// Return the query parameters of the request.
+    pub fn params(&self) -> Vec<(String, String)> {
+        self.url.query_pairs().into_owned().collect()
+    }
+}
+
+impl Display for RequestContext {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        if let Some(core) = &self.core {
+            write!(f, " (core: {})", core)?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request id: {})", request_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Options on how the requests are sent and how the responses are read, e.g.
+///
+/// ```ignore
+/// let options = TransportOptions::new().request_id(true).max_response_size(16 * 1024 * 1024);
+/// let client = SolrClient::builder_from_url("http://localhost:8983")
+///     .transport_options(options)
+///     .build()?;
+/// ```
+///
+/// The options given to a client are passed on to the cores created by the client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransportOptions {
+    pub(crate) request_id: bool,
+    pub(crate) lossy_utf8: bool,
+    pub(crate) max_response_size: Option<usize>,
+    pub(crate) max_url_length: Option<usize>,
+}
+
+impl Default for TransportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransportOptions {
+    pub fn new() -> Self {
+        Self {
+            request_id: false,
+            lossy_utf8: false,
+            max_response_size: None,
+            max_url_length: Some(DEFAULT_MAX_URL_LENGTH),
+        }
+    }
+
+    /// Set whether to attach `X-Request-Id` header with the generated ID to every request without the header.
+    ///
+    /// The ID is included in the context of the errors and in the tracing span of the request,
+    /// which correlates the failures with the logs of Solr.
+    pub fn request_id(mut self, enable: bool) -> Self {
+        self.request_id = enable;
+        self
+    }
+
+    /// Set whether to replace the invalid UTF-8 sequences of the response bodies with U+FFFD.
+    ///
+    /// A legacy index may contain the stored values that are not valid UTF-8, which fail the deserialization of the whole response.
+    /// Use [`SolrLenient`](crate::types::lenient::SolrLenient) to tolerate the unknown values of the fields as well.
+    pub fn lossy_utf8(mut self, enable: bool) -> Self {
+        self.lossy_utf8 = enable;
+        self
+    }
+
+    /// Set the maximum size of the response bodies in bytes.
+    ///
+    /// When the body exceeds the size, the download is aborted and the methods return `ResponseTooLarge` error,
+    /// instead of buffering the whole body into the memory, e.g. of a query with a huge `rows` by mistake.
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Set the maximum length of the URL of GET requests, 7KB by default.
+    ///
+    /// The request whose URL exceeds the length, e.g. with a huge filter query, is sent as POST request
+    /// with the parameters in the form body, instead of being rejected by Jetty with 414 URI Too Long.
+    /// None always sends GET requests.
+    ///
+    /// The select sent as POST request is still retried by the retry policy.
+    pub fn max_url_length(mut self, length: Option<usize>) -> Self {
+        self.max_url_length = length;
+        self
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Transport {
+    pub(crate) client: Client,
+    pub(crate) auth: Option<SolrAuth>,
+    pub(crate) retry: Option<RetryPolicy>,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    pub(crate) load_balancer: Option<LoadBalancer>,
+    pub(crate) observer: Option<Arc<dyn RequestObserver>>,
+    pub(crate) middlewares: Vec<Arc<dyn Middleware>>,
+    /// Names of the parameters whose values are masked in the errors.
+    pub(crate) redacted_params: Vec<String>,
+    /// Headers attached to every request, unless the request has the header of the same name.
+    pub(crate) headers: HeaderMap,
+    pub(crate) options: TransportOptions,
+}
+
+impl Debug for Transport {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Transport")
+            .field("client", &self.client)
+            .field("auth", &self.auth)
+            .field("retry", &self.retry)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("load_balancer", &self.load_balancer)
+            .field("observer", &self.observer.as_ref().map(|_| "<observer>"))
+            .field("middlewares", &self.middlewares.len())
+            .field("redacted_params", &self.redacted_params)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+/// Response of Solr read by the transport.
+#[derive(Debug)]
+pub(crate) struct TransportResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) content_type: Option<String>,
+    /// Body of the response, which is deserialized with `serde_json::from_slice` without copying it into a string.
+    pub(crate) body: Bytes,
+}
+
+impl TransportResponse {
+    /// Read the response, failing as soon as the body exceeds the limit without buffering the rest of it.
+    async fn read(mut response: Response, limit: Option<usize>) -> Result<Self, TransportError> {
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        let body = match limit {
+            None => response.bytes().await?,
+            Some(limit) => {
+                if response
+                    .content_length()
+                    .is_some_and(|length| length > limit as u64)
+                {
+                    return Err(TransportError::ResponseTooLarge(limit));
+                }
+
+                let mut body = BytesMut::new();
+                while let Some(chunk) = response.chunk().await? {
+                    if body.len() + chunk.len() > limit {
+                        return Err(TransportError::ResponseTooLarge(limit));
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                body.freeze()
+            }
+        };
+
+        Ok(Self {
+            status,
+            content_type,
+            body,
+        })
+    }
+
+    /// Return true if the response is an error whose body is not JSON, such as an HTML error page of a proxy.
+    pub(crate) fn is_unparsable_error(&self) -> bool {
+        !self.status.is_success() && serde_json::from_slice::<IgnoredAny>(&self.body).is_err()
+    }
+
+    /// Return the `error` field of the response JSON, or the information built from the status and the body
+    /// if the body does not contain it.
+    pub(crate) fn error_info(&self) -> SolrErrorInfo {
+        serde_json::from_slice::<ErrorOnly>(&self.body)
+            .ok()
+            .and_then(|response| response.error)
+            .unwrap_or_else(|| SolrErrorInfo {
+                metadata: HashMap::new(),
+                msg: self.body_snippet(),
+                code: self.status.as_u16() as u32,
+            })
+    }
+
+    /// Return the body as a string, replacing the invalid UTF-8 sequences.
+    pub(crate) fn into_text(self) -> String {
+        match String::from_utf8(Vec::from(self.body)) {
+            Ok(text) => text,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }
+    }
+
+    /// Return `QTime` of the response header in the body, if any.
+    pub(crate) fn qtime(&self) -> Option<u32> {
+        serde_json::from_slice::<ResponseHeaderOnly>(&self.body)
+            .ok()
+            .and_then(|response| response.header)
+            .and_then(|header| header.qtime)
+    }
+
+    /// Replace the invalid UTF-8 sequences of the body with U+FFFD, leaving the valid body as it is.
+    fn replace_invalid_utf8(&mut self) {
+        if std::str::from_utf8(&self.body).is_err() {
+            self.body = Bytes::from(String::from_utf8_lossy(&self.body).into_owned());
+        }
+    }
+
+    /// Return the beginning of the body to be attached to the error.
+    pub(crate) fn body_snippet(&self) -> String {
+        const MAX_LENGTH: usize = 1024;
+        String::from_utf8_lossy(&self.body)
+            .chars()
+            .take(MAX_LENGTH)
+            .collect()
+    }
+}
+
+/// Part of the response JSON read to build the error.
+#[derive(Deserialize)]
+struct ErrorOnly {
+    error: Option<SolrErrorInfo>,
+}
+
+/// Part of the response JSON read to notify the observer of `QTime`.
+#[derive(Deserialize)]
+struct ResponseHeaderOnly {
+    #[serde(alias = "responseHeader")]
+    header: Option<QTimeOnly>,
+}
+
+#[derive(Deserialize)]
+struct QTimeOnly {
+    #[serde(alias = "QTime")]
+    qtime: Option<u32>,
+}
+
+impl Transport {
+    pub(crate) fn new(client: Client) -> Self {
+        Self {
+            client,
+            auth: None,
+            retry: None,
+            circuit_breaker: None,
+            load_balancer: None,
+            observer: None,
+            middlewares: Vec::new(),
+            redacted_params: Vec::new(),
+            headers: HeaderMap::new(),
+            options: TransportOptions::new(),
+        }
+    }
+
+    /// Attach the default headers and the generated request ID to the request, unless it has the headers already.
+    pub(crate) fn prepare(&self, request: &mut Request) {
+        for name in self.headers.keys() {
+            if !request.headers().contains_key(name) {
+                for value in self.headers.get_all(name) {
+                    request.headers_mut().append(name.clone(), value.clone());
+                }
+            }
+        }
+
+        if self.options.request_id && !request.headers().contains_key(REQUEST_ID_HEADER) {
+            let id = format!("{:032x}", rand::random::<u128>());
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                request.headers_mut().insert(REQUEST_ID_HEADER, value);
+            }
+        }
+    }
+
+    /// Return the context of the request to be attached to the errors, masking the values of the redacted parameters.
+    pub(crate) fn context(&self, request: &Request, core: Option<&str>) -> RequestContext {
+        let mut url = request.url().clone();
+        if !self.redacted_params.is_empty() {
+            let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+            url.query_pairs_mut()
+                .clear()
+                .extend_pairs(params.iter().map(|(key, value)| {
+                    if self.redacted_params.contains(key) {
+                        (key.as_str(), REDACTED)
+                    } else {
+                        (key.as_str(), value.as_str())
+                    }
+                }));
+        }
+
+        RequestContext {
+            method: request.method().clone(),
+            url,
+            core: core.map(String::from),
+            request_id: request_id(request).map(String::from),
+        }
+    }
+
+    /// Send the GET request whose URL is too long as POST request with the parameters in the form body,
+    /// which Solr accepts as well, instead of failing with 414 URI Too Long.
+    fn post_if_too_long(&self, request: &mut Request) {
+        let limit = match self.options.max_url_length {
+            Some(limit) => limit,
+            None => return,
+        };
+        if *request.method() != Method::GET || request.url().as_str().len() <= limit {
+            return;
+        }
+        let query = match request.url().query() {
+            Some(query) => query.to_string(),
+            None => return,
+        };
+
+        request.url_mut().set_query(None);
+        *request.method_mut() = Method::POST;
+        request.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        *request.body_mut() = Some(Body::from(query));
+    }
+
+    /// Send the request and read the response body in the tracing span of the request.
+    ///
+    /// The GET request with the long URL is sent as POST request, after the context of the errors is taken.
+    /// Only the idempotent requests, which do not change the state of Solr, are retried by the retry policy.
+    pub(crate) async fn execute(
+        &self,
+        mut request: Request,
+        idempotent: bool,
+    ) -> Result<TransportResponse, TransportError> {
+        self.post_if_too_long(&mut request);
+        let span = tracing::debug_span!(
+            "solr_request",
+            method = %request.method(),
+            path = request.url().path(),
+            request_id = tracing::field::Empty,
+            trace_id = tracing::field::Empty,
+            http.response.status_code = tracing::field::Empty,
+            solr.qtime = tracing::field::Empty,
+        );
+        if let Some(id) = request_id(&request) {
+            span.record("request_id", id);
+        }
+
+        let mut response = self
+            .observe(request, idempotent)
+            .instrument(span.clone())
+            .await?;
+        #[cfg(feature = "otel")]
+        {
+            span.record("http.response.status_code", response.status.as_u16());
+            if let Some(qtime) = response.qtime() {
+                span.record("solr.qtime", qtime);
+            }
+        }
+        if self.options.lossy_utf8 {
+            response.replace_invalid_utf8();
+        }
+
+        Ok(response)
+    }
+
+    /// Send the request and read the response body, notifying the observer of the request.
+    async fn observe(
+        &self,
+        request: Request,
+        idempotent: bool,
+    ) -> Result<TransportResponse, TransportError> {
+        let observer = match &self.observer {
+            Some(observer) => observer,
+            None => {
+                return TransportResponse::read(
+                    self.send_request(request, idempotent).await?,
+                    self.options.max_response_size,
+                )
+                .await
+            }
+        };
+
+        let info = RequestInfo {
+            method: request.method().clone(),
+            url: request.url().clone(),
+        };
+        observer.on_request_start(&info);
+        let start = Instant::now();
+
+        let mut status = None;
+        let result = match self.send_request(request, idempotent).await {
+            Ok(response) => {
+                status = Some(response.status().as_u16());
+                TransportResponse::read(response, self.options.max_response_size).await
+            }
+            Err(e) => Err(e),
+        };
+
+        let qtime = result.as_ref().ok().and_then(TransportResponse::qtime);
+        observer.on_request_end(
+            &info,
+            &RequestOutcome {
+                duration: start.elapsed(),
+                status,
+                qtime,
+            },
+        );
+
+        result
+    }
+
+    /// Send the request, retrying it according to the retry policy if the request is idempotent.
+    ///
+    /// The idempotency is given by the caller, because some GET requests change the state of Solr,
+    /// e.g. CoreAdmin `SWAP` and Collections API `CREATE`, and some POST requests do not, e.g. the long select.
+    async fn send_request(
+        &self,
+        request: Request,
+        idempotent: bool,
+    ) -> Result<Response, TransportError> {
+        let policy = match &self.retry {
+            Some(policy) if idempotent => policy,
+            _ => return self.attempt(request).await,
+        };
+
+        let mut attempts = 1;
+        loop {
+            // A request with a streaming body can not be cloned, so it is sent only once.
+            let current = match request.try_clone() {
+                Some(current) => current,
+                None => return self.attempt(request).await,
+            };
+
+            let delay = match self.attempt(current).await {
+                Ok(response)
+                    if policy.has_attempts_left(attempts)
+                        && policy.should_retry_response(&response) =>
+                {
+                    policy
+                        .retry_after(&response)
+                        .unwrap_or_else(|| policy.backoff(attempts))
+                }
+                Err(TransportError::RequestError(e))
+                    if policy.has_attempts_left(attempts) && policy.should_retry_error(&e) =>
+                {
+                    policy.backoff(attempts)
+                }
+                result => return result,
+            };
+
+            tokio::time::sleep(delay).await;
+            attempts += 1;
+        }
+    }
+
+    /// Send the request once. With the load balancer, the request is sent to the next node when the connection failed
+    /// or the circuit of the node is open.
+    async fn attempt(&self, request: Request) -> Result<Response, TransportError> {
+        let lb = match &self.load_balancer {
+            Some(lb) => lb,
+            None => return self.attempt_node(request).await,
+        };
+
+        let mut last = None;
+        for node in lb.candidates() {
+            let mut current = match request.try_clone() {
+                Some(current) => current,
+                // A request with a streaming body can not be sent to the other nodes.
+                None => {
+                    let mut request = request;
+                    lb.route(&mut request, node);
+                    return self.attempt_node(request).await;
+                }
+            };
+            lb.route(&mut current, node);
+
+            match self.attempt_node(current).await {
+                Err(TransportError::RequestError(e)) if e.is_connect() => {
+                    lb.mark_dead(node);
+                    last = Some(TransportError::RequestError(e));
+                }
+                Err(TransportError::CircuitOpenError(node)) => {
+                    last = Some(TransportError::CircuitOpenError(node));
+                }
+                result => {
+                    if result.is_ok() {
+                        lb.mark_alive(node);
+                    }
+                    return result;
+                }
+            }
+        }
+
+        Err(last.expect("the load balancer has at least one node"))
+    }
+
+    /// Send the request once to the node in the URL, unless the circuit of the node is open.
+    async fn attempt_node(&self, request: Request) -> Result<Response, TransportError> {
+        let breaker = match &self.circuit_breaker {
+            Some(breaker) => breaker,
+            None => return self.send_with_auth(request).await,
+        };
+
+        let node = request.url().origin().ascii_serialization();
+        if !breaker.try_acquire(&node) {
+            return Err(TransportError::CircuitOpenError(node));
+        }
+
+        let result = self.send_with_auth(request).await;
+        match &result {
+            Ok(response) if !response.status().is_server_error() => breaker.record_success(&node),
+            // The request was not sent, so the node is neither healthy nor failing.
+            Err(TransportError::TokenError(_)) => breaker.release(&node),
+            _ => breaker.record_failure(&node),
+        }
+
+        result
+    }
+
+    /// Send the request with the authentication.
+    ///
+    /// If Solr responds with `401 Unauthorized` and the credentials can be refreshed, the request is sent once again with the refreshed credentials.
+    async fn send_with_auth(&self, request: Request) -> Result<Response, TransportError> {
+        let auth = match &self.auth {
+            Some(auth) => auth,
+            None => return self.dispatch(request).await,
+        };
+
+        let retry = request.try_clone();
+        let request = auth
+            .apply(RequestBuilder::from_parts(self.client.clone(), request))
+            .await
+            .map_err(TransportError::TokenError)?
+            .build()?;
+        let response = self.dispatch(request).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Some(retry) = retry {
+                if auth.refresh().await.map_err(TransportError::TokenError)? {
+                    let retry = auth
+                        .apply(RequestBuilder::from_parts(self.client.clone(), retry))
+                        .await
+                        .map_err(TransportError::TokenError)?
+                        .build()?;
+                    return self.dispatch(retry).await;
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Send the request through the middlewares.
+    async fn dispatch(&self, request: Request) -> Result<Response, TransportError> {
+        Next::new(&self.client, &self.middlewares)
+            .run(request)
+            .await
+    }
+}
+
+/// Return the value of `X-Request-Id` header of the request.
+fn request_id(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_replace_invalid_utf8() {
+        let mut response = TransportResponse {
+            status: StatusCode::OK,
+            content_type: None,
+            body: Bytes::from_static(b"{\"name\":\"caf\xe9\"}"),
+        };
+        assert!(serde_json::from_slice::<Value>(&response.body).is_err());
+
+        response.replace_invalid_utf8();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&response.body).unwrap(),
+            serde_json::json!({"name": "caf\u{fffd}"})
+        );
+    }
+}
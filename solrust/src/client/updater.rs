@@ -0,0 +1,368 @@
+//! This module defines the updater that queues the documents and posts them to the core in batches in the background,
+//! like `ConcurrentUpdateSolrClient` of SolrJ.
+
+use crate::client::core::{SolrCore, SolrCoreError};
+use serde::Serialize;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+type Result<T> = std::result::Result<T, BufferedUpdaterError>;
+
+#[derive(Debug, Error)]
+pub enum BufferedUpdaterError {
+    #[error("Failed to serialize the document")]
+    SerializeError(#[from] serde_json::Error),
+    #[error("Failed to post {documents} documents to solr core")]
+    FlushError {
+        /// Number of the documents in the failed batch, which are discarded.
+        documents: usize,
+        #[source]
+        source: SolrCoreError,
+    },
+    #[error("Updater is already closed")]
+    Closed,
+}
+
+/// Command sent to the background task.
+enum Command {
+    Add(Vec<u8>),
+    Flush(oneshot::Sender<Result<()>>),
+}
+
+/// Builder of [`BufferedUpdater`].
+pub struct BufferedUpdaterBuilder {
+    core: SolrCore,
+    max_documents: usize,
+    max_bytes: usize,
+    flush_interval: Option<Duration>,
+    queue_size: usize,
+    params: Vec<(String, String)>,
+}
+
+impl BufferedUpdaterBuilder {
+    /// Create the builder that flushes every 1000 documents, 10 MiB or 1 second, whichever comes first.
+    pub fn new(core: SolrCore) -> Self {
+        Self {
+            core,
+            max_documents: 1000,
+            max_bytes: 10 * 1024 * 1024,
+            flush_interval: Some(Duration::from_secs(1)),
+            queue_size: 1000,
+            params: Vec::new(),
+        }
+    }
+
+    /// Set the number of the buffered documents at which they are flushed.
+    pub fn max_documents(mut self, max_documents: usize) -> Self {
+        self.max_documents = max_documents.max(1);
+        self
+    }
+
+    /// Set the total size of the serialized documents in bytes at which they are flushed.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes.max(1);
+        self
+    }
+
+    /// Set the maximum time the first buffered document waits before it is flushed. None disables the periodic flush.
+    pub fn flush_interval(mut self, interval: Option<Duration>) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Set the number of the documents queued to the background task, beyond which [`BufferedUpdater::add`] waits.
+    pub fn queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size.max(1);
+        self
+    }
+
+    /// Add the parameter of the update requests, e.g. `commitWithin`.
+    pub fn param(mut self, key: &str, value: &str) -> Self {
+        self.params.push((String::from(key), String::from(value)));
+        self
+    }
+
+    /// Start the background task of the updater.
+    ///
+    /// This method must be called in the context of the tokio runtime.
+    pub fn build(self) -> BufferedUpdater {
+        let (sender, receiver) = mpsc::channel(self.queue_size);
+        let worker = Worker {
+            core: self.core,
+            max_documents: self.max_documents,
+            max_bytes: self.max_bytes,
+            flush_interval: self.flush_interval,
+            params: self.params,
+            buffer: Vec::new(),
+            bytes: 0,
+            deadline: None,
+            error: None,
+        };
+
+        BufferedUpdater {
+            sender,
+            handle: tokio::spawn(worker.run(receiver)),
+        }
+    }
+}
+
+/// Updater that queues the documents and posts them to `/update` of the core in the background, e.g.
+///
+/// ```ignore
+/// let updater = BufferedUpdater::builder(core)
+///     .max_documents(500)
+///     .flush_interval(Some(Duration::from_secs(5)))
+///     .build();
+/// for document in documents {
+///     updater.add(&document).await?;
+/// }
+/// updater.close().await?;
+/// ```
+///
+/// The documents are flushed when the number of them or their size reaches the threshold, or when the interval elapses.
+/// The failure of the flush in the background is returned by the next [`BufferedUpdater::flush`] or [`BufferedUpdater::close`].
+/// The documents are not committed, so use `commitWithin` parameter or commit them after closing the updater.
+pub struct BufferedUpdater {
+    sender: mpsc::Sender<Command>,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl BufferedUpdater {
+    pub fn builder(core: SolrCore) -> BufferedUpdaterBuilder {
+        BufferedUpdaterBuilder::new(core)
+    }
+
+    /// Queue the document, waiting when the queue is full.
+    pub async fn add(&self, document: &impl Serialize) -> Result<()> {
+        let document = serde_json::to_vec(document)?;
+        self.sender
+            .send(Command::Add(document))
+            .await
+            .map_err(|_| BufferedUpdaterError::Closed)
+    }
+
+    /// Post the queued documents, and return the failure of the flushes since the last call if any.
+    pub async fn flush(&self) -> Result<()> {
+        let (reply, result) = oneshot::channel();
+        self.sender
+            .send(Command::Flush(reply))
+            .await
+            .map_err(|_| BufferedUpdaterError::Closed)?;
+
+        result.await.map_err(|_| BufferedUpdaterError::Closed)?
+    }
+
+    /// Post the queued documents and stop the background task.
+    pub async fn close(self) -> Result<()> {
+        drop(self.sender);
+        self.handle
+            .await
+            .map_err(|_| BufferedUpdaterError::Closed)?
+    }
+}
+
+/// State of the background task.
+struct Worker {
+    core: SolrCore,
+    max_documents: usize,
+    max_bytes: usize,
+    flush_interval: Option<Duration>,
+    params: Vec<(String, String)>,
+    /// Serialized documents not posted yet.
+    buffer: Vec<Vec<u8>>,
+    bytes: usize,
+    /// Time when the buffered documents are flushed by the interval.
+    deadline: Option<Instant>,
+    /// The first failure of the flushes since it is reported.
+    error: Option<BufferedUpdaterError>,
+}
+
+impl Worker {
+    async fn run(mut self, mut receiver: mpsc::Receiver<Command>) -> Result<()> {
+        loop {
+            let command = match self.deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                    Ok(command) => command,
+                    Err(_) => {
+                        self.flush().await;
+                        continue;
+                    }
+                },
+                None => receiver.recv().await,
+            };
+
+            match command {
+                Some(Command::Add(document)) => {
+                    if self.buffer.is_empty() {
+                        self.deadline = self
+                            .flush_interval
+                            .map(|interval| Instant::now() + interval);
+                    }
+                    self.bytes += document.len();
+                    self.buffer.push(document);
+
+                    if self.buffer.len() >= self.max_documents || self.bytes >= self.max_bytes {
+                        self.flush().await;
+                    }
+                }
+                Some(Command::Flush(reply)) => {
+                    self.flush().await;
+                    let _ = reply.send(self.take_error());
+                }
+                None => {
+                    self.flush().await;
+                    return self.take_error();
+                }
+            }
+        }
+    }
+
+    /// Post the buffered documents as a JSON array, keeping the failure to be reported.
+    async fn flush(&mut self) {
+        self.deadline = None;
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let documents = self.buffer.len();
+        let mut body = Vec::with_capacity(self.bytes + documents + 1);
+        body.push(b'[');
+        for (i, document) in self.buffer.drain(..).enumerate() {
+            if i > 0 {
+                body.push(b',');
+            }
+            body.extend(document);
+        }
+        body.push(b']');
+        self.bytes = 0;
+
+        let result = match self.core.post_with(body, &self.params).await {
+            Ok(response) => match response.error {
+                Some(error) => Err(SolrCoreError::UnexpectedError((error.code, error.msg))),
+                None => Ok(()),
+            },
+            Err(e) => Err(e),
+        };
+        if let Err(source) = result {
+            tracing::warn!("failed to post {} documents: {}", documents, source);
+            if self.error.is_none() {
+                self.error = Some(BufferedUpdaterError::FlushError { documents, source });
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::testing::{response, StubServer};
+    use serde_json::json;
+
+    fn ok() -> String {
+        response(
+            "200 OK",
+            &[],
+            r#"{"responseHeader":{"status":0,"QTime":1}}"#,
+        )
+    }
+
+    /// Normal test that the documents are flushed by the number of them and on closing.
+    #[tokio::test]
+    async fn test_flush_by_documents() {
+        let server = StubServer::start(vec![ok(), ok()]);
+        let core = SolrCore::new("example", &server.url);
+
+        let updater = BufferedUpdater::builder(core)
+            .max_documents(2)
+            .flush_interval(None)
+            .param("commitWithin", "1000")
+            .build();
+        for id in ["001", "002", "003"] {
+            updater.add(&json!({ "id": id })).await.unwrap();
+        }
+        updater.close().await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /solr/example/update?commitWithin=1000 "));
+        assert!(requests[0].ends_with(r#"[{"id":"001"},{"id":"002"}]"#));
+        assert!(requests[1].ends_with(r#"[{"id":"003"}]"#));
+    }
+
+    /// Normal test that the documents are flushed when the interval elapses.
+    ///
+    /// The time is paused, so the sleep advances the clock to the deadline of the flush without waiting.
+    #[tokio::test(start_paused = true)]
+    async fn test_flush_by_interval() {
+        let server = StubServer::start(vec![ok(), ok()]);
+        let core = SolrCore::new("example", &server.url);
+
+        let updater = BufferedUpdater::builder(core)
+            .flush_interval(Some(Duration::from_millis(50)))
+            .build();
+        updater.add(&json!({"id": "001"})).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        updater.add(&json!({"id": "002"})).await.unwrap();
+        updater.flush().await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].ends_with(r#"[{"id":"001"}]"#));
+        assert!(requests[1].ends_with(r#"[{"id":"002"}]"#));
+        updater.close().await.unwrap();
+    }
+
+    /// Normal test that the documents are flushed by their size.
+    #[tokio::test]
+    async fn test_flush_by_bytes() {
+        let server = StubServer::start(vec![ok(), ok()]);
+        let core = SolrCore::new("example", &server.url);
+
+        let updater = BufferedUpdater::builder(core).max_bytes(20).build();
+        for id in ["001", "002", "003"] {
+            updater.add(&json!({ "id": id })).await.unwrap();
+        }
+        updater.flush().await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].ends_with(r#"[{"id":"001"},{"id":"002"}]"#));
+        assert!(requests[1].ends_with(r#"[{"id":"003"}]"#));
+        updater.close().await.unwrap();
+    }
+
+    /// Abnormal test that the failure of the flush is returned by the next flush.
+    #[tokio::test]
+    async fn test_flush_error() {
+        let server = StubServer::start(vec![
+            response(
+                "400 Bad Request",
+                &[],
+                r#"{"responseHeader":{"status":400,"QTime":0},"error":{"metadata":[],"msg":"unknown field 'foo'","code":400}}"#,
+            ),
+            ok(),
+        ]);
+        let core = SolrCore::new("example", &server.url);
+
+        let updater = BufferedUpdater::builder(core).max_documents(1).build();
+        updater.add(&json!({"foo": "bar"})).await.unwrap();
+
+        assert!(matches!(
+            updater.flush().await,
+            Err(BufferedUpdaterError::FlushError { documents: 1, .. })
+        ));
+
+        updater.add(&json!({"id": "001"})).await.unwrap();
+        updater.close().await.unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 pub mod client;
 pub mod prelude;
 pub mod querybuilder;
+pub mod testing;
 pub mod types;
@@ -1,10 +1,24 @@
-pub use crate::client::core::SolrCore;
+pub use crate::client::auth::SolrAuth;
+pub use crate::client::cloud::SolrCloudClient;
+pub use crate::client::config::SolrConfig;
+pub use crate::client::core::{SolrCore, SolrCoreApi};
+pub use crate::client::lb::LBSolrClient;
 pub use crate::client::solr::SolrClient;
+pub use crate::client::transport::TransportOptions;
 
 pub use crate::querybuilder::common::SolrCommonQueryBuilder;
-pub use crate::querybuilder::dismax::{DisMaxQueryBuilder, SolrDisMaxQueryBuilder};
-pub use crate::querybuilder::edismax::{EDisMaxQueryBuilder, SolrEDisMaxQueryBuilder};
+pub use crate::querybuilder::dismax::{
+    DisMaxQueryBuilder, DisMaxQueryInput, SolrDisMaxQueryBuilder,
+};
+pub use crate::querybuilder::edismax::{EDisMaxQueryBuilder, SolrEDisMaxQueryBuilder, UserFields};
+pub use crate::querybuilder::filter::{FilterOp, SolrFilters};
 pub use crate::querybuilder::standard::{SolrStandardQueryBuilder, StandardQueryBuilder};
+pub use crate::querybuilder::update::UpdateRequestBuilder;
 
+pub use crate::querybuilder::fl::FlBuilder;
+pub use crate::querybuilder::function::FunctionQuery;
+pub use crate::querybuilder::mm::Mm;
 pub use crate::querybuilder::q::{Operator, QueryOperand};
 pub use crate::querybuilder::sort::SortOrderBuilder;
+
+pub use crate::types::document::SolrDocument;
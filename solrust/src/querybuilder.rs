@@ -1,8 +1,17 @@
 pub mod common;
+pub mod datemath;
 pub mod dismax;
 pub mod edismax;
 pub mod facet;
+pub mod filter;
+pub mod fl;
+pub mod function;
+pub mod highlight;
+pub mod json_facet;
+pub mod mm;
+pub mod params;
 pub mod q;
 pub mod sanitizer;
 pub mod sort;
 pub mod standard;
+pub mod update;
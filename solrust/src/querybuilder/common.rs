@@ -1,12 +1,18 @@
 //! This module provides definition and implementation of Solr Common Query Parser.
 
 use crate::querybuilder::facet::FacetBuilder;
+use crate::querybuilder::filter::SolrFilters;
+use crate::querybuilder::fl::FlBuilder;
+use crate::querybuilder::highlight::HighlightBuilder;
+use crate::querybuilder::params::SolrQueryParams;
 use crate::querybuilder::q::{Operator, SolrQueryExpression};
 use crate::querybuilder::sanitizer::SOLR_SPECIAL_CHARACTERS;
 use crate::querybuilder::sort::SortOrderBuilder;
+use crate::types::document::SolrDocument;
+use serde::{Deserialize, Serialize};
 use solrust_derive::SolrCommonQueryParser;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 /// The trait of builder that generates parameter for [Solr Common Query Parser](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html).
@@ -17,10 +23,17 @@ pub trait SolrCommonQueryBuilder {
     fn start(self, start: u32) -> Self;
     /// Add [rows parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#rows-parameter)
     fn rows(self, rows: u32) -> Self;
+    /// Add [cursorMark parameter](https://solr.apache.org/guide/solr/latest/query-guide/pagination-of-results.html#fetching-a-large-number-of-sorted-results-cursors)
+    ///
+    /// Use `*` for the first request, and `nextCursorMark` of the previous response for subsequent requests.
+    fn cursor_mark(self, cursor_mark: &str) -> Self;
     /// Add [fq parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fq-filter-query-parameter)
     ///
     /// `fq` parameter will be added as many times as this method is called.
     fn fq(self, fq: &impl SolrQueryExpression) -> Self;
+    /// Add [fq parameters](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fq-filter-query-parameter)
+    /// converted from the filter struct deriving [`SolrFilters`].
+    fn filters(self, filters: &impl SolrFilters) -> Self;
     /// Add [fq parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fq-filter-query-parameter) with local parameters.
     ///
     /// `fq` parameter will be added as many times as this method is called.
@@ -29,14 +42,33 @@ pub trait SolrCommonQueryBuilder {
         fq: &impl SolrQueryExpression,
         local_params: &[(impl Display, impl Display)],
     ) -> Self;
+    /// Add [fq parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fq-filter-query-parameter)
+    /// with the cache control and the cost, e.g. `{!cache=false cost=100 tag=x}...`.
+    ///
+    /// A non-cached filter with the cost of 100 or more is executed as a post filter if the query parser supports it,
+    /// such as `geofilt` or `frange`.
+    fn fq_with(self, fq: &impl SolrQueryExpression, options: FqOptions) -> Self;
     /// Add [fl parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fl-field-list-parameter)
-    fn fl(self, fl: String) -> Self;
+    fn fl(self, fl: &FlBuilder) -> Self;
+    /// Add [fl parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fl-field-list-parameter)
+    /// listing exactly the fields of the document struct, so that the response is deserialized into the struct.
+    fn fl_for<T: SolrDocument>(self) -> Self;
     /// Add parameters for [debug](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#debug-parameter).
     ///
     /// Calling this method will add the parameters `debug=all` and `debug.explain.structured=true`.
     fn debug(self) -> Self;
+    /// Add [explainOther parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#explainother-parameter)
+    ///
+    /// The score of the documents matching the expression is explained in the debug output,
+    /// to see why they did not match or rank higher.
+    fn explain_other(self, q: &impl SolrQueryExpression) -> Self;
+    /// Add [qt parameter](https://solr.apache.org/guide/solr/latest/configuration-guide/requesthandlers-searchcomponents.html) to select the request handler.
+    fn qt(self, qt: &str) -> Self;
     /// Add [wt parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#wt-parameter)
-    fn wt(self, wt: &str) -> Self;
+    ///
+    /// [`SolrCore::select`](crate::client::core::SolrCore::select) parses only the JSON response,
+    /// so use [`SolrCore::select_raw`](crate::client::core::SolrCore::select_raw) to get the response in the other formats.
+    fn wt(self, wt: ResponseFormat) -> Self;
     /// Add [facet parameters](https://solr.apache.org/guide/solr/latest/query-guide/faceting.html).
     ///
     /// facet parameters will be added as many times as this method is called.
@@ -49,28 +81,183 @@ pub trait SolrCommonQueryBuilder {
         facet: &impl FacetBuilder,
         local_params: &[(impl Display, impl Display)],
     ) -> Self;
+    /// Add [highlighting parameters](https://solr.apache.org/guide/solr/latest/query-guide/highlighting.html).
+    ///
+    /// The parameters of the previous call are overwritten, except for the per-field overrides of the other fields.
+    fn highlight(self, highlight: &HighlightBuilder) -> Self;
+    /// Add [echoParams parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#echoparams-parameter)
+    fn echo_params(self, echo_params: EchoParams) -> Self;
+    /// Add [omitHeader parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#omitheader-parameter)
+    fn omit_header(self, omit_header: bool) -> Self;
+    /// Add `canCancel` and `queryUUID` parameters, which make the query [cancellable](https://solr.apache.org/guide/solr/latest/deployment-guide/task-management.html)
+    /// with [`SolrCore::cancel_query`](crate::client::core::SolrCore::cancel_query) by the given UUID.
+    fn query_uuid(self, uuid: &str) -> Self;
+    /// Add [timeAllowed parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#timeallowed-parameter)
+    ///
+    /// The value is the amount of time, in milliseconds, allowed for a search to complete.
+    fn time_allowed(self, time_allowed: u32) -> Self;
+    /// Add [minExactCount parameter](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#minexactcount-parameter)
+    fn min_exact_count(self, min_exact_count: u32) -> Self;
+    /// Add [shards parameter](https://solr.apache.org/guide/solr/latest/deployment-guide/solrcloud-distributed-requests.html#limiting-which-shards-are-queried)
+    ///
+    /// The given shards are joined with commas.
+    fn shards(self, shards: &[&str]) -> Self;
+    /// Add [shards.tolerant parameter](https://solr.apache.org/guide/solr/latest/deployment-guide/solrcloud-distributed-requests.html#shards-tolerant-parameter)
+    fn shards_tolerant(self, tolerant: bool) -> Self;
+    /// Add [shards.preference parameter](https://solr.apache.org/guide/solr/latest/deployment-guide/solrcloud-distributed-requests.html#shards-preference-parameter)
+    fn shards_preference(self, preference: &str) -> Self;
+    /// Add [distrib parameter](https://solr.apache.org/guide/solr/latest/deployment-guide/solrcloud-distributed-requests.html#distrib-parameter)
+    fn distrib(self, distrib: bool) -> Self;
+    /// Add [_route_ parameter](https://solr.apache.org/guide/solr/latest/deployment-guide/solrcloud-distributed-requests.html#limiting-which-shards-are-queried)
+    fn route(self, route: &str) -> Self;
     /// Add `q.op` parameter.
     ///
     /// This parameter is not a Solr Common Query Parser parameter, but is defined here because it is used by all other query parsers.
     fn op(self, op: Operator) -> Self;
+    /// Build the parameters into [`SolrQueryParams`].
+    ///
+    /// The parameters are ordered by the keys, so that the same query always gives the same parameters.
+    fn into_params(self) -> SolrQueryParams;
     /// Build the parameters.
     fn build(self) -> Vec<(String, String)>;
+    /// Return the parameters built so far without consuming the builder.
+    fn to_params(&self) -> SolrQueryParams;
+    /// Return the URL-encoded query string of the parameters built so far, e.g. to log the query sent to Solr.
+    ///
+    /// The builders also implement [`Display`] rendering the same string.
+    fn to_query_string(&self) -> String;
     /// Escape [Solr special characters](https://solr.apache.org/guide/solr/latest/query-guide/standard-query-parser.html#escaping-special-characters).
     fn sanitize<'a>(&self, s: &'a str) -> Cow<'a, str>;
 }
 
+/// Value of `echoParams` parameter.
+///
+/// https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#echoparams-parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EchoParams {
+    Explicit,
+    All,
+    None,
+}
+
+impl Display for EchoParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EchoParams::Explicit => write!(f, "explicit"),
+            EchoParams::All => write!(f, "all"),
+            EchoParams::None => write!(f, "none"),
+        }
+    }
+}
+
+/// Local parameters of `fq` parameter to control the filter cache and the execution order.
+///
+/// https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#cache-local-parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FqOptions<'a> {
+    /// Whether the result of the filter is cached.
+    pub cache: bool,
+    /// Order of the execution of the non-cached filters. The filters of the lower cost are executed first.
+    pub cost: u32,
+    /// Tag of the filter, which is excluded in the facets.
+    pub tag: Option<&'a str>,
+}
+
+impl Default for FqOptions<'_> {
+    fn default() -> Self {
+        Self {
+            cache: true,
+            cost: 0,
+            tag: None,
+        }
+    }
+}
+
+impl FqOptions<'_> {
+    /// Prepend the local parameters to the filter query.
+    ///
+    /// If the filter query already starts with local parameters such as `{!frange l=0 u=5}`,
+    /// the options are merged into them, since Solr reads only the first local parameters.
+    pub fn apply(&self, fq: &str) -> String {
+        let mut options = format!("cache={} cost={}", self.cache, self.cost);
+        if let Some(tag) = self.tag {
+            options.push_str(&format!(" tag={}", tag));
+        }
+
+        match fq.strip_prefix("{!") {
+            Some(rest) => {
+                // Insert the options after the name of the query parser, which must come first.
+                let position = rest
+                    .find(|c: char| c.is_whitespace() || c == '}')
+                    .unwrap_or(rest.len());
+                let (parser, rest) = rest.split_at(position);
+                format!("{{!{} {}{}", parser, options, rest)
+            }
+            None => format!("{{!{}}}{}", options, fq),
+        }
+    }
+}
+
+/// Value of `wt` parameter, the format of the response.
+///
+/// https://solr.apache.org/guide/solr/latest/query-guide/response-writers.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    Xml,
+    Csv,
+}
+
+impl ResponseFormat {
+    /// Parse the value of `wt` parameter.
+    pub fn from_wt(wt: &str) -> Option<Self> {
+        match wt {
+            "json" => Some(ResponseFormat::Json),
+            "xml" => Some(ResponseFormat::Xml),
+            "csv" => Some(ResponseFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ResponseFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ResponseFormat::Json => write!(f, "json"),
+            ResponseFormat::Xml => write!(f, "xml"),
+            ResponseFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
 /// Implementation of Solr Common Query Parser.
-#[derive(SolrCommonQueryParser)]
+///
+/// The builders can be (de)serialized with serde as a preset of the query, which is loaded from the configuration
+/// and extended per request:
+///
+/// ```ignore
+/// let preset: EDisMaxQueryBuilder = serde_json::from_str(r#"{"params": {"qf": "title^10 body"}}"#)?;
+/// let builder = preset.clone().q(&DisMaxQueryInput::from("rust"));
+/// ```
+///
+/// The parameters of the preset are added to the default parameters of the builder, such as `defType`.
+#[derive(Clone, Debug, Serialize, SolrCommonQueryParser)]
 pub struct CommonQueryBuilder {
-    params: HashMap<String, String>,
-    multi_params: HashMap<String, Vec<String>>,
+    params: BTreeMap<String, String>,
+    multi_params: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for CommonQueryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CommonQueryBuilder {
     pub fn new() -> Self {
         Self {
-            params: HashMap::new(),
-            multi_params: HashMap::new(),
+            params: BTreeMap::new(),
+            multi_params: BTreeMap::new(),
         }
     }
 }
@@ -79,6 +266,7 @@ impl CommonQueryBuilder {
 mod test {
     use super::*;
     use crate::querybuilder::facet::{FieldFacetBuilder, FieldFacetSortOrder, RangeFacetBuilder};
+    use crate::querybuilder::highlight::HighlightFieldBuilder;
     use crate::querybuilder::q::QueryOperand;
 
     #[test]
@@ -89,7 +277,11 @@ mod test {
 
     #[test]
     fn test_w() {
-        let sort = SortOrderBuilder::new().desc("score").asc("age");
+        let sort = SortOrderBuilder::new()
+            .desc("score")
+            .unwrap()
+            .asc("age")
+            .unwrap();
         let builder = CommonQueryBuilder::new().sort(&sort);
 
         assert_eq!(
@@ -110,6 +302,15 @@ mod test {
         assert_eq!(builder.build(), vec![("rows".to_string(), 50.to_string())]);
     }
 
+    #[test]
+    fn test_with_cursor_mark() {
+        let builder = CommonQueryBuilder::new().cursor_mark("*");
+        assert_eq!(
+            builder.build(),
+            vec![("cursorMark".to_string(), "*".to_string())]
+        );
+    }
+
     #[test]
     fn test_with_fq() {
         let op = QueryOperand::from("name:alice");
@@ -132,6 +333,116 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_fq_with_options() {
+        let op = QueryOperand::from("{!frange l=0 u=5}geodist(location,35.5,139.0)");
+        let builder = CommonQueryBuilder::new().fq_with(
+            &op,
+            FqOptions {
+                cache: false,
+                cost: 100,
+                tag: Some("distance"),
+            },
+        );
+
+        assert_eq!(
+            builder.build(),
+            vec![(
+                "fq".to_string(),
+                "{!frange cache=false cost=100 tag=distance l=0 u=5}geodist(location,35.5,139.0)"
+                    .to_string()
+            )],
+        );
+    }
+
+    #[test]
+    fn test_fq_with_default_options() {
+        let op = QueryOperand::from("name:alice");
+        let builder = CommonQueryBuilder::new().fq_with(&op, FqOptions::default());
+
+        assert_eq!(
+            builder.build(),
+            vec![(
+                "fq".to_string(),
+                "{!cache=true cost=0}name:alice".to_string()
+            )],
+        );
+    }
+
+    #[test]
+    fn test_explain_other() {
+        let builder = CommonQueryBuilder::new()
+            .debug()
+            .explain_other(&QueryOperand::from("id:002"));
+
+        let mut expected = vec![
+            ("debug".to_string(), "all".to_string()),
+            ("debug.explain.structured".to_string(), "true".to_string()),
+            ("explainOther".to_string(), "id:002".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_query_uuid() {
+        let builder = CommonQueryBuilder::new().query_uuid("5d5c4c4e-2f0a-4a3b-9d55-0e5f1c0c7a11");
+
+        assert_eq!(
+            builder.build(),
+            vec![
+                ("canCancel".to_string(), "true".to_string()),
+                (
+                    "queryUUID".to_string(),
+                    "5d5c4c4e-2f0a-4a3b-9d55-0e5f1c0c7a11".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_query_string() {
+        let builder = CommonQueryBuilder::new()
+            .fq(&QueryOperand::from("name:alice"))
+            .fq(&QueryOperand::from("age:[20 TO *]"));
+
+        assert_eq!(
+            builder.to_query_string(),
+            "fq=name%3Aalice&fq=age%3A%5B20+TO+*%5D"
+        );
+        assert_eq!(builder.to_string(), builder.to_query_string());
+        // The builder is not consumed.
+        assert_eq!(builder.build().len(), 2);
+    }
+
+    #[test]
+    fn test_build_in_deterministic_order() {
+        let builder = CommonQueryBuilder::new()
+            .rows(10)
+            .fq(&QueryOperand::from("name:alice"))
+            .start(0)
+            .debug()
+            .fq(&QueryOperand::from("age:24"));
+
+        // The single-valued parameters come first in the order of the keys, followed by the multi-valued ones.
+        assert_eq!(
+            builder.build(),
+            vec![
+                (String::from("debug"), String::from("all")),
+                (
+                    String::from("debug.explain.structured"),
+                    String::from("true")
+                ),
+                (String::from("rows"), String::from("10")),
+                (String::from("start"), String::from("0")),
+                (String::from("fq"), String::from("name:alice")),
+                (String::from("fq"), String::from("age:24")),
+            ]
+        );
+    }
+
     #[test]
     fn test_with_multiple_fq() {
         let builder = CommonQueryBuilder::new()
@@ -149,7 +460,8 @@ mod test {
 
     #[test]
     fn test_with_fl() {
-        let builder = CommonQueryBuilder::new().fl(String::from("id,name"));
+        let fl = FlBuilder::new().fields(&["id", "name"]);
+        let builder = CommonQueryBuilder::new().fl(&fl);
 
         assert_eq!(
             builder.build(),
@@ -157,6 +469,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_with_fl_for() {
+        #[derive(SolrDocument)]
+        #[allow(dead_code)]
+        struct Document {
+            id: String,
+            name: String,
+        }
+
+        let builder = CommonQueryBuilder::new().fl_for::<Document>();
+
+        assert_eq!(
+            builder.build(),
+            vec![(String::from("fl"), String::from("id,name")),],
+        );
+    }
+
+    #[test]
+    fn test_into_params() {
+        let params = CommonQueryBuilder::new()
+            .rows(10)
+            .fq(&QueryOperand::from("name:alice"))
+            .into_params();
+
+        assert_eq!(params.get("rows"), Some("10"));
+        assert_eq!(params.get_all("fq"), vec!["name:alice"]);
+    }
+
     #[test]
     fn test_q_op() {
         let builder = CommonQueryBuilder::new().op(Operator::AND);
@@ -167,6 +507,26 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_highlight() {
+        let highlight = HighlightBuilder::new(&["title", "body"])
+            .snippets(3)
+            .field(HighlightFieldBuilder::new("title").fragsize(0));
+        let builder = CommonQueryBuilder::new().highlight(&highlight);
+
+        let mut expected = vec![
+            (String::from("hl"), String::from("true")),
+            (String::from("hl.fl"), String::from("title,body")),
+            (String::from("hl.snippets"), String::from("3")),
+            (String::from("f.title.hl.fragsize"), String::from("0")),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_facet() {
         let facet = FieldFacetBuilder::new("gender").sort(FieldFacetSortOrder::Count);
@@ -246,9 +606,75 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_qt() {
+        let builder = CommonQueryBuilder::new().qt("/query");
+        assert_eq!(
+            builder.build(),
+            vec![("qt".to_string(), "/query".to_string())]
+        )
+    }
+
+    #[test]
+    fn test_echo_params() {
+        let builder = CommonQueryBuilder::new().echo_params(EchoParams::All);
+        assert_eq!(
+            builder.build(),
+            vec![("echoParams".to_string(), "all".to_string())]
+        )
+    }
+
+    #[test]
+    fn test_performance_params() {
+        let builder = CommonQueryBuilder::new()
+            .omit_header(true)
+            .time_allowed(500)
+            .min_exact_count(1000);
+
+        let mut expected = vec![
+            ("omitHeader".to_string(), "true".to_string()),
+            ("timeAllowed".to_string(), "500".to_string()),
+            ("minExactCount".to_string(), "1000".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_distributed_search_params() {
+        let builder = CommonQueryBuilder::new()
+            .shards(&["localhost:8983/solr/shard1", "localhost:7574/solr/shard2"])
+            .shards_tolerant(true)
+            .shards_preference("replica.type:PULL")
+            .distrib(true)
+            .route("tenant1!");
+
+        let mut expected = vec![
+            (
+                "shards".to_string(),
+                "localhost:8983/solr/shard1,localhost:7574/solr/shard2".to_string(),
+            ),
+            ("shards.tolerant".to_string(), "true".to_string()),
+            (
+                "shards.preference".to_string(),
+                "replica.type:PULL".to_string(),
+            ),
+            ("distrib".to_string(), "true".to_string()),
+            ("_route_".to_string(), "tenant1!".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_debug() {
-        let builder = CommonQueryBuilder::new().wt("json");
+        let builder = CommonQueryBuilder::new().wt(ResponseFormat::Json);
         assert_eq!(
             builder.build(),
             vec![("wt".to_string(), "json".to_string())]
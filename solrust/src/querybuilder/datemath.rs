@@ -0,0 +1,138 @@
+//! This module defines the structs that express Solr's date math syntax.
+//!
+//! https://solr.apache.org/guide/solr/latest/indexing-guide/date-formatting-math.html#date-math
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use std::fmt::{Display, Formatter};
+
+/// Unit of time used in date math.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateMathUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+}
+
+impl Display for DateMathUnit {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let unit = match self {
+            DateMathUnit::Year => "YEAR",
+            DateMathUnit::Month => "MONTH",
+            DateMathUnit::Day => "DAY",
+            DateMathUnit::Hour => "HOUR",
+            DateMathUnit::Minute => "MINUTE",
+            DateMathUnit::Second => "SECOND",
+            DateMathUnit::Millisecond => "MILLISECOND",
+        };
+        write!(f, "{}", unit)
+    }
+}
+
+/// Amount of time added to a date, e.g. `+1DAY`, used as the gap of date range facets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateMathGap {
+    amount: i64,
+    unit: DateMathUnit,
+}
+
+impl DateMathGap {
+    pub fn new(amount: i64, unit: DateMathUnit) -> Self {
+        Self { amount, unit }
+    }
+}
+
+impl Display for DateMathGap {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{:+}{}", self.amount, self.unit)
+    }
+}
+
+/// Date math expression, e.g. `NOW/DAY-1MONTH`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateMath {
+    anchor: String,
+    operations: Vec<String>,
+}
+
+impl DateMath {
+    /// Create the expression anchored at the current time, `NOW`.
+    pub fn now() -> Self {
+        Self {
+            anchor: String::from("NOW"),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Create the expression anchored at the given time.
+    pub fn at<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Self {
+        Self {
+            anchor: format_datetime(datetime),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Add the amount of time to the date. A negative amount subtracts it.
+    pub fn add(mut self, amount: i64, unit: DateMathUnit) -> Self {
+        self.operations
+            .push(DateMathGap::new(amount, unit).to_string());
+        self
+    }
+
+    /// Round the date down to the unit.
+    pub fn round(mut self, unit: DateMathUnit) -> Self {
+        self.operations.push(format!("/{}", unit));
+        self
+    }
+}
+
+impl Display for DateMath {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.anchor, self.operations.concat())
+    }
+}
+
+/// Format the date in the format accepted by Solr, the UTC time with a trailing `Z`.
+pub(crate) fn format_datetime<Tz: TimeZone>(datetime: &DateTime<Tz>) -> String {
+    datetime
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_gap() {
+        assert_eq!(DateMathGap::new(1, DateMathUnit::Day).to_string(), "+1DAY");
+        assert_eq!(
+            DateMathGap::new(-3, DateMathUnit::Month).to_string(),
+            "-3MONTH"
+        );
+    }
+
+    #[test]
+    fn test_now() {
+        let expression = DateMath::now()
+            .round(DateMathUnit::Day)
+            .add(-1, DateMathUnit::Month);
+
+        assert_eq!(expression.to_string(), "NOW/DAY-1MONTH");
+    }
+
+    #[test]
+    fn test_at() {
+        let datetime = FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2023, 1, 1, 9, 0, 0)
+            .unwrap();
+        let expression = DateMath::at(&datetime).add(1, DateMathUnit::Year);
+
+        assert_eq!(expression.to_string(), "2023-01-01T00:00:00Z+1YEAR");
+    }
+}
@@ -1,29 +1,37 @@
 //! This module provides definition and implementation of Solr DisMax Query Parser.
 
-use crate::querybuilder::common::SolrCommonQueryBuilder;
+use crate::querybuilder::common::{EchoParams, FqOptions, ResponseFormat, SolrCommonQueryBuilder};
 use crate::querybuilder::facet::FacetBuilder;
+use crate::querybuilder::filter::SolrFilters;
+use crate::querybuilder::fl::FlBuilder;
+use crate::querybuilder::function::FunctionQuery;
+use crate::querybuilder::highlight::HighlightBuilder;
+use crate::querybuilder::mm::Mm;
+use crate::querybuilder::params::SolrQueryParams;
 use crate::querybuilder::q::{Operator, SolrQueryExpression};
 use crate::querybuilder::sanitizer::SOLR_SPECIAL_CHARACTERS;
 use crate::querybuilder::sort::SortOrderBuilder;
+use crate::types::document::SolrDocument;
+use serde::{Deserialize, Serialize};
 use solrust_derive::{SolrCommonQueryParser, SolrDisMaxQueryParser};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 /// The trait of builder that generates parameter for [Solr Standard Query Parser](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html).
 pub trait SolrDisMaxQueryBuilder: SolrCommonQueryBuilder {
     /// Add [q parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#q-parameter).
-    fn q(self, q: String) -> Self;
+    fn q(self, q: &DisMaxQueryInput) -> Self;
     /// Add [qf parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#qf-query-fields-parameter).
-    fn qf(self, qf: &str) -> Self;
+    fn qf(self, qf: &FieldBoosts) -> Self;
     /// Add [qs parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#qs-query-phrase-slop-parameter).
     fn qs(self, qs: u32) -> Self;
     /// Add [pf parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#pf-phrase-fields-parameter).
-    fn pf(self, pf: &str) -> Self;
+    fn pf(self, pf: &FieldBoosts) -> Self;
     /// Add [ps parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#ps-phrase-slop-parameter).
     fn ps(self, ps: u32) -> Self;
     /// Add [mm parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#mm-minimum-should-match-parameter).
-    fn mm(self, mm: &str) -> Self;
+    fn mm(self, mm: &Mm) -> Self;
     /// Add [q.alt parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#q-alt-parameter).
     fn q_alt(self, q: &impl SolrQueryExpression) -> Self;
     /// Add [tie parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#the-tie-tie-breaker-parameter).
@@ -35,25 +43,198 @@ pub trait SolrDisMaxQueryBuilder: SolrCommonQueryBuilder {
     /// Add [bf parameter](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#bf-boost-functions-parameter).
     ///
     /// `bf` parameter will be added as many times as this method is called.
-    fn bf(self, bf: &str) -> Self;
+    fn bf(self, bf: impl Into<FunctionQuery>) -> Self;
+    /// Add `bq` parameter for each of the expressions.
+    fn bqs(self, bqs: &[impl SolrQueryExpression]) -> Self;
+    /// Add `bf` parameter for each of the functions.
+    fn bfs(self, bfs: &[impl Into<FunctionQuery> + Clone]) -> Self;
+    /// Add `q.alt=*:*` parameter if `q` parameter is not set or blank, to match all documents when the user query is empty.
+    ///
+    /// Call this method after setting `q` parameter.
+    fn match_all_if_empty(self) -> Self;
 }
 
-/// Implementation of DisMax Common Query Parser.
-#[derive(SolrCommonQueryParser, SolrDisMaxQueryParser)]
-pub struct DisMaxQueryBuilder {
-    params: HashMap<String, String>,
-    multi_params: HashMap<String, Vec<String>>,
+/// Whether the documents must or must not match a clause of the DisMax query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisMaxOccur {
+    Should,
+    /// Marked with `+`.
+    Required,
+    /// Marked with `-`.
+    Prohibited,
 }
 
-impl DisMaxQueryBuilder {
-    pub fn new() -> Self {
-        let mut params = HashMap::new();
-        params.insert("defType".to_string(), "dismax".to_string());
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DisMaxClauseKind {
+    Term(String),
+    Phrase(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DisMaxClause {
+    occur: DisMaxOccur,
+    kind: DisMaxClauseKind,
+}
+
+/// User input for the `q` parameter of DisMax Query Parser, which consists of terms and phrases
+/// optionally marked as required (`+`) or prohibited (`-`).
+///
+/// The special characters in the terms and phrases are escaped, while the quotes of the phrases and the markers are kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisMaxQueryInput {
+    clauses: Vec<DisMaxClause>,
+}
 
+impl DisMaxQueryInput {
+    pub fn new() -> Self {
         Self {
-            params: params,
-            multi_params: HashMap::new(),
+            clauses: Vec::new(),
+        }
+    }
+
+    /// Parse the raw user input, in which the phrases are quoted and the required or prohibited clauses are
+    /// prefixed with `+` or `-`.
+    ///
+    /// A phrase whose closing quote is missing extends to the end of the input.
+    pub fn parse(input: &str) -> Self {
+        let mut result = Self::new();
+        let mut chars = input.chars().peekable();
+
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.peek().is_none() {
+                break;
+            }
+
+            let occur = match chars.next_if(|c| *c == '+' || *c == '-') {
+                Some('+') => DisMaxOccur::Required,
+                Some(_) => DisMaxOccur::Prohibited,
+                None => DisMaxOccur::Should,
+            };
+
+            if chars.next_if_eq(&'"').is_some() {
+                let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+                result = result.clause(occur, DisMaxClauseKind::Phrase(phrase));
+            } else {
+                let mut term = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    term.push(c);
+                }
+                result = result.clause(occur, DisMaxClauseKind::Term(term));
+            }
+        }
+
+        result
+    }
+
+    /// Add the term.
+    pub fn term(self, term: &str, occur: DisMaxOccur) -> Self {
+        self.clause(occur, DisMaxClauseKind::Term(term.to_string()))
+    }
+
+    /// Add the phrase, which is quoted in the query.
+    pub fn phrase(self, phrase: &str, occur: DisMaxOccur) -> Self {
+        self.clause(occur, DisMaxClauseKind::Phrase(phrase.to_string()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    fn clause(mut self, occur: DisMaxOccur, kind: DisMaxClauseKind) -> Self {
+        let empty = match &kind {
+            DisMaxClauseKind::Term(term) => term.is_empty(),
+            DisMaxClauseKind::Phrase(phrase) => phrase.trim().is_empty(),
+        };
+        if !empty {
+            self.clauses.push(DisMaxClause { occur, kind });
+        }
+        self
+    }
+}
+
+impl Display for DisMaxQueryInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, clause) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match clause.occur {
+                DisMaxOccur::Should => {}
+                DisMaxOccur::Required => write!(f, "+")?,
+                DisMaxOccur::Prohibited => write!(f, "-")?,
+            }
+            match &clause.kind {
+                DisMaxClauseKind::Term(term) => {
+                    write!(f, "{}", SOLR_SPECIAL_CHARACTERS.replace_all(term, r"\$0"))?
+                }
+                DisMaxClauseKind::Phrase(phrase) => write!(
+                    f,
+                    "\"{}\"",
+                    SOLR_SPECIAL_CHARACTERS.replace_all(phrase, r"\$0")
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for DisMaxQueryInput {
+    fn from(input: &str) -> Self {
+        Self::parse(input)
+    }
+}
+
+/// Fields with optional boosts, used for `qf`, `pf`, `pf2` and `pf3` parameters, e.g. `title^10 body^2`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldBoosts {
+    fields: Vec<(String, Option<f64>)>,
+}
+
+impl FieldBoosts {
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    /// Add the field without boost.
+    pub fn field(mut self, field: &str) -> Self {
+        self.fields.push((field.to_string(), None));
+        self
+    }
+
+    /// Add the field with the boost.
+    pub fn boost(mut self, field: &str, boost: f64) -> Self {
+        self.fields.push((field.to_string(), Some(boost)));
+        self
+    }
+}
+
+impl Display for FieldBoosts {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, (field, boost)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", field)?;
+            if let Some(boost) = boost {
+                write!(f, "^{}", boost)?;
+            }
         }
+        Ok(())
+    }
+}
+
+/// Implementation of DisMax Common Query Parser.
+#[derive(Clone, Debug, Serialize, SolrCommonQueryParser, SolrDisMaxQueryParser)]
+#[solr_params(defType = "dismax")]
+pub struct DisMaxQueryBuilder {
+    params: BTreeMap<String, String>,
+    multi_params: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for DisMaxQueryBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -65,8 +246,8 @@ mod test {
 
     #[test]
     fn test_q() {
-        let q = QueryOperand::from("プログラミング Rust");
-        let builder = DisMaxQueryBuilder::new().q(q.to_string());
+        let q = DisMaxQueryInput::from("プログラミング Rust");
+        let builder = DisMaxQueryBuilder::new().q(&q);
 
         let mut expected = vec![
             ("defType".to_string(), "dismax".to_string()),
@@ -80,8 +261,8 @@ mod test {
 
     #[test]
     fn test_sanitized_q() {
-        let q = QueryOperand::from("Programming C++");
-        let builder = DisMaxQueryBuilder::new().q(q.to_string());
+        let q = DisMaxQueryInput::from("Programming C++");
+        let builder = DisMaxQueryBuilder::new().q(&q);
 
         let expected = sorted(
             vec![
@@ -96,10 +277,141 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_q_with_phrases_and_markers() {
+        let q = DisMaxQueryInput::parse(r#"+"Programming Rust" -C++ "unclosed: phrase"#);
+        let builder = DisMaxQueryBuilder::new().q(&q);
+
+        let mut expected = vec![
+            ("defType".to_string(), "dismax".to_string()),
+            (
+                "q".to_string(),
+                r#"+"Programming Rust" -C\+\+ "unclosed\: phrase""#.to_string(),
+            ),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_build_query_input() {
+        let q = DisMaxQueryInput::new()
+            .term("rust", DisMaxOccur::Required)
+            .phrase(r#"say "hello""#, DisMaxOccur::Should)
+            .term("java", DisMaxOccur::Prohibited)
+            .term("", DisMaxOccur::Should);
+
+        assert_eq!(q.to_string(), r#"+rust "say \"hello\"" -java"#);
+    }
+
+    #[test]
+    fn test_parse_lone_markers() {
+        let q = DisMaxQueryInput::parse(" + - \"\" ");
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn test_qf_and_pf_with_boosts() {
+        let builder = DisMaxQueryBuilder::new()
+            .qf(&FieldBoosts::new().boost("title", 10.0).boost("body", 2.5))
+            .pf(&FieldBoosts::new().boost("title", 20.0).field("body"));
+
+        let mut expected = vec![
+            ("defType".to_string(), "dismax".to_string()),
+            ("qf".to_string(), "title^10 body^2.5".to_string()),
+            ("pf".to_string(), "title^20 body".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_mm() {
+        let mm: Mm = "2<-25% 9<-3".parse().unwrap();
+        let builder = DisMaxQueryBuilder::new().mm(&mm);
+
+        let mut expected = vec![
+            ("defType".to_string(), "dismax".to_string()),
+            ("mm".to_string(), "2<-25% 9<-3".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bf() {
+        let builder = DisMaxQueryBuilder::new()
+            .bf(FunctionQuery::call("log", &[FunctionQuery::field("popularity").unwrap()]).unwrap())
+            .bf("recip(rord(start_at),1,1000,1000)");
+
+        let mut expected = vec![
+            ("defType".to_string(), "dismax".to_string()),
+            ("bf".to_string(), "log(popularity)".to_string()),
+            (
+                "bf".to_string(),
+                "recip(rord(start_at),1,1000,1000)".to_string(),
+            ),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_bqs_and_bfs() {
+        let bqs = [
+            QueryOperand::from("category:ABC"),
+            QueryOperand::from("category:ARC"),
+        ];
+        let builder = DisMaxQueryBuilder::new().bqs(&bqs).bfs(&[
+            FunctionQuery::call("log", &[FunctionQuery::field("popularity").unwrap()]).unwrap(),
+            FunctionQuery::from("recip(rord(start_at),1,1000,1000)"),
+        ]);
+
+        let mut expected = vec![
+            ("defType".to_string(), "dismax".to_string()),
+            ("bq".to_string(), "category:ABC".to_string()),
+            ("bq".to_string(), "category:ARC".to_string()),
+            ("bf".to_string(), "log(popularity)".to_string()),
+            (
+                "bf".to_string(),
+                "recip(rord(start_at),1,1000,1000)".to_string(),
+            ),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_match_all_if_empty() {
+        let builder = DisMaxQueryBuilder::new()
+            .q(&DisMaxQueryInput::from("  "))
+            .match_all_if_empty();
+        let actual = builder.build();
+        assert!(actual.contains(&("q.alt".to_string(), "*:*".to_string())));
+
+        let builder = DisMaxQueryBuilder::new()
+            .q(&DisMaxQueryInput::from("Rust"))
+            .match_all_if_empty();
+        let actual = builder.build();
+        assert!(actual.iter().all(|(key, _)| key != "q.alt"));
+    }
+
     #[test]
     fn test_qf() {
-        let q = QueryOperand::from("プログラミング Rust");
-        let builder = DisMaxQueryBuilder::new().q(q.to_string()).qf("title text");
+        let q = DisMaxQueryInput::from("プログラミング Rust");
+        let builder = DisMaxQueryBuilder::new()
+            .q(&q)
+            .qf(&FieldBoosts::new().field("title").field("text"));
 
         let mut expected = vec![
             ("defType".to_string(), "dismax".to_string()),
@@ -115,16 +427,20 @@ mod test {
     #[test]
     fn test_sample_query() {
         let q = QueryOperand::from("*:*");
-        let sort = SortOrderBuilder::new().desc("score").asc("start_at");
+        let sort = SortOrderBuilder::new()
+            .desc("score")
+            .unwrap()
+            .asc("start_at")
+            .unwrap();
         let builder = DisMaxQueryBuilder::new()
-            .q("すぬけ 耳".to_string())
-            .qf("text_ja")
+            .q(&DisMaxQueryInput::from("すぬけ 耳"))
+            .qf(&FieldBoosts::new().field("text_ja"))
             .op(Operator::AND)
-            .wt("json")
+            .wt(ResponseFormat::Json)
             .debug()
             .q_alt(&q)
             .sort(&sort)
-            .fl("problem_title".to_string());
+            .fl(&FlBuilder::new().field("problem_title"));
 
         let mut expected = vec![
             ("defType".to_string(), "dismax".to_string()),
@@ -1,54 +1,108 @@
 //! This module provides definition and implementation of Solr eDisMax Query Parser.
 
-use crate::querybuilder::common::SolrCommonQueryBuilder;
-use crate::querybuilder::dismax::SolrDisMaxQueryBuilder;
+use crate::querybuilder::common::{EchoParams, FqOptions, ResponseFormat, SolrCommonQueryBuilder};
+use crate::querybuilder::dismax::{DisMaxQueryInput, FieldBoosts, SolrDisMaxQueryBuilder};
 use crate::querybuilder::facet::FacetBuilder;
+use crate::querybuilder::filter::SolrFilters;
+use crate::querybuilder::fl::FlBuilder;
+use crate::querybuilder::function::FunctionQuery;
+use crate::querybuilder::highlight::HighlightBuilder;
+use crate::querybuilder::mm::Mm;
+use crate::querybuilder::params::SolrQueryParams;
 use crate::querybuilder::q::{Operator, SolrQueryExpression};
 use crate::querybuilder::sanitizer::SOLR_SPECIAL_CHARACTERS;
 use crate::querybuilder::sort::SortOrderBuilder;
+use crate::types::document::SolrDocument;
+use serde::{Deserialize, Serialize};
 use solrust_derive::{SolrCommonQueryParser, SolrDisMaxQueryParser, SolrEDisMaxQueryParser};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 /// The trait of builder that generates parameter for [Solr eDisMax Query Parser](https://solr.apache.org/guide/solr/latest/query-guide/edismax-query-parser.html).
 pub trait SolrEDisMaxQueryBuilder: SolrDisMaxQueryBuilder {
     /// Add `sow` parameter.
     fn sow(self, sow: bool) -> Self;
-    /// Add `boost` parameter.
-    fn boost(self, boost: &str) -> Self;
+    /// Add `boost` parameter, the function multiplied with the score.
+    ///
+    /// `boost` parameter will be added as many times as this method is called, and the functions are multiplied together.
+    fn boost(self, boost: impl Into<FunctionQuery>) -> Self;
+    /// Add `boost` parameter for each of the functions.
+    fn boosts(self, boosts: &[impl Into<FunctionQuery> + Clone]) -> Self;
+    /// Add `mm.autoRelax` parameter.
+    ///
+    /// If true, the number of the clauses required by `mm` is reduced when the stop words of some fields in `qf` are removed.
+    fn mm_auto_relax(self, flag: bool) -> Self;
     /// Add `lowercaseOperators` parameter.
     fn lowercase_operators(self, flag: bool) -> Self;
     /// Add `pf2` parameter.
-    fn pf2(self, pf: &str) -> Self;
+    fn pf2(self, pf: &FieldBoosts) -> Self;
     /// Add `ps2` parameter.
     fn ps2(self, ps: u32) -> Self;
     /// Add `pf3` parameter.
-    fn pf3(self, pf: &str) -> Self;
+    fn pf3(self, pf: &FieldBoosts) -> Self;
     /// Add `ps3` parameter.
     fn ps3(self, ps: u32) -> Self;
     /// Add `stopwords` parameter.
     fn stopwords(self, flag: bool) -> Self;
     /// Add `uf` parameter.
-    fn uf(self, uf: &str) -> Self;
+    fn uf(self, uf: &UserFields) -> Self;
 }
 
-/// Implementation of Solr eDisMax Query Parser.
-#[derive(SolrCommonQueryParser, SolrDisMaxQueryParser, SolrEDisMaxQueryParser)]
-pub struct EDisMaxQueryBuilder {
-    params: HashMap<String, String>,
-    multi_params: HashMap<String, Vec<String>>,
+/// Fields that the users are allowed to query explicitly, used for `uf` parameter, e.g. `title^2 *_s -price`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UserFields {
+    fields: Vec<String>,
 }
 
-impl EDisMaxQueryBuilder {
+impl UserFields {
     pub fn new() -> Self {
-        let mut params = HashMap::new();
-        params.insert("defType".to_string(), "edismax".to_string());
+        Self { fields: Vec::new() }
+    }
+
+    /// Allow the field. The field name may contain the wildcard `*`.
+    pub fn allow(mut self, field: &str) -> Self {
+        self.fields.push(field.to_string());
+        self
+    }
+
+    /// Allow all fields, `*`.
+    pub fn allow_all(self) -> Self {
+        self.allow("*")
+    }
+
+    /// Allow the field with the boost applied to the clauses on it.
+    pub fn boost(mut self, field: &str, boost: f64) -> Self {
+        self.fields.push(format!("{}^{}", field, boost));
+        self
+    }
+
+    /// Disallow the field, `-<FIELD_NAME>`.
+    pub fn deny(mut self, field: &str) -> Self {
+        self.fields.push(format!("-{}", field));
+        self
+    }
+}
+
+impl Display for UserFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.fields.join(" "))
+    }
+}
 
-        Self {
-            params: params,
-            multi_params: HashMap::new(),
-        }
+/// Implementation of Solr eDisMax Query Parser.
+#[derive(
+    Clone, Debug, Serialize, SolrCommonQueryParser, SolrDisMaxQueryParser, SolrEDisMaxQueryParser,
+)]
+#[solr_params(defType = "edismax")]
+pub struct EDisMaxQueryBuilder {
+    params: BTreeMap<String, String>,
+    multi_params: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for EDisMaxQueryBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -59,8 +113,8 @@ mod test {
 
     #[test]
     fn test_q() {
-        let q = QueryOperand::from("プログラミング Rust");
-        let builder = EDisMaxQueryBuilder::new().q(q.to_string());
+        let q = DisMaxQueryInput::from("プログラミング Rust");
+        let builder = EDisMaxQueryBuilder::new().q(&q);
 
         let mut expected = vec![
             ("defType".to_string(), "edismax".to_string()),
@@ -72,21 +126,119 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_pf2_and_pf3() {
+        let builder = EDisMaxQueryBuilder::new()
+            .pf2(&FieldBoosts::new().boost("title", 5.0))
+            .pf3(&FieldBoosts::new().boost("title", 3.0).boost("body", 1.5));
+
+        let mut expected = vec![
+            ("defType".to_string(), "edismax".to_string()),
+            ("pf2".to_string(), "title^5".to_string()),
+            ("pf3".to_string(), "title^3 body^1.5".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_boosts_and_phrase_slops() {
+        let builder = EDisMaxQueryBuilder::new()
+            .boost("recip(ms(NOW,start_at),3.16e-11,1,1)")
+            .boosts(&[
+                FunctionQuery::field("popularity").unwrap(),
+                FunctionQuery::from("log(votes)"),
+            ])
+            .mm_auto_relax(true)
+            .ps(1)
+            .ps2(2)
+            .ps3(3);
+
+        let mut expected = vec![
+            ("defType".to_string(), "edismax".to_string()),
+            (
+                "boost".to_string(),
+                "recip(ms(NOW,start_at),3.16e-11,1,1)".to_string(),
+            ),
+            ("boost".to_string(), "popularity".to_string()),
+            ("boost".to_string(), "log(votes)".to_string()),
+            ("mm.autoRelax".to_string(), "true".to_string()),
+            ("ps".to_string(), "1".to_string()),
+            ("ps2".to_string(), "2".to_string()),
+            ("ps3".to_string(), "3".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_uf() {
+        let uf = UserFields::new()
+            .allow_all()
+            .boost("title", 2.0)
+            .allow("*_s")
+            .deny("price");
+        let builder = EDisMaxQueryBuilder::new().uf(&uf);
+
+        let mut expected = vec![
+            ("defType".to_string(), "edismax".to_string()),
+            ("uf".to_string(), "* title^2 *_s -price".to_string()),
+        ];
+        let mut actual = builder.build();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_serialize_and_deserialize_preset() {
+        let preset = EDisMaxQueryBuilder::new()
+            .qf(&FieldBoosts::new().boost("title", 10.0).field("body"))
+            .bfs(&["log(popularity)"]);
+        let json = serde_json::to_string(&preset).unwrap();
+        assert_eq!(
+            json,
+            r#"{"params":{"defType":"edismax","qf":"title^10 body"},"multi_params":{"bf":["log(popularity)"]}}"#
+        );
+
+        let loaded: EDisMaxQueryBuilder =
+            serde_json::from_str(r#"{"params": {"qf": "title^10 body"}}"#).unwrap();
+        let builder = loaded.clone().q(&DisMaxQueryInput::from("rust"));
+
+        assert_eq!(
+            builder.build(),
+            vec![
+                ("defType".to_string(), "edismax".to_string()),
+                ("q".to_string(), "rust".to_string()),
+                ("qf".to_string(), "title^10 body".to_string()),
+            ]
+        );
+        assert_eq!(loaded.build().len(), 2);
+    }
+
     #[test]
     fn test_sample_query() {
         let q = QueryOperand::from("*:*");
-        let sort = SortOrderBuilder::new().desc("score").asc("start_at");
+        let sort = SortOrderBuilder::new()
+            .desc("score")
+            .unwrap()
+            .asc("start_at")
+            .unwrap();
         let builder = EDisMaxQueryBuilder::new()
-            .q("すぬけ 耳".to_string())
-            .qf("text_ja text_en")
+            .q(&DisMaxQueryInput::from("すぬけ 耳"))
+            .qf(&FieldBoosts::new().field("text_ja").field("text_en"))
             .op(Operator::AND)
-            .wt("json")
+            .wt(ResponseFormat::Json)
             .sow(true)
             .boost("boost")
             .debug()
             .q_alt(&q)
             .sort(&sort)
-            .fl("problem_title".to_string());
+            .fl(&FlBuilder::new().field("problem_title"));
 
         let mut expected = vec![
             ("defType".to_string(), "edismax".to_string()),
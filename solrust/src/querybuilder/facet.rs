@@ -1,5 +1,7 @@
 //! This module defines the traits and structs that generates query parameters for facet search.
 
+use crate::querybuilder::datemath::{format_datetime, DateMath, DateMathGap};
+use chrono::{DateTime, TimeZone};
 use std::string::ToString;
 
 /// Build parameters for facet search.
@@ -30,6 +32,8 @@ pub struct FieldFacetBuilder {
     prefix: Option<String>,
     contains: Option<String>,
     ignore_case: Option<bool>,
+    exclude_terms: Vec<String>,
+    matches: Option<String>,
     sort: Option<String>,
     limit: Option<u32>,
     offset: Option<u32>,
@@ -46,6 +50,8 @@ impl FieldFacetBuilder {
             prefix: None,
             contains: None,
             ignore_case: None,
+            exclude_terms: Vec::new(),
+            matches: None,
             sort: None,
             limit: None,
             offset: None,
@@ -74,6 +80,21 @@ impl FieldFacetBuilder {
         self
     }
 
+    /// Add `f.<FIELD_NAME>.facet.excludeTerms` parameter.
+    ///
+    /// The terms are added as many as this method is called, and joined with commas.
+    pub fn exclude_terms(mut self, terms: &[&str]) -> Self {
+        self.exclude_terms
+            .extend(terms.iter().map(|term| term.to_string()));
+        self
+    }
+
+    /// Add `f.<FIELD_NAME>.facet.matches` parameter, the regular expression that the facet values must match.
+    pub fn matches(mut self, pattern: &str) -> Self {
+        self.matches = Some(pattern.to_string());
+        self
+    }
+
     /// Add `f.<FIELD_NAME>.facet.sort` parameter.
     pub fn sort(mut self, sort: FieldFacetSortOrder) -> Self {
         self.sort = Some(match sort {
@@ -148,6 +169,20 @@ impl FacetBuilder for FieldFacetBuilder {
             ));
         }
 
+        if !self.exclude_terms.is_empty() {
+            result.push((
+                format!("f.{}.facet.excludeTerms", self.field),
+                self.exclude_terms.join(","),
+            ));
+        }
+
+        if let Some(matches) = &self.matches {
+            result.push((
+                format!("f.{}.facet.matches", self.field),
+                matches.to_string(),
+            ));
+        }
+
         if let Some(sort) = &self.sort {
             result.push((format!("f.{}.facet.sort", self.field), sort.to_string()));
         }
@@ -186,6 +221,107 @@ impl FacetBuilder for FieldFacetBuilder {
     }
 }
 
+/// Implementation of the builder generates the facet parameters that are not scoped to a field,
+/// which are the defaults of all field facets.
+pub struct GlobalFacetParams {
+    limit: Option<i32>,
+    min_count: Option<u32>,
+    sort: Option<String>,
+    threads: Option<i32>,
+    overrequest_count: Option<u32>,
+    overrequest_ratio: Option<f64>,
+}
+
+impl Default for GlobalFacetParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlobalFacetParams {
+    pub fn new() -> Self {
+        Self {
+            limit: None,
+            min_count: None,
+            sort: None,
+            threads: None,
+            overrequest_count: None,
+            overrequest_ratio: None,
+        }
+    }
+
+    /// Add `facet.limit` parameter. A negative value means unlimited.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Add `facet.mincount` parameter.
+    pub fn min_count(mut self, min_count: u32) -> Self {
+        self.min_count = Some(min_count);
+        self
+    }
+
+    /// Add `facet.sort` parameter.
+    pub fn sort(mut self, sort: FieldFacetSortOrder) -> Self {
+        self.sort = Some(match sort {
+            FieldFacetSortOrder::Count => "count".to_string(),
+            FieldFacetSortOrder::Index => "index".to_string(),
+        });
+        self
+    }
+
+    /// Add `facet.threads` parameter. A negative value means as many threads as the fields.
+    pub fn threads(mut self, threads: i32) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Add `facet.overrequest.count` parameter.
+    pub fn overrequest_count(mut self, count: u32) -> Self {
+        self.overrequest_count = Some(count);
+        self
+    }
+
+    /// Add `facet.overrequest.ratio` parameter.
+    pub fn overrequest_ratio(mut self, ratio: f64) -> Self {
+        self.overrequest_ratio = Some(ratio);
+        self
+    }
+}
+
+impl FacetBuilder for GlobalFacetParams {
+    fn build(&self) -> Vec<(String, String)> {
+        let mut result: Vec<(String, String)> = Vec::new();
+
+        if let Some(limit) = &self.limit {
+            result.push((String::from("facet.limit"), limit.to_string()));
+        }
+
+        if let Some(min_count) = &self.min_count {
+            result.push((String::from("facet.mincount"), min_count.to_string()));
+        }
+
+        if let Some(sort) = &self.sort {
+            result.push((String::from("facet.sort"), sort.to_string()));
+        }
+
+        if let Some(threads) = &self.threads {
+            result.push((String::from("facet.threads"), threads.to_string()));
+        }
+
+        if let Some(count) = &self.overrequest_count {
+            result.push((String::from("facet.overrequest.count"), count.to_string()));
+        }
+
+        if let Some(ratio) = &self.overrequest_ratio {
+            result.push((String::from("facet.overrequest.ratio"), ratio.to_string()));
+        }
+
+        result
+    }
+}
+
 pub enum RangeFacetOtherOptions {
     Before,
     After,
@@ -202,27 +338,80 @@ pub enum RangeFacetIncludeOptions {
     All,
 }
 
+/// Algorithm that Solr should use when faceting a range.
+///
+/// https://solr.apache.org/guide/solr/latest/query-guide/faceting.html#range-faceting:~:text=facet.range.method
+pub enum RangeFacetMethod {
+    Filter,
+    Dv,
+}
+
+/// Type of the values that bound the ranges of range facetting.
+///
+/// The gap is the same type as the values for numeric values, and the date math gap for dates.
+pub trait RangeFacetValue {
+    type Gap: ToString;
+
+    fn to_range_value(&self) -> String;
+}
+
+macro_rules! impl_numeric_range_facet_value {
+    ($($t:ty),*) => {
+        $(
+            impl RangeFacetValue for $t {
+                type Gap = $t;
+
+                fn to_range_value(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_range_facet_value!(i32, i64, u32, u64, f32, f64);
+
+impl<Tz: TimeZone> RangeFacetValue for DateTime<Tz> {
+    type Gap = DateMathGap;
+
+    fn to_range_value(&self) -> String {
+        format_datetime(self)
+    }
+}
+
+impl RangeFacetValue for DateMath {
+    type Gap = DateMathGap;
+
+    fn to_range_value(&self) -> String {
+        self.to_string()
+    }
+}
+
 /// Implementation of the builder generates parameters for range facetting.
-pub struct RangeFacetBuilder {
+pub struct RangeFacetBuilder<T: RangeFacetValue> {
     field: String,
-    start: String,
-    end: String,
-    gap: String,
+    start: T,
+    end: T,
+    gap: T::Gap,
     hardend: Option<bool>,
     other: Option<RangeFacetOtherOptions>,
     include: Option<RangeFacetIncludeOptions>,
+    method: Option<RangeFacetMethod>,
+    min_count: Option<u32>,
 }
 
-impl RangeFacetBuilder {
-    pub fn new(field: &str, start: impl ToString, end: impl ToString, gap: impl ToString) -> Self {
+impl<T: RangeFacetValue> RangeFacetBuilder<T> {
+    pub fn new(field: &str, start: T, end: T, gap: T::Gap) -> Self {
         Self {
             field: field.to_string(),
-            start: start.to_string(),
-            end: end.to_string(),
-            gap: gap.to_string(),
+            start,
+            end,
+            gap,
             hardend: None,
             other: None,
             include: None,
+            method: None,
+            min_count: None,
         }
     }
 
@@ -243,24 +432,36 @@ impl RangeFacetBuilder {
         self.include = Some(include);
         self
     }
+
+    /// Add `f.<FIELD_NAME>.facet.range.method` parameter.
+    pub fn method(mut self, method: RangeFacetMethod) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Add `f.<FIELD_NAME>.facet.mincount` parameter, the minimum count of the ranges to be returned.
+    pub fn min_count(mut self, min_count: u32) -> Self {
+        self.min_count = Some(min_count);
+        self
+    }
 }
 
-impl FacetBuilder for RangeFacetBuilder {
+impl<T: RangeFacetValue> FacetBuilder for RangeFacetBuilder<T> {
     fn build(&self) -> Vec<(String, String)> {
         let mut result = Vec::new();
 
         result.push((String::from("facet.range"), self.field.clone()));
         result.push((
             format!("f.{}.facet.range.start", self.field),
-            self.start.clone(),
+            self.start.to_range_value(),
         ));
         result.push((
             format!("f.{}.facet.range.end", self.field),
-            self.end.clone(),
+            self.end.to_range_value(),
         ));
         result.push((
             format!("f.{}.facet.range.gap", self.field),
-            self.gap.clone(),
+            self.gap.to_string(),
         ));
 
         if let Some(hardend) = self.hardend {
@@ -296,13 +497,212 @@ impl FacetBuilder for RangeFacetBuilder {
             ));
         }
 
+        if let Some(method) = &self.method {
+            result.push((
+                format!("f.{}.facet.range.method", self.field),
+                match method {
+                    RangeFacetMethod::Filter => String::from("filter"),
+                    RangeFacetMethod::Dv => String::from("dv"),
+                },
+            ));
+        }
+
+        if let Some(min_count) = &self.min_count {
+            result.push((
+                format!("f.{}.facet.mincount", self.field),
+                min_count.to_string(),
+            ));
+        }
+
         result
     }
 }
 
+/// Implementation of the builder generates parameters for [pivot facetting](https://solr.apache.org/guide/solr/latest/query-guide/faceting.html#pivot-decision-tree-faceting).
+///
+/// The stats fields, the range facets and the facet queries tagged with the given names are computed for each node of the pivot, e.g.
+///
+/// ```ignore
+/// let builder = StandardQueryBuilder::new()
+///     .facet(&StatsFieldBuilder::new("price").tag("s1"))
+///     .facet_with_local_params(&RangeFacetBuilder::new("price", 0, 1000, 100), &[("tag", "r1")])
+///     .facet(&PivotFacetBuilder::new(&["category", "in_stock"]).stats("s1").range("r1"));
+/// ```
+pub struct PivotFacetBuilder {
+    fields: Vec<String>,
+    stats: Option<String>,
+    range: Option<String>,
+    query: Option<String>,
+    min_count: Option<u32>,
+}
+
+impl PivotFacetBuilder {
+    pub fn new(fields: &[&str]) -> Self {
+        Self {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            stats: None,
+            range: None,
+            query: None,
+            min_count: None,
+        }
+    }
+
+    /// Add `stats` local parameter, the tag of the stats fields computed in each node.
+    pub fn stats(mut self, tag: &str) -> Self {
+        self.stats = Some(tag.to_string());
+        self
+    }
+
+    /// Add `range` local parameter, the tag of the range facets computed in each node.
+    pub fn range(mut self, tag: &str) -> Self {
+        self.range = Some(tag.to_string());
+        self
+    }
+
+    /// Add `query` local parameter, the tag of the facet queries computed in each node.
+    pub fn query(mut self, tag: &str) -> Self {
+        self.query = Some(tag.to_string());
+        self
+    }
+
+    /// Add `facet.pivot.mincount` parameter.
+    pub fn min_count(mut self, min_count: u32) -> Self {
+        self.min_count = Some(min_count);
+        self
+    }
+}
+
+impl FacetBuilder for PivotFacetBuilder {
+    fn build(&self) -> Vec<(String, String)> {
+        let mut result = Vec::new();
+
+        let local_params: Vec<String> = [
+            ("stats", &self.stats),
+            ("range", &self.range),
+            ("query", &self.query),
+        ]
+        .iter()
+        .filter_map(|(key, tag)| tag.as_ref().map(|tag| format!("{}={}", key, tag)))
+        .collect();
+        let fields = self.fields.join(",");
+        if local_params.is_empty() {
+            result.push((String::from("facet.pivot"), fields));
+        } else {
+            result.push((
+                String::from("facet.pivot"),
+                format!("{{!{}}}{}", local_params.join(" "), fields),
+            ));
+        }
+
+        if let Some(min_count) = &self.min_count {
+            result.push((String::from("facet.pivot.mincount"), min_count.to_string()));
+        }
+
+        result
+    }
+}
+
+/// Implementation of the builder generates parameters for the [Stats Component](https://solr.apache.org/guide/solr/latest/query-guide/stats-component.html).
+///
+/// It is added with the facets since the tagged stats fields are referred from [`PivotFacetBuilder`].
+pub struct StatsFieldBuilder {
+    field: String,
+    tag: Option<String>,
+}
+
+impl StatsFieldBuilder {
+    pub fn new(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            tag: None,
+        }
+    }
+
+    /// Add `tag` local parameter, referred by `stats` local parameter of the pivot facet.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+}
+
+impl FacetBuilder for StatsFieldBuilder {
+    fn build(&self) -> Vec<(String, String)> {
+        let field = match &self.tag {
+            Some(tag) => format!("{{!tag={}}}{}", tag, self.field),
+            None => self.field.clone(),
+        };
+
+        vec![
+            (String::from("stats"), String::from("true")),
+            (String::from("stats.field"), field),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::querybuilder::datemath::DateMathUnit;
+    use chrono::Utc;
+
+    #[test]
+    fn test_global_facet_params() {
+        let builder = GlobalFacetParams::new()
+            .limit(-1)
+            .min_count(1)
+            .sort(FieldFacetSortOrder::Index)
+            .threads(4)
+            .overrequest_count(20)
+            .overrequest_ratio(1.5);
+
+        assert_eq!(
+            vec![
+                (String::from("facet.limit"), String::from("-1")),
+                (String::from("facet.mincount"), String::from("1")),
+                (String::from("facet.sort"), String::from("index")),
+                (String::from("facet.threads"), String::from("4")),
+                (String::from("facet.overrequest.count"), String::from("20")),
+                (String::from("facet.overrequest.ratio"), String::from("1.5")),
+            ],
+            builder.build()
+        );
+    }
+
+    #[test]
+    fn test_pivot_facet() {
+        let builder = PivotFacetBuilder::new(&["category", "in_stock"])
+            .stats("s1")
+            .range("r1")
+            .min_count(1);
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("facet.pivot"),
+                    String::from("{!stats=s1 range=r1}category,in_stock")
+                ),
+                (String::from("facet.pivot.mincount"), String::from("1")),
+            ],
+            builder.build()
+        );
+        assert_eq!(
+            vec![(String::from("facet.pivot"), String::from("category"))],
+            PivotFacetBuilder::new(&["category"]).build()
+        );
+    }
+
+    #[test]
+    fn test_stats_field() {
+        let builder = StatsFieldBuilder::new("price").tag("s1");
+
+        assert_eq!(
+            vec![
+                (String::from("stats"), String::from("true")),
+                (String::from("stats.field"), String::from("{!tag=s1}price")),
+            ],
+            builder.build()
+        );
+    }
 
     #[test]
     fn test_simple_field_facet() {
@@ -314,6 +714,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_field_facet_with_exclude_terms_and_matches() {
+        let builder = FieldFacetBuilder::new("category")
+            .exclude_terms(&["Other", "Unknown"])
+            .exclude_terms(&["None"])
+            .matches("^A.*");
+
+        assert_eq!(
+            vec![
+                (String::from("facet.field"), String::from("category")),
+                (
+                    String::from("f.category.facet.excludeTerms"),
+                    String::from("Other,Unknown,None")
+                ),
+                (
+                    String::from("f.category.facet.matches"),
+                    String::from("^A.*")
+                ),
+            ],
+            builder.build()
+        );
+    }
+
     #[test]
     fn test_field_facet_with_all_params() {
         let builder = FieldFacetBuilder::new("category")
@@ -391,4 +814,94 @@ mod test {
             builder.build()
         )
     }
+
+    #[test]
+    fn test_float_range_facet_with_method_and_min_count() {
+        let builder = RangeFacetBuilder::new("rating", 0.0, 5.0, 0.5)
+            .method(RangeFacetMethod::Dv)
+            .min_count(1);
+
+        assert_eq!(
+            vec![
+                (String::from("facet.range"), String::from("rating")),
+                (
+                    String::from("f.rating.facet.range.start"),
+                    String::from("0")
+                ),
+                (String::from("f.rating.facet.range.end"), String::from("5")),
+                (
+                    String::from("f.rating.facet.range.gap"),
+                    String::from("0.5")
+                ),
+                (
+                    String::from("f.rating.facet.range.method"),
+                    String::from("dv")
+                ),
+                (String::from("f.rating.facet.mincount"), String::from("1")),
+            ],
+            builder.build()
+        )
+    }
+
+    #[test]
+    fn test_date_range_facet() {
+        let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let builder = RangeFacetBuilder::new(
+            "start_at",
+            start,
+            end,
+            DateMathGap::new(1, DateMathUnit::Month),
+        );
+
+        assert_eq!(
+            vec![
+                (String::from("facet.range"), String::from("start_at")),
+                (
+                    String::from("f.start_at.facet.range.start"),
+                    String::from("2023-01-01T00:00:00Z")
+                ),
+                (
+                    String::from("f.start_at.facet.range.end"),
+                    String::from("2024-01-01T00:00:00Z")
+                ),
+                (
+                    String::from("f.start_at.facet.range.gap"),
+                    String::from("+1MONTH")
+                ),
+            ],
+            builder.build()
+        )
+    }
+
+    #[test]
+    fn test_date_math_range_facet() {
+        let builder = RangeFacetBuilder::new(
+            "start_at",
+            DateMath::now()
+                .round(DateMathUnit::Day)
+                .add(-7, DateMathUnit::Day),
+            DateMath::now().round(DateMathUnit::Day),
+            DateMathGap::new(1, DateMathUnit::Day),
+        );
+
+        assert_eq!(
+            vec![
+                (String::from("facet.range"), String::from("start_at")),
+                (
+                    String::from("f.start_at.facet.range.start"),
+                    String::from("NOW/DAY-7DAY")
+                ),
+                (
+                    String::from("f.start_at.facet.range.end"),
+                    String::from("NOW/DAY")
+                ),
+                (
+                    String::from("f.start_at.facet.range.gap"),
+                    String::from("+1DAY")
+                ),
+            ],
+            builder.build()
+        )
+    }
 }
@@ -0,0 +1,228 @@
+//! This module provides the declarative filter queries built from the filter structs.
+
+use crate::querybuilder::sanitizer::SOLR_SPECIAL_CHARACTERS;
+pub use solrust_derive::SolrFilters;
+
+/// Operator of the filter given by `#[filter(op = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterOp {
+    /// `field:value`
+    Eq,
+    /// `-field:value`
+    Ne,
+    /// `field:{value TO *]`
+    Gt,
+    /// `field:[value TO *]`
+    Ge,
+    /// `field:[* TO value}`
+    Lt,
+    /// `field:[* TO value]`
+    Le,
+    /// `field:(value1 OR value2 ...)`
+    In,
+    /// `field:"value"`
+    Phrase,
+}
+
+impl FilterOp {
+    /// Build the filter expression of the field with the values, or `None` if no value is given.
+    ///
+    /// The operators other than [`FilterOp::In`] use only the first value.
+    /// Every value is escaped, so that it is always a single term or phrase.
+    pub fn expression(&self, field: &str, values: &[String]) -> Option<String> {
+        let value = values.first()?;
+
+        let expression = match self {
+            FilterOp::Eq => format!("{}:{}", field, escape_term(value)),
+            FilterOp::Ne => format!("-{}:{}", field, escape_term(value)),
+            FilterOp::Gt => format!("{}:{{{} TO *}}", field, escape_term(value)),
+            FilterOp::Ge => format!("{}:[{} TO *}}", field, escape_term(value)),
+            FilterOp::Lt => format!("{}:[* TO {}}}", field, escape_term(value)),
+            FilterOp::Le => format!("{}:[* TO {}]", field, escape_term(value)),
+            FilterOp::In => format!(
+                "{}:({})",
+                field,
+                values
+                    .iter()
+                    .map(|value| escape_term(value))
+                    .collect::<Vec<_>>()
+                    .join(" OR ")
+            ),
+            FilterOp::Phrase => format!(r#"{}:"{}""#, field, escape_phrase(value)),
+        };
+
+        Some(expression)
+    }
+}
+
+/// Escape the value as a single term, including the whitespaces and the backslashes,
+/// so that the untrusted value never splits into the multiple terms or escapes the closing parenthesis.
+fn escape_term(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c.is_whitespace() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    SOLR_SPECIAL_CHARACTERS
+        .replace_all(&escaped, r"\$0")
+        .into_owned()
+}
+
+/// Escape the value in the double quotes, so that the untrusted value never closes the phrase.
+fn escape_phrase(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c == '"' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Trait of the filter struct, which is converted to the `fq` expressions.
+///
+/// Derive this trait with `#[derive(SolrFilters)]` to translate the search filters of a web API to Solr declaratively.
+/// The derived code refers to [`FilterOp`], so import it together with the trait:
+///
+/// ```ignore
+/// use solrust::querybuilder::filter::{FilterOp, SolrFilters};
+///
+/// #[derive(Deserialize, SolrFilters)]
+/// struct SearchFilters {
+///     #[filter(field = "category", op = "eq")]
+///     category: Option<String>,
+///     #[filter(field = "price", op = "ge")]
+///     min_price: Option<u32>,
+///     #[filter(field = "tags", op = "in")]
+///     tags: Option<Vec<String>>,
+/// }
+///
+/// let builder = StandardQueryBuilder::new().filters(&filters);
+/// ```
+pub trait SolrFilters {
+    /// Return the `fq` expressions of the fields whose values are set.
+    fn filters(&self) -> Vec<String>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(SolrFilters)]
+    struct SearchFilters {
+        #[filter(field = "category")]
+        category: Option<String>,
+        #[filter(field = "price", op = "ge")]
+        min_price: Option<u32>,
+        #[filter(field = "price", op = "lt")]
+        max_price: Option<u32>,
+        #[filter(op = "in")]
+        tags: Vec<String>,
+        #[filter(field = "status", op = "ne")]
+        excluded_status: Option<String>,
+        #[filter(field = "title", op = "phrase")]
+        title: Option<String>,
+        #[allow(dead_code)]
+        page: u32,
+    }
+
+    #[test]
+    fn test_filters() {
+        let filters = SearchFilters {
+            category: Some(String::from("book")),
+            min_price: Some(100),
+            max_price: None,
+            tags: vec![String::from("rust"), String::from("c++")],
+            excluded_status: Some(String::from("deleted")),
+            title: Some(String::from("hello world")),
+            page: 1,
+        };
+
+        assert_eq!(
+            filters.filters(),
+            vec![
+                String::from("category:book"),
+                String::from("price:[100 TO *}"),
+                String::from(r"tags:(rust OR c\+\+)"),
+                String::from("-status:deleted"),
+                String::from(r#"title:"hello world""#),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_in_with_whitespace_and_backslash() {
+        let values = vec![String::from("foo bar"), String::from(r"C:\")];
+
+        assert_eq!(
+            FilterOp::In.expression("tags", &values),
+            Some(String::from(r"tags:(foo\ bar OR C\:\\)"))
+        );
+    }
+
+    #[test]
+    fn test_eq_and_ne_with_whitespace_and_backslash() {
+        let values = vec![String::from(r#"science "fiction\"#)];
+
+        assert_eq!(
+            FilterOp::Eq.expression("category", &values),
+            Some(String::from(r#"category:science\ \"fiction\\"#))
+        );
+        assert_eq!(
+            FilterOp::Ne.expression("category", &values),
+            Some(String::from(r#"-category:science\ \"fiction\\"#))
+        );
+    }
+
+    #[test]
+    fn test_range_with_whitespace_and_backslash() {
+        let values = vec![String::from(r#"a b"\"#)];
+
+        assert_eq!(
+            FilterOp::Gt.expression("title", &values),
+            Some(String::from(r#"title:{a\ b\"\\ TO *}"#))
+        );
+        assert_eq!(
+            FilterOp::Ge.expression("title", &values),
+            Some(String::from(r#"title:[a\ b\"\\ TO *}"#))
+        );
+        assert_eq!(
+            FilterOp::Lt.expression("title", &values),
+            Some(String::from(r#"title:[* TO a\ b\"\\}"#))
+        );
+        assert_eq!(
+            FilterOp::Le.expression("title", &values),
+            Some(String::from(r#"title:[* TO a\ b\"\\]"#))
+        );
+    }
+
+    #[test]
+    fn test_phrase_with_quote_and_backslash() {
+        let values = vec![String::from(r#"say "hello" C:\"#)];
+
+        assert_eq!(
+            FilterOp::Phrase.expression("title", &values),
+            Some(String::from(r#"title:"say \"hello\" C:\\""#))
+        );
+    }
+
+    #[test]
+    fn test_filters_without_values() {
+        let filters = SearchFilters {
+            category: None,
+            min_price: None,
+            max_price: None,
+            tags: Vec::new(),
+            excluded_status: None,
+            title: None,
+            page: 1,
+        };
+
+        assert!(filters.filters().is_empty());
+    }
+}
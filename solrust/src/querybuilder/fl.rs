@@ -0,0 +1,124 @@
+//! This module defines builder struct that build the value for `fl` parameter.
+
+/// Builder of the [field list](https://solr.apache.org/guide/solr/latest/query-guide/common-query-parameters.html#fl-field-list-parameter),
+/// including the pseudo-fields and the [document transformers](https://solr.apache.org/guide/solr/latest/query-guide/document-transformers.html).
+///
+/// ```ignore
+/// let fl = FlBuilder::new().fields(&["id", "name"]).alias("price", "price_i").score().explain();
+/// assert_eq!(fl.build(), "id,name,price:price_i,score,[explain]");
+/// ```
+pub struct FlBuilder {
+    fields: Vec<String>,
+}
+
+impl Default for FlBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlBuilder {
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    pub fn build(&self) -> String {
+        self.fields.join(",")
+    }
+
+    fn push(mut self, field: String) -> Self {
+        if !self.fields.contains(&field) {
+            self.fields.push(field);
+        }
+        self
+    }
+
+    /// Add the field, or the glob pattern of the fields such as `*` and `name_*`.
+    pub fn field(self, field: &str) -> Self {
+        self.push(String::from(field))
+    }
+
+    pub fn fields(self, fields: &[&str]) -> Self {
+        fields.iter().fold(self, |fl, field| fl.field(field))
+    }
+
+    /// Add the field renamed to the alias in the response, e.g. `price:price_i`.
+    ///
+    /// The field can also be a function query or a document transformer.
+    pub fn alias(self, alias: &str, field: &str) -> Self {
+        self.push(format!("{}:{}", alias, field))
+    }
+
+    /// Add the `score` pseudo-field.
+    pub fn score(self) -> Self {
+        self.field("score")
+    }
+
+    /// Add the document transformer with the parameters, e.g. `[child limit=10]`.
+    pub fn transformer(self, name: &str, params: &[(&str, &str)]) -> Self {
+        let transformer = std::iter::once(String::from(name))
+            .chain(
+                params
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value)),
+            )
+            .collect::<Vec<String>>()
+            .join(" ");
+        self.push(format!("[{}]", transformer))
+    }
+
+    /// Add the `[explain]` transformer.
+    pub fn explain(self) -> Self {
+        self.transformer("explain", &[])
+    }
+
+    /// Add the `[docid]` transformer, the Lucene document id.
+    pub fn docid(self) -> Self {
+        self.transformer("docid", &[])
+    }
+
+    /// Add the `[shard]` transformer, the shard from which the document comes.
+    pub fn shard(self) -> Self {
+        self.transformer("shard", &[])
+    }
+
+    /// Add the `[child]` transformer returning at most `limit` child documents.
+    pub fn child(self, limit: u32) -> Self {
+        self.transformer("child", &[("limit", &limit.to_string())])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build_field_list() {
+        let fl = FlBuilder::new()
+            .fields(&["id", "name"])
+            .alias("price", "price_i")
+            .score()
+            .build();
+
+        assert_eq!(fl, "id,name,price:price_i,score");
+    }
+
+    #[test]
+    fn test_build_field_list_with_transformers() {
+        let fl = FlBuilder::new()
+            .field("id")
+            .explain()
+            .docid()
+            .shard()
+            .child(10)
+            .transformer("subquery", &[])
+            .alias("parent", "[docid]")
+            .field("id")
+            .build();
+
+        assert_eq!(
+            fl,
+            "id,[explain],[docid],[shard],[child limit=10],[subquery],parent:[docid]"
+        );
+    }
+}
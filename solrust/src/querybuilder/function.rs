@@ -0,0 +1,169 @@
+//! This module defines the struct that expresses [function queries](https://solr.apache.org/guide/solr/latest/query-guide/function-queries.html),
+//! used for `bf`, `boost` and the sort by functions.
+
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FunctionQueryError {
+    #[error("Name `{0}` is not a valid identifier")]
+    InvalidNameError(String),
+}
+
+/// Function query composed of the fields, the constants and the function calls, e.g. `div(popularity,price)`.
+///
+/// A string is converted into the function query as it is, so that the methods accepting the function query also accept the raw expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionQuery {
+    expression: String,
+}
+
+impl FunctionQuery {
+    /// Create the value of the field, or return an error if the name is not a valid identifier.
+    pub fn field(name: &str) -> Result<Self, FunctionQueryError> {
+        validate_identifier(name)?;
+        Ok(Self {
+            expression: String::from(name),
+        })
+    }
+
+    /// Create the numeric constant.
+    pub fn constant(value: f64) -> Self {
+        Self {
+            expression: value.to_string(),
+        }
+    }
+
+    /// Create the string constant, which is quoted.
+    pub fn string(value: &str) -> Self {
+        Self {
+            expression: format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'")),
+        }
+    }
+
+    /// Create the call of the function with the arguments, e.g. `call("sum", &[x, y])` to `sum(x,y)`,
+    /// or return an error if the name is not a valid identifier.
+    pub fn call(name: &str, args: &[FunctionQuery]) -> Result<Self, FunctionQueryError> {
+        validate_identifier(name)?;
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        Ok(Self {
+            expression: format!("{}({})", name, args.join(",")),
+        })
+    }
+
+    /// Create the `geodist` function, the distance from the point to the location of the field.
+    pub fn geodist(field: &str, lat: f64, lon: f64) -> Result<Self, FunctionQueryError> {
+        Self::call(
+            "geodist",
+            &[
+                Self::field(field)?,
+                Self::constant(lat),
+                Self::constant(lon),
+            ],
+        )
+    }
+}
+
+impl Display for FunctionQuery {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl From<&str> for FunctionQuery {
+    fn from(expression: &str) -> Self {
+        Self {
+            expression: String::from(expression),
+        }
+    }
+}
+
+impl From<String> for FunctionQuery {
+    fn from(expression: String) -> Self {
+        Self { expression }
+    }
+}
+
+fn validate_identifier(name: &str) -> Result<(), FunctionQueryError> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '*')
+    {
+        Ok(())
+    } else {
+        Err(FunctionQueryError::InvalidNameError(String::from(name)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compose_function() {
+        let function = FunctionQuery::call(
+            "recip",
+            &[
+                FunctionQuery::call(
+                    "ms",
+                    &[
+                        FunctionQuery::from("NOW"),
+                        FunctionQuery::field("start_at").unwrap(),
+                    ],
+                )
+                .unwrap(),
+                FunctionQuery::constant(3.16e-11),
+                FunctionQuery::constant(1.0),
+                FunctionQuery::constant(1.0),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            function.to_string(),
+            "recip(ms(NOW,start_at),0.0000000000316,1,1)"
+        );
+    }
+
+    #[test]
+    fn test_string_constant() {
+        let function = FunctionQuery::call(
+            "termfreq",
+            &[
+                FunctionQuery::field("text").unwrap(),
+                FunctionQuery::string("it's"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(function.to_string(), r"termfreq(text,'it\'s')");
+    }
+
+    #[test]
+    fn test_geodist() {
+        assert_eq!(
+            FunctionQuery::geodist("location", 35.5, 139.0)
+                .unwrap()
+                .to_string(),
+            "geodist(location,35.5,139)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        assert_eq!(
+            FunctionQuery::field("price desc"),
+            Err(FunctionQueryError::InvalidNameError(String::from(
+                "price desc"
+            )))
+        );
+        assert_eq!(
+            FunctionQuery::call("log(x),sum", &[]),
+            Err(FunctionQueryError::InvalidNameError(String::from(
+                "log(x),sum"
+            )))
+        );
+        assert!(FunctionQuery::geodist("", 35.5, 139.0).is_err());
+    }
+}
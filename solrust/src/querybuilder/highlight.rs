@@ -0,0 +1,262 @@
+//! This module defines the builder that generates query parameters for [highlighting](https://solr.apache.org/guide/solr/latest/query-guide/highlighting.html).
+
+use crate::querybuilder::q::SolrQueryExpression;
+use std::fmt::Display;
+
+/// Value of `hl.method` parameter, the implementation of the highlighter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HighlightMethod {
+    Unified,
+    Original,
+    FastVector,
+}
+
+impl Display for HighlightMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HighlightMethod::Unified => write!(f, "unified"),
+            HighlightMethod::Original => write!(f, "original"),
+            HighlightMethod::FastVector => write!(f, "fastVector"),
+        }
+    }
+}
+
+/// Options of the highlighting, which are the defaults of all fields or the overrides of a field.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct HighlightOptions {
+    fragsize: Option<u32>,
+    snippets: Option<u32>,
+    tag_pre: Option<String>,
+    tag_post: Option<String>,
+}
+
+impl HighlightOptions {
+    /// Build the parameters with the prefix, `hl.` or `f.<FIELD_NAME>.hl.`.
+    fn build(&self, prefix: &str) -> Vec<(String, String)> {
+        let mut result = Vec::new();
+
+        if let Some(fragsize) = &self.fragsize {
+            result.push((format!("{}fragsize", prefix), fragsize.to_string()));
+        }
+
+        if let Some(snippets) = &self.snippets {
+            result.push((format!("{}snippets", prefix), snippets.to_string()));
+        }
+
+        if let Some(tag_pre) = &self.tag_pre {
+            result.push((format!("{}tag.pre", prefix), tag_pre.clone()));
+        }
+
+        if let Some(tag_post) = &self.tag_post {
+            result.push((format!("{}tag.post", prefix), tag_post.clone()));
+        }
+
+        result
+    }
+}
+
+/// Implementation of the builder generates parameters for the overrides of the highlighting of a field.
+///
+/// The options not set fall back to those of [`HighlightBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighlightFieldBuilder {
+    field: String,
+    options: HighlightOptions,
+}
+
+impl HighlightFieldBuilder {
+    pub fn new(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            options: HighlightOptions::default(),
+        }
+    }
+
+    /// Add `f.<FIELD_NAME>.hl.fragsize` parameter. 0 highlights the whole value of the field.
+    pub fn fragsize(mut self, fragsize: u32) -> Self {
+        self.options.fragsize = Some(fragsize);
+        self
+    }
+
+    /// Add `f.<FIELD_NAME>.hl.snippets` parameter.
+    pub fn snippets(mut self, snippets: u32) -> Self {
+        self.options.snippets = Some(snippets);
+        self
+    }
+
+    /// Add `f.<FIELD_NAME>.hl.tag.pre` and `f.<FIELD_NAME>.hl.tag.post` parameters.
+    pub fn tags(mut self, pre: &str, post: &str) -> Self {
+        self.options.tag_pre = Some(pre.to_string());
+        self.options.tag_post = Some(post.to_string());
+        self
+    }
+}
+
+/// Implementation of the builder generates parameters for highlighting, e.g.
+///
+/// ```ignore
+/// let highlight = HighlightBuilder::new(&["title", "body"])
+///     .snippets(3)
+///     .tags("<em>", "</em>")
+///     .field(HighlightFieldBuilder::new("title").fragsize(0).snippets(1));
+/// let builder = StandardQueryBuilder::new().q(&q).highlight(&highlight);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighlightBuilder {
+    fields: Vec<String>,
+    method: Option<HighlightMethod>,
+    q: Option<String>,
+    qparser: Option<String>,
+    options: HighlightOptions,
+    overrides: Vec<HighlightFieldBuilder>,
+}
+
+impl HighlightBuilder {
+    pub fn new(fields: &[&str]) -> Self {
+        Self {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            method: None,
+            q: None,
+            qparser: None,
+            options: HighlightOptions::default(),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Add `hl.method` parameter.
+    pub fn method(mut self, method: HighlightMethod) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Add `hl.q` parameter, the query to highlight instead of `q`.
+    ///
+    /// It is useful to highlight only the terms the user entered, not the terms added to boost the score.
+    pub fn q(mut self, q: &impl SolrQueryExpression) -> Self {
+        self.q = Some(q.to_string());
+        self
+    }
+
+    /// Add `hl.qparser` parameter, the query parser of `hl.q`. The parser of `q` is used by default.
+    pub fn qparser(mut self, qparser: &str) -> Self {
+        self.qparser = Some(qparser.to_string());
+        self
+    }
+
+    /// Add `hl.fragsize` parameter. 0 highlights the whole value of the fields.
+    pub fn fragsize(mut self, fragsize: u32) -> Self {
+        self.options.fragsize = Some(fragsize);
+        self
+    }
+
+    /// Add `hl.snippets` parameter.
+    pub fn snippets(mut self, snippets: u32) -> Self {
+        self.options.snippets = Some(snippets);
+        self
+    }
+
+    /// Add `hl.tag.pre` and `hl.tag.post` parameters.
+    pub fn tags(mut self, pre: &str, post: &str) -> Self {
+        self.options.tag_pre = Some(pre.to_string());
+        self.options.tag_post = Some(post.to_string());
+        self
+    }
+
+    /// Override the options of the field. The field is added to `hl.fl` if it is not listed yet.
+    pub fn field(mut self, field: HighlightFieldBuilder) -> Self {
+        if !self.fields.contains(&field.field) {
+            self.fields.push(field.field.clone());
+        }
+        self.overrides.push(field);
+        self
+    }
+
+    pub fn build(&self) -> Vec<(String, String)> {
+        let mut result = vec![
+            (String::from("hl"), String::from("true")),
+            (String::from("hl.fl"), self.fields.join(",")),
+        ];
+
+        if let Some(method) = &self.method {
+            result.push((String::from("hl.method"), method.to_string()));
+        }
+
+        if let Some(q) = &self.q {
+            result.push((String::from("hl.q"), q.clone()));
+        }
+
+        if let Some(qparser) = &self.qparser {
+            result.push((String::from("hl.qparser"), qparser.clone()));
+        }
+
+        result.extend(self.options.build("hl."));
+        for field in self.overrides.iter() {
+            result.extend(field.options.build(&format!("f.{}.hl.", field.field)));
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::querybuilder::q::QueryOperand;
+
+    #[test]
+    fn test_simple_highlight() {
+        let builder = HighlightBuilder::new(&["title", "body"]);
+
+        assert_eq!(
+            vec![
+                (String::from("hl"), String::from("true")),
+                (String::from("hl.fl"), String::from("title,body")),
+            ],
+            builder.build()
+        );
+    }
+
+    #[test]
+    fn test_highlight_with_field_overrides() {
+        let builder = HighlightBuilder::new(&["body"])
+            .method(HighlightMethod::Unified)
+            .fragsize(100)
+            .snippets(3)
+            .tags("<em>", "</em>")
+            .field(HighlightFieldBuilder::new("title").fragsize(0).snippets(1))
+            .field(HighlightFieldBuilder::new("body").tags("<b>", "</b>"));
+
+        assert_eq!(
+            vec![
+                (String::from("hl"), String::from("true")),
+                (String::from("hl.fl"), String::from("body,title")),
+                (String::from("hl.method"), String::from("unified")),
+                (String::from("hl.fragsize"), String::from("100")),
+                (String::from("hl.snippets"), String::from("3")),
+                (String::from("hl.tag.pre"), String::from("<em>")),
+                (String::from("hl.tag.post"), String::from("</em>")),
+                (String::from("f.title.hl.fragsize"), String::from("0")),
+                (String::from("f.title.hl.snippets"), String::from("1")),
+                (String::from("f.body.hl.tag.pre"), String::from("<b>")),
+                (String::from("f.body.hl.tag.post"), String::from("</b>")),
+            ],
+            builder.build()
+        );
+    }
+
+    #[test]
+    fn test_highlight_query() {
+        let q = QueryOperand::from("title:rust");
+        let builder = HighlightBuilder::new(&["title"]).q(&q).qparser("lucene");
+
+        assert_eq!(
+            vec![
+                (String::from("hl"), String::from("true")),
+                (String::from("hl.fl"), String::from("title")),
+                (String::from("hl.q"), String::from("title:rust")),
+                (String::from("hl.qparser"), String::from("lucene")),
+            ],
+            builder.build()
+        );
+    }
+}
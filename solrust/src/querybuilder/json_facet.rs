@@ -0,0 +1,608 @@
+//! This module defines the builders that generate the `json.facet` parameter of [JSON Facet API](https://solr.apache.org/guide/solr/latest/query-guide/json-facet-api.html).
+//!
+//! The results are deserialized into [`SolrJsonFacetResponse`](crate::types::response::SolrJsonFacetResponse).
+
+use crate::querybuilder::facet::FacetBuilder;
+use crate::querybuilder::function::FunctionQuery;
+use crate::querybuilder::q::{local_param_value, SolrQueryExpression};
+use serde_json::{json, Map, Value};
+
+/// Node of the JSON Facet DSL, such as a facet or an aggregation.
+pub trait JsonFacetNode {
+    fn to_value(&self) -> Value;
+}
+
+/// Builder of the `json.facet` parameter with the named facets, e.g.
+///
+/// ```ignore
+/// let facet = JsonFacetBuilder::new()
+///     .facet("categories", &JsonTermsFacet::new("category").limit(10))
+///     .facet(
+///         "all_colors",
+///         &JsonTermsFacet::new("color").domain(JsonFacetDomain::new().exclude_tags(&["color"])),
+///     );
+/// let builder = StandardQueryBuilder::new().q(&q).facet(&facet);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonFacetBuilder {
+    facets: Map<String, Value>,
+}
+
+impl JsonFacetBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the facet with the name, by which the result is keyed in the response.
+    pub fn facet(mut self, name: &str, facet: &impl JsonFacetNode) -> Self {
+        self.facets.insert(String::from(name), facet.to_value());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.facets.is_empty()
+    }
+
+    /// Render the facets as the value of `json.facet` parameter.
+    pub fn to_json(&self) -> String {
+        Value::Object(self.facets.clone()).to_string()
+    }
+}
+
+impl FacetBuilder for JsonFacetBuilder {
+    fn build(&self) -> Vec<(String, String)> {
+        vec![(String::from("json.facet"), self.to_json())]
+    }
+}
+
+/// Domain of a facet, the set of the documents on which the facet is computed instead of the documents matching the query.
+///
+/// The changes are applied in the order of `excludeTags`, `query`, `blockParent`/`blockChildren` and `filter` by Solr.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonFacetDomain {
+    options: Map<String, Value>,
+}
+
+impl JsonFacetDomain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `excludeTags`, the tags of the filters removed from the domain, e.g. for multi-select faceting.
+    pub fn exclude_tags(mut self, tags: &[&str]) -> Self {
+        self.push("excludeTags", tags.iter().map(|tag| json!(tag)));
+        self
+    }
+
+    /// Add `query`, the query replacing the domain.
+    pub fn query(mut self, query: &impl SolrQueryExpression) -> Self {
+        self.push("query", [json!(query.to_string())]);
+        self
+    }
+
+    /// Add `filter`, the query filtering the domain.
+    ///
+    /// The filters are added as many times as this method is called.
+    pub fn filter(mut self, filter: &impl SolrQueryExpression) -> Self {
+        self.push("filter", [json!(filter.to_string())]);
+        self
+    }
+
+    /// Add `blockChildren`, which maps the parent documents in the domain to their children.
+    ///
+    /// The query must match all the parent documents, e.g. `doc_type:product`.
+    pub fn block_children(mut self, parents: &impl SolrQueryExpression) -> Self {
+        self.options
+            .insert(String::from("blockChildren"), json!(parents.to_string()));
+        self
+    }
+
+    /// Add `blockParent`, which maps the child documents in the domain to their parents.
+    ///
+    /// The query must match all the parent documents, e.g. `doc_type:product`.
+    pub fn block_parent(mut self, parents: &impl SolrQueryExpression) -> Self {
+        self.options
+            .insert(String::from("blockParent"), json!(parents.to_string()));
+        self
+    }
+
+    fn push(&mut self, key: &str, values: impl IntoIterator<Item = Value>) {
+        if let Value::Array(array) = self
+            .options
+            .entry(key)
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            array.extend(values);
+        }
+    }
+}
+
+impl JsonFacetNode for JsonFacetDomain {
+    fn to_value(&self) -> Value {
+        Value::Object(self.options.clone())
+    }
+}
+
+/// Options and sub facets shared by the facets.
+#[derive(Clone, Debug, PartialEq)]
+struct JsonFacetOptions {
+    options: Map<String, Value>,
+}
+
+impl JsonFacetOptions {
+    fn new(facet_type: &str) -> Self {
+        let mut options = Map::new();
+        options.insert(String::from("type"), json!(facet_type));
+        Self { options }
+    }
+
+    fn set(&mut self, key: &str, value: Value) {
+        self.options.insert(String::from(key), value);
+    }
+
+    fn facet(&mut self, name: &str, facet: &impl JsonFacetNode) {
+        if let Value::Object(facets) = self
+            .options
+            .entry("facet")
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            facets.insert(String::from(name), facet.to_value());
+        }
+    }
+}
+
+/// Terms facet, which counts the documents by the values of the field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonTermsFacet {
+    inner: JsonFacetOptions,
+}
+
+impl JsonTermsFacet {
+    pub fn new(field: &str) -> Self {
+        let mut inner = JsonFacetOptions::new("terms");
+        inner.set("field", json!(field));
+        Self { inner }
+    }
+
+    /// Add `offset` option.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.inner.set("offset", json!(offset));
+        self
+    }
+
+    /// Add `limit` option. -1 means unlimited.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.inner.set("limit", json!(limit));
+        self
+    }
+
+    /// Add `sort` option, e.g. `count desc` or `index asc`, or the name of a sub aggregation.
+    pub fn sort(mut self, sort: &str) -> Self {
+        self.inner.set("sort", json!(sort));
+        self
+    }
+
+    /// Add `mincount` option.
+    pub fn min_count(mut self, min_count: u32) -> Self {
+        self.inner.set("mincount", json!(min_count));
+        self
+    }
+
+    /// Add `missing` option, the bucket of the documents without the field.
+    pub fn missing(mut self, missing: bool) -> Self {
+        self.inner.set("missing", json!(missing));
+        self
+    }
+
+    /// Add `numBuckets` option, the number of the buckets regardless of the limit.
+    pub fn num_buckets(mut self, num_buckets: bool) -> Self {
+        self.inner.set("numBuckets", json!(num_buckets));
+        self
+    }
+
+    /// Add `allBuckets` option, the bucket of all the documents with the field.
+    pub fn all_buckets(mut self, all_buckets: bool) -> Self {
+        self.inner.set("allBuckets", json!(all_buckets));
+        self
+    }
+
+    /// Add `prefix` option.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.inner.set("prefix", json!(prefix));
+        self
+    }
+
+    /// Add `domain` option.
+    pub fn domain(mut self, domain: JsonFacetDomain) -> Self {
+        self.inner.set("domain", domain.to_value());
+        self
+    }
+
+    /// Add the sub facet or the aggregation computed in each bucket.
+    pub fn facet(mut self, name: &str, facet: &impl JsonFacetNode) -> Self {
+        self.inner.facet(name, facet);
+        self
+    }
+}
+
+impl JsonFacetNode for JsonTermsFacet {
+    fn to_value(&self) -> Value {
+        Value::Object(self.inner.options.clone())
+    }
+}
+
+/// Query facet, which counts the documents matching the query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonQueryFacet {
+    inner: JsonFacetOptions,
+}
+
+impl JsonQueryFacet {
+    pub fn new(query: &impl SolrQueryExpression) -> Self {
+        let mut inner = JsonFacetOptions::new("query");
+        inner.set("q", json!(query.to_string()));
+        Self { inner }
+    }
+
+    /// Add `domain` option.
+    pub fn domain(mut self, domain: JsonFacetDomain) -> Self {
+        self.inner.set("domain", domain.to_value());
+        self
+    }
+
+    /// Add the sub facet or the aggregation computed in the bucket.
+    pub fn facet(mut self, name: &str, facet: &impl JsonFacetNode) -> Self {
+        self.inner.facet(name, facet);
+        self
+    }
+}
+
+impl JsonFacetNode for JsonQueryFacet {
+    fn to_value(&self) -> Value {
+        Value::Object(self.inner.options.clone())
+    }
+}
+
+/// Range facet, which counts the documents by the ranges of the numeric or date field.
+///
+/// The dates are given as the strings, e.g. `NOW/DAY-7DAYS` and `+1DAY`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonRangeFacet {
+    inner: JsonFacetOptions,
+}
+
+impl JsonRangeFacet {
+    pub fn new(
+        field: &str,
+        start: impl Into<Value>,
+        end: impl Into<Value>,
+        gap: impl Into<Value>,
+    ) -> Self {
+        let mut inner = JsonFacetOptions::new("range");
+        inner.set("field", json!(field));
+        inner.set("start", start.into());
+        inner.set("end", end.into());
+        inner.set("gap", gap.into());
+        Self { inner }
+    }
+
+    /// Add `hardend` option.
+    pub fn hardend(mut self, hardend: bool) -> Self {
+        self.inner.set("hardend", json!(hardend));
+        self
+    }
+
+    /// Add `other` option, e.g. `before`, `after`, `between` or `all`.
+    pub fn other(mut self, other: &str) -> Self {
+        self.inner.set("other", json!(other));
+        self
+    }
+
+    /// Add `mincount` option.
+    pub fn min_count(mut self, min_count: u32) -> Self {
+        self.inner.set("mincount", json!(min_count));
+        self
+    }
+
+    /// Add `domain` option.
+    pub fn domain(mut self, domain: JsonFacetDomain) -> Self {
+        self.inner.set("domain", domain.to_value());
+        self
+    }
+
+    /// Add the sub facet or the aggregation computed in each bucket.
+    pub fn facet(mut self, name: &str, facet: &impl JsonFacetNode) -> Self {
+        self.inner.facet(name, facet);
+        self
+    }
+}
+
+impl JsonFacetNode for JsonRangeFacet {
+    fn to_value(&self) -> Value {
+        Value::Object(self.inner.options.clone())
+    }
+}
+
+/// Aggregation computed over the domain, e.g. `avg(price)`.
+///
+/// The results are deserialized into [`SolrJsonFacetStat`](crate::types::response::SolrJsonFacetStat).
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonAggregation {
+    expression: String,
+}
+
+impl JsonAggregation {
+    fn call(name: &str, arg: impl Into<FunctionQuery>) -> Self {
+        Self {
+            expression: format!("{}({})", name, arg.into()),
+        }
+    }
+
+    /// Create `sum` aggregation of the field or the function.
+    pub fn sum(arg: impl Into<FunctionQuery>) -> Self {
+        Self::call("sum", arg)
+    }
+
+    /// Create `avg` aggregation of the field or the function.
+    pub fn avg(arg: impl Into<FunctionQuery>) -> Self {
+        Self::call("avg", arg)
+    }
+
+    /// Create `min` aggregation of the field or the function.
+    pub fn min(arg: impl Into<FunctionQuery>) -> Self {
+        Self::call("min", arg)
+    }
+
+    /// Create `max` aggregation of the field or the function.
+    pub fn max(arg: impl Into<FunctionQuery>) -> Self {
+        Self::call("max", arg)
+    }
+
+    /// Create `unique` aggregation, the exact number of the unique values of the field.
+    pub fn unique(field: &str) -> Self {
+        Self::call("unique", field)
+    }
+
+    /// Create `hll` aggregation, the number of the unique values of the field estimated by HyperLogLog.
+    pub fn hll(field: &str) -> Self {
+        Self::call("hll", field)
+    }
+
+    /// Create `percentile` aggregation with the percents, e.g. `percentile(price,50,95)`.
+    ///
+    /// The result is a number for a percent, and a list of the numbers for the multiple percents.
+    pub fn percentile(arg: impl Into<FunctionQuery>, percents: &[f64]) -> Self {
+        let mut args = vec![arg.into().to_string()];
+        args.extend(percents.iter().map(|percent| percent.to_string()));
+        Self {
+            expression: format!("percentile({})", args.join(",")),
+        }
+    }
+}
+
+impl JsonFacetNode for JsonAggregation {
+    fn to_value(&self) -> Value {
+        json!(self.expression)
+    }
+}
+
+/// `relatedness` aggregation, which scores how the terms of the buckets are related to the foreground set
+/// compared with the background set, as in the semantic knowledge graph.
+///
+/// The result is deserialized into [`SolrRelatedness`](crate::types::response::SolrRelatedness).
+/// Sort the terms facet by it to find the significant terms, e.g. `sort("r desc")`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonRelatedness {
+    fore: String,
+    back: String,
+    min_popularity: Option<f64>,
+}
+
+impl JsonRelatedness {
+    /// Create the aggregation with the queries of the foreground and the background sets.
+    pub fn new(fore: &impl SolrQueryExpression, back: &impl SolrQueryExpression) -> Self {
+        Self {
+            fore: format!("{{!v={}}}", local_param_value(&fore.to_string())),
+            back: format!("{{!v={}}}", local_param_value(&back.to_string())),
+            min_popularity: None,
+        }
+    }
+
+    /// Create the aggregation with the names of the request parameters of the foreground and the background queries,
+    /// e.g. `fore` and `back` for `relatedness($fore,$back)`.
+    pub fn from_params(fore: &str, back: &str) -> Self {
+        Self {
+            fore: format!("${}", fore),
+            back: format!("${}", back),
+            min_popularity: None,
+        }
+    }
+
+    /// Add `min_popularity` option, to skip the terms rare in the foreground or the background set.
+    pub fn min_popularity(mut self, min_popularity: f64) -> Self {
+        self.min_popularity = Some(min_popularity);
+        self
+    }
+}
+
+impl JsonFacetNode for JsonRelatedness {
+    fn to_value(&self) -> Value {
+        let mut options = Map::new();
+        options.insert(String::from("type"), json!("func"));
+        options.insert(
+            String::from("func"),
+            json!(format!("relatedness({},{})", self.fore, self.back)),
+        );
+        if let Some(min_popularity) = self.min_popularity {
+            options.insert(String::from("min_popularity"), json!(min_popularity));
+        }
+        Value::Object(options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::querybuilder::q::QueryOperand;
+
+    #[test]
+    fn test_terms_facet_with_sub_facets() {
+        let facet = JsonFacetBuilder::new().facet(
+            "categories",
+            &JsonTermsFacet::new("category")
+                .limit(5)
+                .sort("count desc")
+                .min_count(1)
+                .facet(
+                    "cheap",
+                    &JsonQueryFacet::new(&QueryOperand::from("price:[* TO 100]")),
+                ),
+        );
+
+        let value: Value = serde_json::from_str(&facet.to_json()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "categories": {
+                    "type": "terms",
+                    "field": "category",
+                    "limit": 5,
+                    "sort": "count desc",
+                    "mincount": 1,
+                    "facet": {
+                        "cheap": {"type": "query", "q": "price:[* TO 100]"}
+                    }
+                }
+            })
+        );
+        assert_eq!(facet.build()[0].0, "json.facet");
+    }
+
+    #[test]
+    fn test_aggregations() {
+        let facet = JsonFacetBuilder::new()
+            .facet("total", &JsonAggregation::sum("price"))
+            .facet("brands", &JsonAggregation::unique("brand"))
+            .facet("visitors", &JsonAggregation::hll("user_id"))
+            .facet(
+                "latency",
+                &JsonAggregation::percentile("response_time", &[50.0, 95.0, 99.9]),
+            )
+            .facet(
+                "categories",
+                &JsonTermsFacet::new("category")
+                    .sort("avg_price desc")
+                    .facet("avg_price", &JsonAggregation::avg("price"))
+                    .facet(
+                        "max_discount",
+                        &JsonAggregation::max(
+                            FunctionQuery::call(
+                                "sub",
+                                &[
+                                    FunctionQuery::field("list_price").unwrap(),
+                                    FunctionQuery::field("price").unwrap(),
+                                ],
+                            )
+                            .unwrap(),
+                        ),
+                    )
+                    .facet("min_price", &JsonAggregation::min("price")),
+            );
+
+        let value: Value = serde_json::from_str(&facet.to_json()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "total": "sum(price)",
+                "brands": "unique(brand)",
+                "visitors": "hll(user_id)",
+                "latency": "percentile(response_time,50,95,99.9)",
+                "categories": {
+                    "type": "terms",
+                    "field": "category",
+                    "sort": "avg_price desc",
+                    "facet": {
+                        "avg_price": "avg(price)",
+                        "max_discount": "max(sub(list_price,price))",
+                        "min_price": "min(price)"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_relatedness() {
+        let facet = JsonTermsFacet::new("keywords").sort("r desc").facet(
+            "r",
+            &JsonRelatedness::new(
+                &QueryOperand::from("category:rust AND year:2023"),
+                &QueryOperand::from("*:*"),
+            )
+            .min_popularity(0.001),
+        );
+
+        assert_eq!(
+            facet.to_value(),
+            json!({
+                "type": "terms",
+                "field": "keywords",
+                "sort": "r desc",
+                "facet": {
+                    "r": {
+                        "type": "func",
+                        "func": "relatedness({!v='category:rust AND year:2023'},{!v=*:*})",
+                        "min_popularity": 0.001
+                    }
+                }
+            })
+        );
+        assert_eq!(
+            JsonRelatedness::from_params("fore", "back").to_value(),
+            json!({"type": "func", "func": "relatedness($fore,$back)"})
+        );
+    }
+
+    #[test]
+    fn test_domain() {
+        let domain = JsonFacetDomain::new()
+            .exclude_tags(&["color", "size"])
+            .filter(&QueryOperand::from("in_stock:true"))
+            .filter(&QueryOperand::from("price:[* TO 100]"))
+            .block_parent(&QueryOperand::from("doc_type:product"));
+        let facet = JsonTermsFacet::new("brand").domain(domain);
+
+        assert_eq!(
+            facet.to_value(),
+            json!({
+                "type": "terms",
+                "field": "brand",
+                "domain": {
+                    "excludeTags": ["color", "size"],
+                    "filter": ["in_stock:true", "price:[* TO 100]"],
+                    "blockParent": "doc_type:product"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_facet_with_block_children() {
+        let facet = JsonRangeFacet::new("price", 0, 1000, 250)
+            .other("after")
+            .domain(JsonFacetDomain::new().block_children(&QueryOperand::from("doc_type:product")));
+
+        assert_eq!(
+            facet.to_value(),
+            json!({
+                "type": "range",
+                "field": "price",
+                "start": 0,
+                "end": 1000,
+                "gap": 250,
+                "other": "after",
+                "domain": {"blockChildren": "doc_type:product"}
+            })
+        );
+    }
+}
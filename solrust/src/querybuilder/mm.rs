@@ -0,0 +1,203 @@
+//! This module defines the struct that expresses the [minimum should match](https://solr.apache.org/guide/solr/latest/query-guide/dismax-query-parser.html#mm-minimum-should-match-parameter)
+//! specification of DisMax and eDisMax Query Parser.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MmError {
+    #[error("Percentage `{0}` is out of the range from -100 to 100")]
+    InvalidPercentageError(i32),
+    #[error("Conditional specification has no conditions")]
+    EmptyConditionsError,
+    #[error("Condition `{0}` is not greater than the previous condition")]
+    UnorderedConditionError(u32),
+    #[error("Failed to parse minimum should match specification `{0}`")]
+    ParseError(String),
+}
+
+/// Number of the optional clauses that must match, as an absolute count or a percentage.
+///
+/// A negative value is the number of the clauses that may be missing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MmValue {
+    Count(i32),
+    Percent(i32),
+}
+
+impl MmValue {
+    fn validate(&self) -> Result<(), MmError> {
+        match self {
+            MmValue::Percent(percent) if !(-100..=100).contains(percent) => {
+                Err(MmError::InvalidPercentageError(*percent))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for MmValue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            MmValue::Count(count) => write!(f, "{}", count),
+            MmValue::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+impl FromStr for MmValue {
+    type Err = MmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = match s.strip_suffix('%') {
+            Some(percent) => percent.parse().map(MmValue::Percent),
+            None => s.parse().map(MmValue::Count),
+        }
+        .map_err(|_| MmError::ParseError(s.to_string()))?;
+
+        value.validate()?;
+        Ok(value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MmKind {
+    Value(MmValue),
+    Conditional(Vec<(u32, MmValue)>),
+}
+
+/// Validated minimum should match specification, e.g. `3`, `75%` or `2<-25% 9<-3`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mm {
+    kind: MmKind,
+}
+
+impl Mm {
+    /// Create the specification of the absolute number of the clauses.
+    pub fn count(count: i32) -> Self {
+        Self {
+            kind: MmKind::Value(MmValue::Count(count)),
+        }
+    }
+
+    /// Create the specification of the percentage of the clauses.
+    pub fn percent(percent: i32) -> Result<Self, MmError> {
+        let value = MmValue::Percent(percent);
+        value.validate()?;
+        Ok(Self {
+            kind: MmKind::Value(value),
+        })
+    }
+
+    /// Create the conditional specification. Each condition `(n, value)` means that `value` applies
+    /// when there are more than `n` optional clauses, and all of the clauses must match otherwise.
+    ///
+    /// The conditions must be in ascending order of `n`.
+    pub fn conditional(conditions: &[(u32, MmValue)]) -> Result<Self, MmError> {
+        if conditions.is_empty() {
+            return Err(MmError::EmptyConditionsError);
+        }
+
+        let mut previous: Option<u32> = None;
+        for (n, value) in conditions {
+            if previous.is_some_and(|previous| *n <= previous) {
+                return Err(MmError::UnorderedConditionError(*n));
+            }
+            value.validate()?;
+            previous = Some(*n);
+        }
+
+        Ok(Self {
+            kind: MmKind::Conditional(conditions.to_vec()),
+        })
+    }
+}
+
+impl Display for Mm {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self.kind {
+            MmKind::Value(value) => write!(f, "{}", value),
+            MmKind::Conditional(conditions) => {
+                for (i, (n, value)) in conditions.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}<{}", n, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Mm {
+    type Err = MmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !s.contains('<') {
+            let value: MmValue = s.parse()?;
+            return Ok(Self {
+                kind: MmKind::Value(value),
+            });
+        }
+
+        let conditions = s
+            .split_whitespace()
+            .map(|condition| {
+                let (n, value) = condition
+                    .split_once('<')
+                    .ok_or_else(|| MmError::ParseError(s.to_string()))?;
+                let n = n
+                    .parse::<u32>()
+                    .map_err(|_| MmError::ParseError(s.to_string()))?;
+                Ok((n, value.parse::<MmValue>()?))
+            })
+            .collect::<Result<Vec<(u32, MmValue)>, MmError>>()?;
+
+        Self::conditional(&conditions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_count_and_percent() {
+        assert_eq!(Mm::count(-2).to_string(), "-2");
+        assert_eq!(Mm::percent(75).unwrap().to_string(), "75%");
+        assert_eq!(Mm::percent(101), Err(MmError::InvalidPercentageError(101)));
+    }
+
+    #[test]
+    fn test_conditional() {
+        let mm = Mm::conditional(&[(2, MmValue::Percent(-25)), (9, MmValue::Count(-3))]).unwrap();
+        assert_eq!(mm.to_string(), "2<-25% 9<-3");
+
+        assert_eq!(
+            Mm::conditional(&[(9, MmValue::Count(-3)), (2, MmValue::Percent(-25))]),
+            Err(MmError::UnorderedConditionError(2))
+        );
+        assert_eq!(Mm::conditional(&[]), Err(MmError::EmptyConditionsError));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("3".parse::<Mm>().unwrap(), Mm::count(3));
+        assert_eq!("-25%".parse::<Mm>().unwrap(), Mm::percent(-25).unwrap());
+        assert_eq!(
+            "2<-25% 9<-3".parse::<Mm>().unwrap().to_string(),
+            "2<-25% 9<-3"
+        );
+        assert_eq!(
+            "3<150%".parse::<Mm>(),
+            Err(MmError::InvalidPercentageError(150))
+        );
+        assert_eq!(
+            "2<-25% nine<-3".parse::<Mm>(),
+            Err(MmError::ParseError(String::from("2<-25% nine<-3")))
+        );
+    }
+}
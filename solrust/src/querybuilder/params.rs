@@ -0,0 +1,157 @@
+//! This module provides the struct of the parameters built by the query builders.
+
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+/// Parameters of a search request built by the query builders.
+///
+/// The parameters can be iterated as the pairs of the key and the value, serialized as the query of a request,
+/// or rendered as the encoded query string.
+///
+/// ```ignore
+/// let params = StandardQueryBuilder::new().q(&q).rows(10).into_params();
+/// assert_eq!(params.get("rows"), Some("10"));
+/// let url = format!("http://localhost:8983/solr/example/select?{}", params);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolrQueryParams {
+    params: Vec<(String, String)>,
+}
+
+impl SolrQueryParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the parameter. A parameter with the same key can be added multiple times, such as `fq`.
+    pub fn push(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.params.push((key.into(), value.into()));
+    }
+
+    /// Return the first value of the parameter with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Return all values of the parameter with the given key.
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.params
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, (String, String)> {
+        self.params.iter()
+    }
+
+    /// Render the parameters as the URL-encoded query string, e.g. `q=name%3Aalice&rows=10`.
+    pub fn to_query_string(&self) -> String {
+        url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(self.params.iter())
+            .finish()
+    }
+}
+
+impl Display for SolrQueryParams {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_query_string())
+    }
+}
+
+impl Serialize for SolrQueryParams {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.params.iter())
+    }
+}
+
+impl IntoIterator for SolrQueryParams {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.params.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SolrQueryParams {
+    type Item = &'a (String, String);
+    type IntoIter = std::slice::Iter<'a, (String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.params.iter()
+    }
+}
+
+impl FromIterator<(String, String)> for SolrQueryParams {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            params: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<(String, String)> for SolrQueryParams {
+    fn extend<I: IntoIterator<Item = (String, String)>>(&mut self, iter: I) {
+        self.params.extend(iter)
+    }
+}
+
+impl From<SolrQueryParams> for Vec<(String, String)> {
+    fn from(params: SolrQueryParams) -> Self {
+        params.params
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn params() -> SolrQueryParams {
+        let mut params = SolrQueryParams::new();
+        params.push("q", "name:alice bob");
+        params.push("fq", "age:[20 TO *]");
+        params.push("fq", "gender:female");
+        params
+    }
+
+    #[test]
+    fn test_get() {
+        let params = params();
+
+        assert_eq!(params.get("q"), Some("name:alice bob"));
+        assert_eq!(params.get_all("fq"), vec!["age:[20 TO *]", "gender:female"]);
+        assert_eq!(params.get("rows"), None);
+    }
+
+    #[test]
+    fn test_to_query_string() {
+        assert_eq!(
+            params().to_query_string(),
+            "q=name%3Aalice+bob&fq=age%3A%5B20+TO+*%5D&fq=gender%3Afemale"
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(
+            serde_json::to_string(&params()).unwrap(),
+            r#"[["q","name:alice bob"],["fq","age:[20 TO *]"],["fq","gender:female"]]"#
+        );
+    }
+}
@@ -75,10 +75,10 @@ impl ops::Add<QueryExpression> for QueryOperand {
         match rhs.operator {
             Operator::OR => {
                 let mut operands = vec![QueryExpressionKind::Operand(self)];
-                operands.extend(rhs.operands.into_iter());
+                operands.extend(rhs.operands);
                 QueryExpression {
                     operator: Operator::OR,
-                    operands: operands,
+                    operands,
                 }
             }
             Operator::AND => QueryExpression {
@@ -100,10 +100,10 @@ impl ops::Mul<QueryExpression> for QueryOperand {
         match rhs.operator {
             Operator::AND => {
                 let mut operands = vec![QueryExpressionKind::Operand(self)];
-                operands.extend(rhs.operands.into_iter());
+                operands.extend(rhs.operands);
                 QueryExpression {
                     operator: Operator::AND,
-                    operands: operands,
+                    operands,
                 }
             }
             Operator::OR => QueryExpression {
@@ -142,7 +142,7 @@ impl Aggregation<QueryOperand> for QueryExpression {
             operator: Operator::OR,
             operands: operands
                 .into_iter()
-                .map(|op| QueryExpressionKind::Operand(op))
+                .map(QueryExpressionKind::Operand)
                 .collect(),
         }
     }
@@ -152,7 +152,7 @@ impl Aggregation<QueryOperand> for QueryExpression {
             operator: Operator::AND,
             operands: operands
                 .into_iter()
-                .map(|op| QueryExpressionKind::Operand(op))
+                .map(QueryExpressionKind::Operand)
                 .collect(),
         }
     }
@@ -165,7 +165,7 @@ impl Aggregation<QueryExpression> for QueryExpression {
             operator: Operator::OR,
             operands: operands
                 .into_iter()
-                .map(|op| QueryExpressionKind::Expression(op))
+                .map(QueryExpressionKind::Expression)
                 .collect(),
         }
     }
@@ -175,7 +175,7 @@ impl Aggregation<QueryExpression> for QueryExpression {
             operator: Operator::AND,
             operands: operands
                 .into_iter()
-                .map(|op| QueryExpressionKind::Expression(op))
+                .map(QueryExpressionKind::Expression)
                 .collect(),
         }
     }
@@ -196,7 +196,7 @@ impl Display for QueryExpression {
             .iter()
             .map(|expr| match expr {
                 QueryExpressionKind::Operand(op) => op.to_string(),
-                QueryExpressionKind::Expression(expr) => format!("({})", expr.to_string()),
+                QueryExpressionKind::Expression(expr) => format!("({})", expr),
             })
             .collect::<Vec<String>>()
             .join(operator);
@@ -212,22 +212,19 @@ impl ops::Add<QueryExpression> for QueryExpression {
 
     fn add(self, rhs: QueryExpression) -> QueryExpression {
         if self.operator == Operator::OR && rhs.operator == Operator::OR {
-            let operands = Vec::from_iter(itertools::chain(
-                self.operands.into_iter(),
-                rhs.operands.into_iter(),
-            ));
-            return QueryExpression {
+            let operands = Vec::from_iter(itertools::chain(self.operands, rhs.operands));
+            QueryExpression {
                 operator: Operator::OR,
                 operands,
-            };
+            }
         } else {
-            return QueryExpression {
+            QueryExpression {
                 operator: Operator::OR,
                 operands: vec![
                     QueryExpressionKind::Expression(self),
                     QueryExpressionKind::Expression(rhs),
                 ],
-            };
+            }
         }
     }
 }
@@ -238,22 +235,19 @@ impl ops::Mul<QueryExpression> for QueryExpression {
 
     fn mul(self, rhs: QueryExpression) -> QueryExpression {
         if self.operator == Operator::AND && rhs.operator == Operator::AND {
-            let operands = Vec::from_iter(itertools::chain(
-                self.operands.into_iter(),
-                rhs.operands.into_iter(),
-            ));
-            return QueryExpression {
+            let operands = Vec::from_iter(itertools::chain(self.operands, rhs.operands));
+            QueryExpression {
                 operator: Operator::AND,
                 operands,
-            };
+            }
         } else {
-            return QueryExpression {
+            QueryExpression {
                 operator: Operator::AND,
                 operands: vec![
                     QueryExpressionKind::Expression(self),
                     QueryExpressionKind::Expression(rhs),
                 ],
-            };
+            }
         }
     }
 }
@@ -450,7 +444,7 @@ impl BoostQueryOperand {
         Self {
             field: String::from(field),
             word: String::from(word),
-            boost: boost,
+            boost,
         }
     }
 }
@@ -483,7 +477,7 @@ impl FuzzyQueryOperand {
         Self {
             field: String::from(field),
             word: String::from(word),
-            fuzzy: fuzzy,
+            fuzzy,
         }
     }
 }
@@ -516,7 +510,7 @@ impl ProximityQueryOperand {
         Self {
             field: String::from(field),
             word: String::from(word),
-            proximity: proximity,
+            proximity,
         }
     }
 }
@@ -549,7 +543,7 @@ impl ConstantQueryOperand {
         Self {
             field: String::from(field),
             word: String::from(word),
-            weight: weight,
+            weight,
         }
     }
 }
@@ -568,6 +562,133 @@ impl From<ConstantQueryOperand> for QueryOperand {
     }
 }
 
+/// Struct to building the query of [Switch Query Parser](https://solr.apache.org/guide/solr/latest/query-guide/other-parsers.html#switch-query-parser),
+/// which selects the query by the value, e.g. `{!switch case.yes=in_stock:true case.no=in_stock:false default=*:* v=$in_stock}`.
+///
+/// It is mostly used in the defaults of the request handlers, to toggle the filters by the request parameters.
+pub struct SwitchQueryOperand {
+    value: String,
+    cases: Vec<(Option<String>, String)>,
+    default: Option<String>,
+}
+
+impl SolrQueryOperandModel for SwitchQueryOperand {}
+
+impl SwitchQueryOperand {
+    /// Create the switch on the value, which is usually a parameter reference such as `$in_stock`.
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: String::from(value),
+            cases: Vec::new(),
+            default: None,
+        }
+    }
+
+    /// Add `case.<KEY>` local parameter, the query used when the value is the key.
+    pub fn case(mut self, key: &str, query: &impl SolrQueryExpression) -> Self {
+        self.cases
+            .push((Some(String::from(key)), query.to_string()));
+        self
+    }
+
+    /// Add `case` local parameter, the query used when the value is blank.
+    pub fn blank_case(mut self, query: &impl SolrQueryExpression) -> Self {
+        self.cases.push((None, query.to_string()));
+        self
+    }
+
+    /// Add `default` local parameter, the query used when the value matches no case.
+    pub fn default(mut self, query: &impl SolrQueryExpression) -> Self {
+        self.default = Some(query.to_string());
+        self
+    }
+}
+
+impl Display for SwitchQueryOperand {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{{!switch")?;
+        for (key, query) in self.cases.iter() {
+            match key {
+                Some(key) => write!(f, " case.{}={}", key, local_param_value(query))?,
+                None => write!(f, " case={}", local_param_value(query))?,
+            }
+        }
+        if let Some(default) = &self.default {
+            write!(f, " default={}", local_param_value(default))?;
+        }
+        write!(f, " v={}}}", local_param_value(&self.value))?;
+        Ok(())
+    }
+}
+
+impl From<SwitchQueryOperand> for QueryOperand {
+    fn from(op: SwitchQueryOperand) -> QueryOperand {
+        QueryOperand(op.to_string())
+    }
+}
+
+/// Struct to building the query of [Terms Query Parser](https://solr.apache.org/guide/solr/latest/query-guide/other-parsers.html#terms-query-parser),
+/// which matches any of the values of the field, e.g. `{!terms f=id}001,002,003`.
+///
+/// It is far more efficient than the disjunction of many operands, such as the filter by thousands of IDs.
+/// The values are not escaped, so they must not contain the separator.
+pub struct TermsListQueryOperand {
+    field: String,
+    values: Vec<String>,
+    separator: Option<String>,
+}
+
+impl SolrQueryOperandModel for TermsListQueryOperand {}
+
+impl TermsListQueryOperand {
+    pub fn new(field: &str, values: &[impl ToString]) -> Self {
+        Self {
+            field: String::from(field),
+            values: values.iter().map(|value| value.to_string()).collect(),
+            separator: None,
+        }
+    }
+
+    /// Add `separator` local parameter, used instead of the comma for the values containing commas.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = Some(String::from(separator));
+        self
+    }
+}
+
+impl Display for TermsListQueryOperand {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{{!terms f={}", local_param_value(&self.field))?;
+        let separator = match &self.separator {
+            Some(separator) => {
+                write!(f, " separator={}", local_param_value(separator))?;
+                separator.as_str()
+            }
+            None => ",",
+        };
+        write!(f, "}}{}", self.values.join(separator))?;
+        Ok(())
+    }
+}
+
+impl From<TermsListQueryOperand> for QueryOperand {
+    fn from(op: TermsListQueryOperand) -> QueryOperand {
+        QueryOperand(op.to_string())
+    }
+}
+
+/// Quote the value of the local parameter if it contains the characters that end the value, such as spaces.
+pub(crate) fn local_param_value(value: &str) -> String {
+    if !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '\'' | '"' | '}' | '\\'))
+    {
+        return String::from(value);
+    }
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -620,6 +741,56 @@ mod test {
         assert_eq!(String::from(r#"name:"alice""#), q.to_string());
     }
 
+    #[test]
+    fn test_switch_query_operand() {
+        let q = SwitchQueryOperand::new("$in_stock")
+            .case("yes", &QueryOperand::from("in_stock:true"))
+            .case("no", &QueryOperand::from("in_stock:false"))
+            .blank_case(&QueryOperand::from("*:*"))
+            .default(&QueryOperand::from("in_stock:true AND price:[* TO 100]"));
+
+        assert_eq!(
+            String::from(
+                r#"{!switch case.yes=in_stock:true case.no=in_stock:false case=*:* default='in_stock:true AND price:[* TO 100]' v=$in_stock}"#
+            ),
+            q.to_string()
+        );
+    }
+
+    #[test]
+    fn test_switch_query_operand_with_quoted_value() {
+        let q = SwitchQueryOperand::new("it's").case("x", &QueryOperand::from(r"a\b"));
+
+        assert_eq!(
+            String::from(r"{!switch case.x='a\\b' v='it\'s'}"),
+            q.to_string()
+        );
+    }
+
+    #[test]
+    fn test_terms_list_query_operand() {
+        let q = TermsListQueryOperand::new("id", &["001", "002", "003"]);
+        assert_eq!(String::from("{!terms f=id}001,002,003"), q.to_string());
+
+        let q = TermsListQueryOperand::new("price", &[100, 200]);
+        assert_eq!(String::from("{!terms f=price}100,200"), q.to_string());
+    }
+
+    #[test]
+    fn test_terms_list_query_operand_with_separator() {
+        let q = TermsListQueryOperand::new("tag", &["a", "b"]).separator(" ");
+        assert_eq!(
+            String::from("{!terms f=tag separator=' '}a b"),
+            q.to_string()
+        );
+
+        let q = TermsListQueryOperand::new("name", &["Doe, John", "Roe, Jane"]).separator("|");
+        assert_eq!(
+            String::from("{!terms f=name separator=|}Doe, John|Roe, Jane"),
+            q.to_string()
+        );
+    }
+
     #[test]
     fn test_range_query_with_default_parameter() {
         let q = RangeQueryOperand::new("age");
@@ -1,26 +1,96 @@
 //! This module defines builder struct that build the value for `sort` parameter.
 
+use crate::querybuilder::function::{FunctionQuery, FunctionQueryError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SortError {
+    #[error("Sort key `{0}` is added twice")]
+    DuplicateKeyError(String),
+    #[error("Sort field `{0}` is empty or contains whitespace")]
+    InvalidFieldError(String),
+    #[error(transparent)]
+    FunctionQueryError(#[from] FunctionQueryError),
+}
+
 pub struct SortOrderBuilder {
     order: Vec<String>,
+    /// Sort keys already added, to detect the duplicate keys.
+    keys: Vec<String>,
+}
+
+impl Default for SortOrderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SortOrderBuilder {
     pub fn new() -> Self {
-        Self { order: Vec::new() }
+        Self {
+            order: Vec::new(),
+            keys: Vec::new(),
+        }
     }
 
     pub fn build(&self) -> String {
         self.order.join(",")
     }
 
-    pub fn asc(mut self, field: &str) -> Self {
-        self.order.push(format!("{} asc", field));
-        self
+    fn push(mut self, key: String, direction: &str) -> Result<Self, SortError> {
+        if self.keys.contains(&key) {
+            return Err(SortError::DuplicateKeyError(key));
+        }
+        self.order.push(format!("{} {}", key, direction));
+        self.keys.push(key);
+        Ok(self)
+    }
+
+    fn push_field(self, field: &str, direction: &str) -> Result<Self, SortError> {
+        if field.is_empty() || field.contains(char::is_whitespace) {
+            return Err(SortError::InvalidFieldError(String::from(field)));
+        }
+        self.push(String::from(field), direction)
+    }
+
+    /// Sort by the field in ascending order.
+    ///
+    /// Return an error if the field is empty, contains whitespace or is already added.
+    pub fn asc(self, field: &str) -> Result<Self, SortError> {
+        self.push_field(field, "asc")
+    }
+
+    /// Sort by the field in descending order.
+    pub fn desc(self, field: &str) -> Result<Self, SortError> {
+        self.push_field(field, "desc")
+    }
+
+    /// Sort by the [function query](https://solr.apache.org/guide/solr/latest/query-guide/function-queries.html)
+    /// in ascending order, e.g. `div(popularity,price)`.
+    pub fn asc_by_function(self, function: impl Into<FunctionQuery>) -> Result<Self, SortError> {
+        self.push(function.into().to_string(), "asc")
+    }
+
+    /// Sort by the function query in descending order.
+    pub fn desc_by_function(self, function: impl Into<FunctionQuery>) -> Result<Self, SortError> {
+        self.push(function.into().to_string(), "desc")
     }
 
-    pub fn desc(mut self, field: &str) -> Self {
-        self.order.push(format!("{} desc", field));
-        self
+    /// Sort by the distance from the point to the location of the field in ascending order, that is, the nearest first.
+    pub fn asc_by_geodist(self, field: &str, lat: f64, lon: f64) -> Result<Self, SortError> {
+        self.asc_by_function(FunctionQuery::geodist(field, lat, lon)?)
+    }
+
+    /// Sort by the distance from the point to the location of the field in descending order.
+    pub fn desc_by_geodist(self, field: &str, lat: f64, lon: f64) -> Result<Self, SortError> {
+        self.desc_by_function(FunctionQuery::geodist(field, lat, lon)?)
+    }
+
+    /// Sort randomly with the seed, which requires the dynamic field `random_*` of `RandomSortField` in the schema.
+    ///
+    /// The same seed gives the same order.
+    pub fn random(self, seed: u64) -> Result<Self, SortError> {
+        self.push(format!("random_{}", seed), "asc")
     }
 }
 
@@ -30,8 +100,52 @@ mod test {
 
     #[test]
     fn test_build_sort_order() {
-        let sort = SortOrderBuilder::new().desc("score").asc("name").build();
+        let sort = SortOrderBuilder::new()
+            .desc("score")
+            .unwrap()
+            .asc("name")
+            .unwrap()
+            .build();
 
         assert_eq!(String::from("score desc,name asc"), sort);
     }
+
+    #[test]
+    fn test_build_sort_order_with_functions() {
+        let sort = SortOrderBuilder::new()
+            .desc_by_function("div(popularity,price)")
+            .unwrap()
+            .asc_by_geodist("location", 35.68, 139.76)
+            .unwrap()
+            .random(1234)
+            .unwrap()
+            .build();
+
+        assert_eq!(
+            sort,
+            "div(popularity,price) desc,geodist(location,35.68,139.76) asc,random_1234 asc"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_sort_field() {
+        let result = SortOrderBuilder::new().asc("name").unwrap().desc("name");
+
+        assert!(matches!(
+            result,
+            Err(SortError::DuplicateKeyError(key)) if key == "name"
+        ));
+    }
+
+    #[test]
+    fn test_sort_field_with_whitespace() {
+        assert!(matches!(
+            SortOrderBuilder::new().asc("name desc"),
+            Err(SortError::InvalidFieldError(field)) if field == "name desc"
+        ));
+        assert!(matches!(
+            SortOrderBuilder::new().desc(""),
+            Err(SortError::InvalidFieldError(field)) if field.is_empty()
+        ));
+    }
 }
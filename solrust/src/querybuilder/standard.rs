@@ -1,13 +1,19 @@
 //! This module provides definition and implementation of Solr Standard Query Parser.
 
-use crate::querybuilder::common::SolrCommonQueryBuilder;
+use crate::querybuilder::common::{EchoParams, FqOptions, ResponseFormat, SolrCommonQueryBuilder};
 use crate::querybuilder::facet::FacetBuilder;
+use crate::querybuilder::filter::SolrFilters;
+use crate::querybuilder::fl::FlBuilder;
+use crate::querybuilder::highlight::HighlightBuilder;
+use crate::querybuilder::params::SolrQueryParams;
 use crate::querybuilder::q::{Operator, SolrQueryExpression};
 use crate::querybuilder::sanitizer::SOLR_SPECIAL_CHARACTERS;
 use crate::querybuilder::sort::SortOrderBuilder;
+use crate::types::document::SolrDocument;
+use serde::{Deserialize, Serialize};
 use solrust_derive::{SolrCommonQueryParser, SolrStandardQueryParser};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 /// The trait of builder that generates parameter for [Solr Standard Query Parser](https://solr.apache.org/guide/solr/latest/query-guide/standard-query-parser.html).
@@ -21,17 +27,23 @@ pub trait SolrStandardQueryBuilder: SolrCommonQueryBuilder {
 }
 
 /// Implementation of Solr Standard Query Parser.
-#[derive(SolrCommonQueryParser, SolrStandardQueryParser)]
+#[derive(Clone, Debug, Serialize, SolrCommonQueryParser, SolrStandardQueryParser)]
 pub struct StandardQueryBuilder {
-    params: HashMap<String, String>,
-    multi_params: HashMap<String, Vec<String>>,
+    params: BTreeMap<String, String>,
+    multi_params: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for StandardQueryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StandardQueryBuilder {
     pub fn new() -> Self {
         Self {
-            params: HashMap::new(),
-            multi_params: HashMap::new(),
+            params: BTreeMap::new(),
+            multi_params: BTreeMap::new(),
         }
     }
 }
@@ -39,7 +51,9 @@ impl StandardQueryBuilder {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::querybuilder::facet::{FieldFacetBuilder, RangeFacetBuilder};
+    use crate::querybuilder::facet::{
+        FieldFacetBuilder, PivotFacetBuilder, RangeFacetBuilder, StatsFieldBuilder,
+    };
     use crate::querybuilder::q::{QueryOperand, StandardQueryOperand};
 
     #[test]
@@ -56,14 +70,13 @@ mod test {
     #[test]
     fn test_sample_query() {
         let q = QueryOperand::from(StandardQueryOperand::new("text_ja", "高橋?"));
-        let sort = SortOrderBuilder::new().desc("score").desc("difficulty");
+        let sort = SortOrderBuilder::new()
+            .desc("score")
+            .unwrap()
+            .desc("difficulty")
+            .unwrap();
         let facet1 = FieldFacetBuilder::new("category");
-        let facet2 = RangeFacetBuilder::new(
-            "difficulty",
-            0.to_string(),
-            2000.to_string(),
-            400.to_string(),
-        );
+        let facet2 = RangeFacetBuilder::new("difficulty", 0, 2000, 400);
         let builder = StandardQueryBuilder::new()
             .q(&q)
             .op(Operator::AND)
@@ -101,4 +114,36 @@ mod test {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_pivot_facet_with_stats_and_ranges() {
+        let builder = StandardQueryBuilder::new()
+            .facet(&StatsFieldBuilder::new("price").tag("s1"))
+            .facet_with_local_params(
+                &RangeFacetBuilder::new("price", 0, 1000, 500),
+                &[("tag", "r1")],
+            )
+            .facet(
+                &PivotFacetBuilder::new(&["category", "in_stock"])
+                    .stats("s1")
+                    .range("r1"),
+            )
+            .facet_with_local_params(
+                &PivotFacetBuilder::new(&["manu"]).stats("s1"),
+                &[("ex", "m")],
+            );
+
+        let actual = builder.build();
+        assert!(actual.contains(&("stats".to_string(), "true".to_string())));
+        assert!(actual.contains(&("stats.field".to_string(), "{!tag=s1}price".to_string())));
+        assert!(actual.contains(&("facet.range".to_string(), "{!tag=r1}price".to_string())));
+        assert!(actual.contains(&(
+            "facet.pivot".to_string(),
+            "{!stats=s1 range=r1}category,in_stock".to_string()
+        )));
+        assert!(actual.contains(&(
+            "facet.pivot".to_string(),
+            "{!ex=m stats=s1}manu".to_string()
+        )));
+    }
 }
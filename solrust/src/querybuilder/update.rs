@@ -0,0 +1,193 @@
+//! This module defines the builder of the update request that combines multiple commands in one body,
+//! in the [JSON update command format](https://solr.apache.org/guide/solr/latest/indexing-guide/indexing-with-update-handlers.html#sending-json-update-commands).
+
+use crate::querybuilder::q::SolrQueryExpression;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Options of the `add` command.
+///
+/// The options not set are omitted, and the defaults of Solr are used.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AddOptions {
+    /// Index-time boost of the document. It is not supported since Solr 7.
+    pub boost: Option<f64>,
+    /// Whether to replace the document with the same unique key.
+    pub overwrite: Option<bool>,
+    /// Milliseconds within which the document is committed.
+    pub commit_within: Option<u32>,
+}
+
+/// Builder of the update request with the commands executed in order, e.g.
+///
+/// ```ignore
+/// let request = UpdateRequestBuilder::new()
+///     .add_document(&document)?
+///     .delete_by_id("002")
+///     .delete_by_query(&QueryOperand::from("category:obsolete"))
+///     .commit();
+/// core.update(&request).await?;
+/// ```
+///
+/// The body is the JSON object with the same keys repeated, e.g. `{"add":{"doc":{..}},"delete":{"id":"002"},"commit":{}}`,
+/// which Solr accepts as the commands.
+///
+/// The parameters of the request, such as `update.chain`, are sent in the query string.
+#[derive(Clone, Debug, Default)]
+pub struct UpdateRequestBuilder {
+    commands: Vec<(&'static str, Value)>,
+    params: Vec<(String, String)>,
+}
+
+impl UpdateRequestBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the document.
+    pub fn add_document(self, doc: &impl Serialize) -> Result<Self, serde_json::Error> {
+        self.add_document_with(doc, AddOptions::default())
+    }
+
+    /// Add the document with the options.
+    pub fn add_document_with(
+        mut self,
+        doc: &impl Serialize,
+        options: AddOptions,
+    ) -> Result<Self, serde_json::Error> {
+        let mut command = Map::new();
+        command.insert(String::from("doc"), serde_json::to_value(doc)?);
+        if let Some(boost) = options.boost {
+            command.insert(String::from("boost"), json!(boost));
+        }
+        if let Some(overwrite) = options.overwrite {
+            command.insert(String::from("overwrite"), json!(overwrite));
+        }
+        if let Some(commit_within) = options.commit_within {
+            command.insert(String::from("commitWithin"), json!(commit_within));
+        }
+
+        self.commands.push(("add", Value::Object(command)));
+        Ok(self)
+    }
+
+    /// Delete the document by the unique key.
+    pub fn delete_by_id(mut self, id: &str) -> Self {
+        self.commands.push(("delete", json!({ "id": id })));
+        self
+    }
+
+    /// Delete the documents matching the query.
+    pub fn delete_by_query(mut self, query: &impl SolrQueryExpression) -> Self {
+        self.commands
+            .push(("delete", json!({ "query": query.to_string() })));
+        self
+    }
+
+    /// Commit the changes.
+    pub fn commit(mut self) -> Self {
+        self.commands.push(("commit", json!({})));
+        self
+    }
+
+    /// Commit the changes with optimization.
+    pub fn optimize(mut self) -> Self {
+        self.commands.push(("optimize", json!({})));
+        self
+    }
+
+    /// Process the documents with the update request processor chain, e.g. for language detection or deduplication.
+    pub fn chain(self, name: &str) -> Self {
+        self.param("update.chain", name)
+    }
+
+    /// Add the parameter of the update request.
+    pub fn param(mut self, key: &str, value: &str) -> Self {
+        self.params.push((String::from(key), String::from(value)));
+        self
+    }
+
+    pub fn params(&self) -> &Vec<(String, String)> {
+        &self.params
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Render the commands as the body of the request.
+    pub fn to_json(&self) -> String {
+        let commands: Vec<String> = self
+            .commands
+            .iter()
+            .map(|(command, value)| format!(r#""{}":{}"#, command, value))
+            .collect();
+        format!("{{{}}}", commands.join(","))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::querybuilder::q::QueryOperand;
+
+    #[derive(Serialize)]
+    struct Document {
+        id: String,
+        name: String,
+    }
+
+    #[test]
+    fn test_mixed_commands() {
+        let request = UpdateRequestBuilder::new()
+            .add_document(&Document {
+                id: String::from("001"),
+                name: String::from("alice"),
+            })
+            .unwrap()
+            .add_document_with(
+                &Document {
+                    id: String::from("002"),
+                    name: String::from("bob"),
+                },
+                AddOptions {
+                    overwrite: Some(false),
+                    commit_within: Some(1000),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .delete_by_id("003")
+            .delete_by_query(&QueryOperand::from("name:carol"))
+            .commit();
+
+        assert_eq!(
+            request.to_json(),
+            r#"{"add":{"doc":{"id":"001","name":"alice"}},"add":{"commitWithin":1000,"doc":{"id":"002","name":"bob"},"overwrite":false},"delete":{"id":"003"},"delete":{"query":"name:carol"},"commit":{}}"#
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let request = UpdateRequestBuilder::new();
+
+        assert!(request.is_empty());
+        assert_eq!(request.to_json(), "{}");
+    }
+
+    #[test]
+    fn test_params() {
+        let request = UpdateRequestBuilder::new()
+            .chain("dedupe")
+            .param("commitWithin", "1000");
+
+        assert_eq!(
+            request.params(),
+            &vec![
+                (String::from("update.chain"), String::from("dedupe")),
+                (String::from("commitWithin"), String::from("1000")),
+            ]
+        );
+        assert!(request.is_empty());
+    }
+}
@@ -0,0 +1,482 @@
+//! This module defines the in-memory fake of the Solr core for testing the application without Solr.
+
+use crate::client::core::{SolrCoreApi, SolrCoreError};
+use crate::types::response::*;
+use async_trait::async_trait;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+type Result<T> = std::result::Result<T, SolrCoreError>;
+
+/// Update operation waiting for the commit.
+#[derive(Debug)]
+enum Operation {
+    Add(Value),
+    DeleteById(String),
+    DeleteByQuery(String),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    documents: Vec<Value>,
+    pending: Vec<Operation>,
+}
+
+/// In-memory fake of the Solr core implementing [`SolrCoreApi`].
+///
+/// The fake supports the subset of Solr enough to test the application logic offline:
+///
+/// - Posting the documents, and the `add`, `delete` (by `id` or by query), `commit` and `rollback` commands in JSON.
+///   The posted documents are searchable after the commit, and the documents with the same `id` are replaced.
+/// - `q` and `fq` parameters of the form `*:*`, `field:value` and `field:*`.
+/// - `start`, `rows` and `sort` parameters, where `sort` is a single field.
+/// - `facet.field` parameter with `facet=true`.
+///
+/// ```ignore
+/// let core: Arc<dyn SolrCoreApi> = Arc::new(FakeSolrCore::new("example").with_documents(vec![json!({"id": "1"})]));
+/// ```
+#[derive(Debug)]
+pub struct FakeSolrCore {
+    name: String,
+    state: Mutex<State>,
+}
+
+impl FakeSolrCore {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Add the documents, which are searchable without the commit.
+    pub fn with_documents(self, documents: Vec<Value>) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            for document in documents {
+                add_document(&mut state.documents, document);
+            }
+        }
+        self
+    }
+
+    /// Return the committed documents.
+    pub fn documents(&self) -> Vec<Value> {
+        self.state.lock().unwrap().documents.clone()
+    }
+
+    fn search(&self, params: &[(String, String)]) -> Result<SolrSelectResponse<Value>> {
+        let param = |key: &str| {
+            params
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        let state = self.state.lock().unwrap();
+
+        let q = param("q").unwrap_or("*:*");
+        let filters: Vec<&str> = params
+            .iter()
+            .filter(|(k, _)| k == "fq")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        let mut hits: Vec<&Value> = state
+            .documents
+            .iter()
+            .filter(|document| {
+                matches(document, q) && filters.iter().all(|fq| matches(document, fq))
+            })
+            .collect();
+
+        if let Some((field, order)) = param("sort").and_then(|sort| sort.split_once(' ')) {
+            hits.sort_by(|a, b| compare(&a[field], &b[field]));
+            if order.trim() == "desc" {
+                hits.reverse();
+            }
+        }
+
+        let start = param("start").and_then(|v| v.parse().ok()).unwrap_or(0);
+        let rows = param("rows").and_then(|v| v.parse().ok()).unwrap_or(10);
+        let docs: Vec<&Value> = hits.iter().skip(start).take(rows).copied().collect();
+
+        let mut response = json!({
+            "responseHeader": {"status": 0, "QTime": 0},
+            "response": {"numFound": hits.len(), "start": start, "numFoundExact": true, "docs": docs},
+        });
+
+        if param("facet") == Some("true") {
+            let mut facet_fields = Map::new();
+            for (_, field) in params.iter().filter(|(k, _)| k == "facet.field") {
+                facet_fields.insert(field.clone(), Value::Array(count_facet(&hits, field)));
+            }
+            response["facet_counts"] = json!({
+                "facet_queries": {},
+                "facet_fields": facet_fields,
+                "facet_ranges": {},
+                "facet_intervals": {},
+                "facet_heatmaps": {},
+            });
+        }
+
+        Ok(serde_json::from_value(response)?)
+    }
+
+    fn update(&self, body: &[u8]) -> Result<()> {
+        let body: UpdateBody = serde_json::from_slice(body)?;
+        let mut state = self.state.lock().unwrap();
+
+        let commands = match body {
+            UpdateBody::Documents(documents) => {
+                for document in documents {
+                    state.pending.push(Operation::Add(document));
+                }
+                return Ok(());
+            }
+            UpdateBody::Commands(commands) => commands,
+        };
+
+        for (command, value) in commands {
+            match command.as_str() {
+                "add" => match value.get("doc") {
+                    Some(document) => state.pending.push(Operation::Add(document.clone())),
+                    None => state.pending.push(Operation::Add(value)),
+                },
+                "delete" => match value {
+                    Value::Object(delete) => {
+                        if let Some(id) = delete.get("id") {
+                            state
+                                .pending
+                                .push(Operation::DeleteById(value_to_string(id)));
+                        }
+                        if let Some(Value::String(query)) = delete.get("query") {
+                            state.pending.push(Operation::DeleteByQuery(query.clone()));
+                        }
+                    }
+                    Value::Array(ids) => {
+                        for id in ids {
+                            state
+                                .pending
+                                .push(Operation::DeleteById(value_to_string(&id)));
+                        }
+                    }
+                    id => state
+                        .pending
+                        .push(Operation::DeleteById(value_to_string(&id))),
+                },
+                "commit" | "optimize" => {
+                    let State { documents, pending } = &mut *state;
+                    for operation in pending.drain(..) {
+                        match operation {
+                            Operation::Add(document) => add_document(documents, document),
+                            Operation::DeleteById(id) => {
+                                documents.retain(|document| value_to_string(&document["id"]) != id)
+                            }
+                            Operation::DeleteByQuery(query) => {
+                                documents.retain(|document| !matches(document, &query))
+                            }
+                        }
+                    }
+                }
+                "rollback" => state.pending.clear(),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Body of the update request, which is the array of the documents or the object of the commands.
+///
+/// The commands are kept in order with the repeated keys, e.g. `{"add":{..},"add":{..},"commit":{}}`,
+/// which are lost when the body is parsed into [`Value`].
+enum UpdateBody {
+    Documents(Vec<Value>),
+    Commands(Vec<(String, Value)>),
+}
+
+impl<'de> Deserialize<'de> for UpdateBody {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct UpdateBodyVisitor;
+
+        impl<'de> Visitor<'de> for UpdateBodyVisitor {
+            type Value = UpdateBody;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an array of documents or an object of update commands")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut documents = Vec::new();
+                while let Some(document) = seq.next_element()? {
+                    documents.push(document);
+                }
+                Ok(UpdateBody::Documents(documents))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut commands = Vec::new();
+                while let Some(command) = map.next_entry()? {
+                    commands.push(command);
+                }
+                Ok(UpdateBody::Commands(commands))
+            }
+        }
+
+        deserializer.deserialize_any(UpdateBodyVisitor)
+    }
+}
+
+/// Add the document, replacing the document with the same `id`.
+fn add_document(documents: &mut Vec<Value>, document: Value) {
+    let id = document.get("id").map(value_to_string);
+    match id.and_then(|id| {
+        documents
+            .iter()
+            .position(|d| d.get("id").map(value_to_string) == Some(id.clone()))
+    }) {
+        Some(position) => documents[position] = document,
+        None => documents.push(document),
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Return true if the document matches the query of the form `*:*`, `field:value` or `field:*`.
+fn matches(document: &Value, query: &str) -> bool {
+    let query = query.trim();
+    if query.is_empty() || query == "*:*" {
+        return true;
+    }
+
+    let (field, expected) = match query.split_once(':') {
+        Some((field, value)) => (Some(field), value.trim_matches('"')),
+        None => (None, query.trim_matches('"')),
+    };
+    let field_matches = |value: &Value| match value {
+        Value::Array(values) => values
+            .iter()
+            .any(|value| expected == "*" || value_to_string(value) == expected),
+        Value::Null => false,
+        value => expected == "*" || value_to_string(value) == expected,
+    };
+
+    match field {
+        Some(field) => document.get(field).map(field_matches).unwrap_or(false),
+        None => document
+            .as_object()
+            .map(|fields| fields.values().any(field_matches))
+            .unwrap_or(false),
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => value_to_string(a).cmp(&value_to_string(b)),
+    }
+}
+
+/// Count the values of the field in the documents, in the same format as `facet_fields` of Solr.
+fn count_facet(documents: &[&Value], field: &str) -> Vec<Value> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    for document in documents {
+        match document.get(field) {
+            Some(Value::Array(values)) => {
+                for value in values {
+                    *counts.entry(value_to_string(value)).or_default() += 1;
+                }
+            }
+            Some(Value::Null) | None => {}
+            Some(value) => *counts.entry(value_to_string(value)).or_default() += 1,
+        }
+    }
+
+    let mut counts: Vec<(String, u64)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+        .into_iter()
+        .flat_map(|(value, count)| vec![Value::from(value), Value::from(count)])
+        .collect()
+}
+
+#[async_trait]
+impl SolrCoreApi for FakeSolrCore {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn ping(&self) -> Result<SolrPingResponse> {
+        Ok(serde_json::from_value(json!({
+            "responseHeader": {"status": 0, "QTime": 0},
+            "status": "OK",
+        }))?)
+    }
+
+    async fn status(&self) -> Result<SolrCoreStatus> {
+        let num_docs = self.state.lock().unwrap().documents.len();
+        Ok(serde_json::from_value(json!({
+            "name": self.name,
+            "instanceDir": "",
+            "dataDir": "",
+            "config": "solrconfig.xml",
+            "schema": "managed-schema.xml",
+            "startTime": "1970-01-01T00:00:00Z",
+            "uptime": 0,
+            "index": {
+                "numDocs": num_docs,
+                "maxDoc": num_docs,
+                "deletedDocs": 0,
+                "version": 0,
+                "segmentCount": 0,
+                "current": true,
+                "hasDeletions": false,
+                "directory": "",
+                "segmentsFile": "",
+                "segmentsFileSizeInBytes": 0,
+                "userData": {},
+                "sizeInBytes": 0,
+                "size": "0 bytes",
+            },
+        }))?)
+    }
+
+    async fn reload(&self) -> Result<u32> {
+        Ok(0)
+    }
+
+    async fn select(&self, params: &[(String, String)]) -> Result<SolrSelectResponse<Value>> {
+        self.search(params)
+    }
+
+    async fn select_with_handler(
+        &self,
+        _handler: &str,
+        params: &[(String, String)],
+    ) -> Result<SolrSelectResponse<Value>> {
+        self.search(params)
+    }
+
+    async fn post(&self, body: Vec<u8>) -> Result<SolrSimpleResponse> {
+        self.update(&body)?;
+
+        Ok(serde_json::from_value(json!({
+            "responseHeader": {"status": 0, "QTime": 0},
+        }))?)
+    }
+
+    async fn commit(&self, optimize: bool) -> Result<SolrSimpleResponse> {
+        let command = if optimize { "optimize" } else { "commit" };
+        self.post(format!(r#"{{"{}": {{}}}}"#, command).into_bytes())
+            .await
+    }
+
+    async fn rollback(&self) -> Result<()> {
+        self.update(br#"{"rollback": {}}"#)
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        self.update(br#"{"delete": {"query": "*:*"}}"#)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::querybuilder::update::UpdateRequestBuilder;
+
+    fn params(params: &[(&str, &str)]) -> Vec<(String, String)> {
+        params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_post_and_commit() {
+        let core = FakeSolrCore::new("example");
+
+        core.post(br#"[{"id": "1", "name": "foo"}, {"id": "2", "name": "bar"}]"#.to_vec())
+            .await
+            .unwrap();
+        let response = core.select(&params(&[("q", "*:*")])).await.unwrap();
+        assert_eq!(response.response.num_found, 0);
+
+        core.commit(false).await.unwrap();
+        let response = core.select(&params(&[("q", "*:*")])).await.unwrap();
+        assert_eq!(response.response.num_found, 2);
+
+        core.post(br#"{"delete": {"id": "1"}}"#.to_vec())
+            .await
+            .unwrap();
+        core.rollback().await.unwrap();
+        core.commit(false).await.unwrap();
+        assert_eq!(core.documents().len(), 2);
+
+        core.truncate().await.unwrap();
+        core.commit(false).await.unwrap();
+        assert!(core.documents().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_commands_in_order() {
+        let core = FakeSolrCore::new("example").with_documents(vec![json!({"id": "1"})]);
+
+        let request = UpdateRequestBuilder::new()
+            .add_document(&json!({"id": "2"}))
+            .unwrap()
+            .add_document(&json!({"id": "3"}))
+            .unwrap()
+            .delete_by_id("1")
+            .commit()
+            .delete_by_id("2");
+        core.post(request.to_json().into_bytes()).await.unwrap();
+
+        let ids: Vec<Value> = core.documents().iter().map(|d| d["id"].clone()).collect();
+        assert_eq!(ids, vec![json!("2"), json!("3")]);
+    }
+
+    #[tokio::test]
+    async fn test_select_with_filters_and_facets() {
+        let core = FakeSolrCore::new("example").with_documents(vec![
+            json!({"id": "1", "category": "book", "tags": ["a", "b"], "price": 300}),
+            json!({"id": "2", "category": "book", "tags": ["b"], "price": 100}),
+            json!({"id": "3", "category": "music", "tags": ["a"], "price": 200}),
+        ]);
+
+        let response = core
+            .select(&params(&[
+                ("q", "category:book"),
+                ("fq", "tags:b"),
+                ("sort", "price asc"),
+                ("facet", "true"),
+                ("facet.field", "tags"),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.response.num_found, 2);
+        assert_eq!(response.response.docs[0]["id"], "2");
+        assert_eq!(
+            response.facet_counts.unwrap().facet_fields["tags"],
+            vec![(String::from("b"), 2), (String::from("a"), 1)]
+        );
+    }
+}
@@ -1,2 +1,8 @@
+pub mod binary;
 pub mod datetime;
+pub mod document;
+pub mod facet;
+pub mod lenient;
 pub mod response;
+#[cfg(feature = "xml")]
+pub mod xml;
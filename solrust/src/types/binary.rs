@@ -0,0 +1,90 @@
+//! This module defines a custom struct to serialize `Vec<u8>` to the base64 format of Solr's `BinaryField`
+//! / deserialize the base64 string returned by Solr to `Vec<u8>`.
+//!
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+use serde_with::{DeserializeAs, SerializeAs};
+
+pub struct SolrBinary;
+
+/// Implementation for serialize Vec<u8> to the base64 string with padding.
+impl SerializeAs<Vec<u8>> for SolrBinary {
+    fn serialize_as<S>(source: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&STANDARD.encode(source))
+    }
+}
+
+/// Implementation to deserialize the base64 string to Vec<u8>.
+/// Solr may wrap the encoded value in lines, so the whitespaces are ignored.
+impl<'de> DeserializeAs<'de, Vec<u8>> for SolrBinary {
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let value: String = value.split_whitespace().collect();
+        STANDARD
+            .decode(value)
+            .map_err(|e| serde::de::Error::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Document {
+        id: String,
+        #[serde_as(as = "SolrBinary")]
+        payload: Vec<u8>,
+        #[serde_as(as = "Option<SolrBinary>")]
+        #[serde(default)]
+        thumbnail: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_serialize_binary() {
+        let document = Document {
+            id: String::from("001"),
+            payload: vec![0, 1, 2, 253, 254, 255],
+            thumbnail: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&document).unwrap(),
+            r#"{"id":"001","payload":"AAEC/f7/","thumbnail":null}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_binary() {
+        let document: Document = serde_json::from_str(
+            r#"{"id": "001", "payload": "AAEC/f7/", "thumbnail": "aGVs\nbG8="}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            document,
+            Document {
+                id: String::from("001"),
+                payload: vec![0, 1, 2, 253, 254, 255],
+                thumbnail: Some(b"hello".to_vec()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_binary() {
+        let result = serde_json::from_str::<Document>(r#"{"id": "001", "payload": "not base64!"}"#);
+
+        assert!(result.is_err());
+    }
+}
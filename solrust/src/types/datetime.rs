@@ -100,7 +100,7 @@ impl<'de> DeserializeAs<'de, DateTime<Local>> for SolrDateTime {
 #[cfg(test)]
 mod test {
     use super::*;
-    use chrono::offset::TimeZone;
+    use chrono::NaiveDateTime;
     use serde::{Deserialize, Serialize};
     use serde_with::serde_as;
 
@@ -210,9 +210,9 @@ mod test {
     #[test]
     fn test_serialize_utc_datetime() {
         let doc = DocumentWithUtcDateTimeOffset {
-            start_at: Utc
-                .datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
-                .unwrap(),
+            start_at: NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                .unwrap()
+                .and_utc(),
         };
         let json = serde_json::to_string(&doc).unwrap();
         assert_eq!(json, r#"{"start_at":"2022-10-01T12:30:15Z"}"#)
@@ -224,8 +224,9 @@ mod test {
         let doc: DocumentWithUtcDateTimeOffset = serde_json::from_str(raw).unwrap();
         assert_eq!(
             doc.start_at,
-            Utc.datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+            NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
                 .unwrap()
+                .and_utc()
         );
     }
     // ============================================================================
@@ -243,8 +244,9 @@ mod test {
     fn test_serialize_optional_utc_datetime() {
         let doc = DocumentWithOptionalUtcDateTimeOffset {
             start_at: Some(
-                Utc.datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
-                    .unwrap(),
+                NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                    .unwrap()
+                    .and_utc(),
             ),
         };
         let json = serde_json::to_string(&doc).unwrap();
@@ -265,8 +267,9 @@ mod test {
         assert_eq!(
             doc.start_at,
             Some(
-                Utc.datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
                     .unwrap()
+                    .and_utc()
             )
         );
     }
@@ -298,8 +301,9 @@ mod test {
     #[test]
     fn test_serialize_local_datetime() {
         let doc = DocumentWithLocalDateTimeOffset {
-            start_at: Local
-                .datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+            start_at: NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                .unwrap()
+                .and_local_timezone(Local)
                 .unwrap(),
         };
         let json = serde_json::to_string(&doc).unwrap();
@@ -312,8 +316,9 @@ mod test {
         let doc: DocumentWithLocalDateTimeOffset = serde_json::from_str(raw).unwrap();
         assert_eq!(
             doc.start_at,
-            Local
-                .datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+            NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                .unwrap()
+                .and_local_timezone(Local)
                 .unwrap()
         )
     }
@@ -332,8 +337,9 @@ mod test {
     fn test_serialize_optional_local_datetime() {
         let doc = DocumentWithOptionalLocalDateTimeOffset {
             start_at: Some(
-                Local
-                    .datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                    .unwrap()
+                    .and_local_timezone(Local)
                     .unwrap(),
             ),
         };
@@ -355,8 +361,9 @@ mod test {
         assert_eq!(
             doc.start_at,
             Some(
-                Local
-                    .datetime_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                NaiveDateTime::parse_from_str("2022-10-01T12:30:15", "%Y-%m-%dT%H:%M:%S")
+                    .unwrap()
+                    .and_local_timezone(Local)
                     .unwrap()
             )
         );
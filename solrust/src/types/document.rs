@@ -0,0 +1,67 @@
+//! This module defines the trait of the structs that represent the documents in Solr.
+
+pub use solrust_derive::SolrDocument;
+
+/// Trait of the document struct, which knows the names of its fields in Solr.
+///
+/// Derive this trait with `#[derive(SolrDocument)]` to refer to the fields without typos in the query builders:
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, SolrDocument)]
+/// struct Book {
+///     id: String,
+///     #[serde(rename = "title_txt_ja")]
+///     title: String,
+/// }
+///
+/// let q = StandardQueryOperand::new(Book::TITLE, "Rust");
+/// assert_eq!(Book::FIELDS, &["id", "title_txt_ja"]);
+/// ```
+pub trait SolrDocument {
+    /// Names of the fields in Solr, in the order of the declaration.
+    const FIELDS: &'static [&'static str];
+
+    /// Return the `fl` parameter value to get exactly the fields of this document.
+    fn field_list() -> String {
+        Self::FIELDS.join(",")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, SolrDocument)]
+    #[allow(dead_code)]
+    struct Book {
+        id: String,
+        #[serde(rename = "title_txt_ja")]
+        title: String,
+        #[serde(skip)]
+        score: f64,
+    }
+
+    #[derive(Serialize, Deserialize, SolrDocument)]
+    #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    struct Product {
+        product_id: String,
+        r#type: String,
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(Book::FIELDS, &["id", "title_txt_ja"]);
+        assert_eq!(Book::ID, "id");
+        assert_eq!(Book::TITLE, "title_txt_ja");
+        assert_eq!(Book::field_list(), "id,title_txt_ja");
+    }
+
+    #[test]
+    fn test_fields_with_rename_all() {
+        assert_eq!(Product::FIELDS, &["productId", "type"]);
+        assert_eq!(Product::PRODUCT_ID, "productId");
+        assert_eq!(Product::TYPE, "type");
+    }
+}
@@ -0,0 +1,82 @@
+//! This module defines the trait of the typed values of the facet fields.
+
+pub use solrust_derive::FacetValue;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FacetValueError {
+    #[error("Facet field `{0}` is not in the response")]
+    FieldNotFoundError(String),
+    #[error("Unknown value `{value}` of facet field `{field}`")]
+    UnknownValueError { field: String, value: String },
+}
+
+/// Trait of the typed key of the facet counts, which is converted from the value of the facet field.
+///
+/// Derive this trait for an enum with unit variants to get the facet counts keyed by the enum.
+/// Each variant matches its name, or the value given by `#[facet(value = "...")]`:
+///
+/// ```ignore
+/// #[derive(FacetValue)]
+/// enum Category {
+///     #[facet(value = "ABC")]
+///     Abc,
+///     #[facet(value = "ARC")]
+///     Arc,
+/// }
+///
+/// let counts: Vec<(Category, u64)> = facet_counts.typed_facet_field("category")?;
+/// ```
+pub trait FacetValue: Sized {
+    /// Convert the value of the facet field, or return `None` if the value is unknown.
+    fn from_facet_value(value: &str) -> Option<Self>;
+}
+
+impl FacetValue for String {
+    fn from_facet_value(value: &str) -> Option<Self> {
+        Some(String::from(value))
+    }
+}
+
+macro_rules! impl_facet_value_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl FacetValue for $t {
+                fn from_facet_value(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_facet_value_from_str!(bool, i32, i64, u32, u64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(FacetValue, Debug, PartialEq)]
+    enum Category {
+        #[facet(value = "ABC")]
+        Abc,
+        #[facet(value = "Other Contests")]
+        OtherContests,
+        Marathon,
+    }
+
+    #[test]
+    fn test_from_facet_value() {
+        assert_eq!(Category::from_facet_value("ABC"), Some(Category::Abc));
+        assert_eq!(
+            Category::from_facet_value("Other Contests"),
+            Some(Category::OtherContests)
+        );
+        assert_eq!(
+            Category::from_facet_value("Marathon"),
+            Some(Category::Marathon)
+        );
+        assert_eq!(Category::from_facet_value("ARC"), None);
+        assert_eq!(Category::OtherContests.as_facet_value(), "Other Contests");
+    }
+}
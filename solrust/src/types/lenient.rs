@@ -0,0 +1,136 @@
+//! This module defines a custom struct to deserialize the fields of the documents leniently,
+//! so that an unknown value in a legacy index does not fail the deserialization of the whole response.
+//!
+//! ```ignore
+//! #[serde_as]
+//! #[derive(Deserialize)]
+//! struct Product {
+//!     id: String,
+//!     /// None if the value is not a known variant.
+//!     #[serde_as(as = "SolrLenient")]
+//!     #[serde(default)]
+//!     status: Option<Status>,
+//!     /// The unknown values are skipped.
+//!     #[serde_as(as = "SolrLenient")]
+//!     #[serde(default)]
+//!     tags: Vec<Tag>,
+//! }
+//! ```
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_with::DeserializeAs;
+
+pub struct SolrLenient;
+
+/// Implementation to deserialize the value that can not be deserialized into T to None.
+impl<'de, T> DeserializeAs<'de, Option<T>> for SolrLenient
+where
+    T: DeserializeOwned,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Ok(serde_json::from_value(value).ok())
+    }
+}
+
+/// Implementation to deserialize the values of the multivalued field, skipping those that can not be deserialized into T.
+///
+/// A single value is accepted as well as an array.
+impl<'de, T> DeserializeAs<'de, Vec<T>> for SolrLenient
+where
+    T: DeserializeOwned,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let values = match Value::deserialize(deserializer)? {
+            Value::Array(values) => values,
+            Value::Null => Vec::new(),
+            value => vec![value],
+        };
+        Ok(values
+            .into_iter()
+            .filter_map(|value| serde_json::from_value(value).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_with::serde_as;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Active,
+        Retired,
+    }
+
+    #[serde_as]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Document {
+        id: String,
+        #[serde_as(as = "SolrLenient")]
+        #[serde(default)]
+        status: Option<Status>,
+        #[serde_as(as = "SolrLenient")]
+        #[serde(default)]
+        history: Vec<Status>,
+    }
+
+    #[test]
+    fn test_deserialize_known_values() {
+        let document: Document = serde_json::from_str(
+            r#"{"id": "001", "status": "active", "history": ["retired", "active"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            document,
+            Document {
+                id: String::from("001"),
+                status: Some(Status::Active),
+                history: vec![Status::Retired, Status::Active],
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_unknown_values() {
+        let documents: Vec<Document> = serde_json::from_str(
+            r#"[
+                {"id": "001", "status": "archived", "history": ["retired", "deleted", "active"]},
+                {"id": "002", "history": "active"},
+                {"id": "003", "status": null}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            documents,
+            vec![
+                Document {
+                    id: String::from("001"),
+                    status: None,
+                    history: vec![Status::Retired, Status::Active],
+                },
+                Document {
+                    id: String::from("002"),
+                    status: None,
+                    history: vec![Status::Active],
+                },
+                Document {
+                    id: String::from("003"),
+                    status: None,
+                    history: Vec::new(),
+                },
+            ]
+        );
+    }
+}
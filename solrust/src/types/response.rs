@@ -1,21 +1,67 @@
 //! This module defines the models of the Solr REST API response.
 
 use crate::types::datetime::SolrDateTime;
+use crate::types::facet::{FacetValue, FacetValueError};
 use chrono::{DateTime, FixedOffset};
 use itertools::Itertools;
-use serde::de::Error;
+use serde::de::{DeserializeOwned, Error};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use serde_with::serde_as;
 use std::collections::HashMap;
 
 /// Model of `responseHeader` field.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct SolrResponseHeader {
     pub status: u32,
     #[serde(alias = "QTime")]
     pub qtime: u32,
     pub params: Option<HashMap<String, Value>>,
+    /// `true` when the search was stopped before completing, e.g. by `timeAllowed`.
+    #[serde(alias = "partialResults")]
+    pub partial_results: Option<bool>,
+    /// `true` when the segment terminated early with `segmentTerminateEarly` parameter.
+    #[serde(alias = "segmentTerminatedEarly")]
+    pub segment_terminated_early: Option<bool>,
+    /// Achieved replication factor of the update in SolrCloud, the number of the replicas that received it.
+    pub rf: Option<u32>,
+    /// Minimum replication factor requested by `min_rf` parameter.
+    pub min_rf: Option<u32>,
+    /// Failures of the documents ignored by `TolerantUpdateProcessor`.
+    pub errors: Option<Vec<SolrUpdateError>>,
+    /// Maximum number of the failures tolerated by `TolerantUpdateProcessor`, -1 means unlimited.
+    #[serde(alias = "maxErrors")]
+    pub max_errors: Option<i64>,
+}
+
+impl SolrResponseHeader {
+    /// Return true if the results in the response may be incomplete.
+    pub fn is_partial(&self) -> bool {
+        self.partial_results.unwrap_or(false)
+    }
+
+    /// Return true if the update reached at least `min_rf` replicas.
+    ///
+    /// It is false when the response does not report the achieved replication factor, e.g. in standalone mode.
+    pub fn is_replicated(&self, min_rf: u32) -> bool {
+        self.rf.is_some_and(|rf| rf >= min_rf)
+    }
+
+    /// Return the failures of the documents ignored by `TolerantUpdateProcessor`.
+    pub fn update_errors(&self) -> &[SolrUpdateError] {
+        self.errors.as_deref().unwrap_or_default()
+    }
+}
+
+/// Model of an element of `errors` field in the response header of the update with `TolerantUpdateProcessor`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SolrUpdateError {
+    /// Type of the failed operation, `ADD`, `DELID` or `DELQ`.
+    #[serde(rename = "type")]
+    pub error_type: String,
+    /// Unique key of the document, or the query of the delete.
+    pub id: String,
+    pub message: String,
 }
 
 /// Model of the response which ping api returns.
@@ -29,11 +75,56 @@ pub struct SolrPingResponse {
 /// Model of `error` field.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrErrorInfo {
-    pub metadata: Vec<String>,
+    /// Metadata of the error, e.g. `error-class` and `root-error-class`.
+    #[serde(deserialize_with = "deserialize_error_metadata", default)]
+    pub metadata: HashMap<String, String>,
     pub msg: String,
     pub code: u32,
 }
 
+impl SolrErrorInfo {
+    /// Return the Java class of the exception, e.g. `org.apache.solr.common.SolrException`.
+    pub fn error_class(&self) -> Option<&str> {
+        self.metadata.get("error-class").map(|class| class.as_str())
+    }
+
+    /// Return the Java class of the root cause of the exception.
+    pub fn root_error_class(&self) -> Option<&str> {
+        self.metadata
+            .get("root-error-class")
+            .map(|class| class.as_str())
+    }
+}
+
+/// Function to deserialize the metadata of the error, which is an array with alternating keys and values,
+/// or an object with `json.nl=map` parameter.
+fn deserialize_error_metadata<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Value = Deserialize::deserialize(deserializer)?;
+    let to_string = |value: Value| match value {
+        Value::String(value) => value,
+        value => value.to_string(),
+    };
+    match value {
+        Value::Array(values) => Ok(values
+            .into_iter()
+            .tuples()
+            .map(|(key, value)| (to_string(key), to_string(value)))
+            .collect()),
+        Value::Object(map) => Ok(map
+            .into_iter()
+            .map(|(key, value)| (key, to_string(value)))
+            .collect()),
+        Value::Null => Ok(HashMap::new()),
+        value => Err(D::Error::custom(format!(
+            "Failed to parse error metadata. [{}]",
+            value
+        ))),
+    }
+}
+
 /// Model of `lucene` field in the response JSON of
 /// a request to `/solr/admin/info/system`.
 #[derive(Serialize, Deserialize, Debug)]
@@ -105,7 +196,8 @@ pub struct SolrCoreStatus {
     #[serde(alias = "startTime")]
     pub start_time: String,
     pub uptime: u64,
-    pub index: SolrIndexInfo,
+    /// Details of the index, which are absent when requested with `indexInfo=false`.
+    pub index: Option<SolrIndexInfo>,
 }
 
 /// Model of the response JSON of a request to `/solr/admin/cores`.
@@ -122,11 +214,31 @@ pub struct SolrCoreList {
 impl SolrCoreList {
     /// Return vector of the name of Solr cores present in the Solr instance.
     pub fn as_vec(&self) -> Option<Vec<String>> {
-        if let Some(cores) = &self.status {
-            Some(cores.keys().cloned().collect())
-        } else {
-            return None;
-        }
+        self.status
+            .as_ref()
+            .map(|cores| cores.keys().cloned().collect())
+    }
+
+    /// Return the iterator over the names and the statuses of the cores, in the order of the name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SolrCoreStatus)> {
+        self.status
+            .iter()
+            .flat_map(|cores| cores.iter())
+            .map(|(name, status)| (name.as_str(), status))
+            .sorted_by_key(|(name, _)| *name)
+    }
+
+    /// Return the status of the core of the name.
+    pub fn get(&self, name: &str) -> Option<&SolrCoreStatus> {
+        self.status.as_ref().and_then(|cores| cores.get(name))
+    }
+
+    pub fn len(&self) -> usize {
+        self.status.as_ref().map(|cores| cores.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -136,24 +248,627 @@ pub struct SolrSimpleResponse {
     #[serde(alias = "responseHeader")]
     pub header: SolrResponseHeader,
     pub error: Option<SolrErrorInfo>,
+    /// Name of the shard that handled the update, set by [`crate::client::routing::CloudRouter`].
+    #[serde(skip)]
+    pub shard: Option<String>,
+}
+
+/// Model of the response JSON of a request to the real-time get handler `/solr/<CORE_NAME>/get` with `id` parameter.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrRealTimeGetResponse {
+    /// The latest version of the document, even if it is not committed yet, or None if it does not exist.
+    pub doc: Option<Value>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the response JSON of a request to `/solr/<CORE_NAME>/tasks/cancel`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrCancelQueryResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Result of the cancellation, e.g. `success` or `not found`.
+    #[serde(alias = "cancellationResult")]
+    pub cancellation_result: Option<String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+impl SolrCancelQueryResponse {
+    /// Return true if the query was running and is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_result
+            .as_deref()
+            .is_some_and(|result| result.starts_with("success"))
+    }
+}
+
+/// Model of the response JSON of a request to `/solr/<CORE_NAME>/tasks/list`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrTaskListResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Map of the UUIDs of the running cancellable queries to their query strings.
+    #[serde(alias = "taskList", default)]
+    pub task_list: HashMap<String, String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the response JSON of a request to the Data Import Handler.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrDihResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    pub command: Option<String>,
+    /// Status of the handler, `idle` or `busy`.
+    #[serde(default)]
+    pub status: String,
+    #[serde(alias = "importResponse")]
+    pub import_response: Option<String>,
+    /// Statistics of the last or running import, e.g. `Total Documents Processed`.
+    #[serde(alias = "statusMessages", default)]
+    pub status_messages: HashMap<String, String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+impl SolrDihResponse {
+    /// Return true if an import is running.
+    pub fn is_busy(&self) -> bool {
+        self.status == "busy"
+    }
+
+    /// Return the number of the documents processed by the import.
+    pub fn documents_processed(&self) -> Option<u64> {
+        self.status_messages
+            .get("Total Documents Processed")
+            .and_then(|count| count.parse().ok())
+    }
+}
+
+/// Model of the response JSON of a `suggest.build` or `suggest.reload` request to the suggest handler.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrSuggestCommandResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Command executed by the suggest component, `build` or `reload`.
+    pub command: Option<String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the response JSON of a request to the Collections API that changes the cluster,
+/// such as CREATE and ADDREPLICA actions.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrCollectionsResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Responses of the nodes on which the operation succeeded, keyed by the node name.
+    pub success: Option<HashMap<String, Value>>,
+    /// Error messages of the nodes on which the operation failed, keyed by the node name.
+    pub failure: Option<HashMap<String, Value>>,
+    /// ID of the request submitted with `async` parameter.
+    #[serde(alias = "requestid")]
+    pub request_id: Option<String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+impl SolrCollectionsResponse {
+    /// Return true if the operation failed on some of the nodes.
+    pub fn has_failure(&self) -> bool {
+        self.failure
+            .as_ref()
+            .map(|failure| !failure.is_empty())
+            .unwrap_or(false)
+    }
+}
+
+/// State of the asynchronous request of the Collections API.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SolrRequestState {
+    Submitted,
+    Running,
+    Completed,
+    Failed,
+    NotFound,
+}
+
+/// Model of the response JSON of the REQUESTSTATUS action of the Collections API.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrRequestStatusResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    pub status: SolrRequestStatus,
+    /// Responses of the nodes on which the operation succeeded, keyed by the node name.
+    pub success: Option<HashMap<String, Value>>,
+    /// Error messages of the nodes on which the operation failed, keyed by the node name.
+    pub failure: Option<HashMap<String, Value>>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the `status` field in the response JSON of the REQUESTSTATUS action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrRequestStatus {
+    pub state: SolrRequestState,
+    pub msg: String,
+}
+
+/// Model of the response JSON of the LIST action of the Collections API.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrCollectionList {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    #[serde(default)]
+    pub collections: Vec<String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the response JSON of a request to `/solr/admin/metrics`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrMetricsResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Metrics keyed by the registry name, e.g. `solr.jvm` and `solr.core.<CORE_NAME>`, and then by the metric name.
+    #[serde(default)]
+    pub metrics: HashMap<String, HashMap<String, SolrMetric>>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+impl SolrMetricsResponse {
+    /// Return the metric of the registry, e.g. `get("solr.jvm", "memory.heap.used")`.
+    pub fn get(&self, registry: &str, name: &str) -> Option<&SolrMetric> {
+        self.metrics
+            .get(registry)
+            .and_then(|metrics| metrics.get(name))
+    }
+}
+
+/// Model of a metric in the response JSON of the Metrics API.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum SolrMetric {
+    Number(f64),
+    Timer(SolrTimerMetric),
+    Meter(SolrMeterMetric),
+    Cache(SolrCacheMetric),
+    /// Metrics of the other shapes, such as strings and maps of gauges.
+    Other(Value),
+}
+
+impl SolrMetric {
+    /// Return the value of the numeric metric.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SolrMetric::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Model of the timer metric, such as the request times of the handler.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrTimerMetric {
+    pub count: u64,
+    #[serde(alias = "meanRate")]
+    pub mean_rate: Option<f64>,
+    #[serde(alias = "1minRate")]
+    pub one_minute_rate: Option<f64>,
+    #[serde(alias = "5minRate")]
+    pub five_minute_rate: Option<f64>,
+    #[serde(alias = "15minRate")]
+    pub fifteen_minute_rate: Option<f64>,
+    pub min_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub mean_ms: f64,
+    pub median_ms: Option<f64>,
+    pub stddev_ms: Option<f64>,
+    pub p75_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub p999_ms: Option<f64>,
+}
+
+/// Model of the meter metric, such as the number of the errors of the handler.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrMeterMetric {
+    pub count: u64,
+    #[serde(alias = "meanRate")]
+    pub mean_rate: f64,
+    #[serde(alias = "1minRate")]
+    pub one_minute_rate: Option<f64>,
+    #[serde(alias = "5minRate")]
+    pub five_minute_rate: Option<f64>,
+    #[serde(alias = "15minRate")]
+    pub fifteen_minute_rate: Option<f64>,
+}
+
+/// Model of the metric of the cache, such as `CACHE.searcher.queryResultCache`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrCacheMetric {
+    pub lookups: u64,
+    pub hits: u64,
+    pub hitratio: f64,
+    pub inserts: Option<u64>,
+    pub evictions: Option<u64>,
+    pub size: Option<u64>,
+    pub cumulative_lookups: Option<u64>,
+    pub cumulative_hits: Option<u64>,
+    pub cumulative_hitratio: Option<f64>,
+}
+
+/// Model of the response JSON of a request to `/solr/admin/info/threads`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrThreadsResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    pub system: SolrThreadsInfo,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the `system` field in the response JSON of a request to `/solr/admin/info/threads`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrThreadsInfo {
+    #[serde(alias = "threadCount")]
+    pub thread_count: SolrThreadCount,
+    /// Solr returns the dump as the flat list of the states and the threads, from which the threads are extracted.
+    #[serde(
+        alias = "threadDump",
+        default,
+        deserialize_with = "deserialize_thread_dump"
+    )]
+    pub thread_dump: Vec<SolrThreadInfo>,
+}
+
+impl SolrThreadsInfo {
+    /// Return the threads in the state, e.g. `BLOCKED`.
+    pub fn threads_in(&self, state: &str) -> Vec<&SolrThreadInfo> {
+        self.thread_dump
+            .iter()
+            .filter(|thread| thread.state == state)
+            .collect()
+    }
+}
+
+/// Model of the `threadCount` field in the response JSON of a request to `/solr/admin/info/threads`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrThreadCount {
+    pub current: u64,
+    pub peak: u64,
+    pub daemon: u64,
+}
+
+/// Model of the thread in the response JSON of a request to `/solr/admin/info/threads`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrThreadInfo {
+    pub id: u64,
+    pub name: String,
+    /// State of the thread, e.g. `RUNNABLE`, `WAITING` or `BLOCKED`.
+    pub state: String,
+    /// Lock which the thread is waiting for.
+    pub lock: Option<String>,
+    /// CPU time of the thread, e.g. `12.34ms`.
+    #[serde(alias = "cpuTime")]
+    pub cpu_time: Option<String>,
+    #[serde(alias = "userTime")]
+    pub user_time: Option<String>,
+    #[serde(alias = "stackTrace", default)]
+    pub stack_trace: Vec<String>,
+}
+
+/// Deserialize the threads from the flat list of the states and the threads.
+fn deserialize_thread_dump<'de, D>(deserializer: D) -> Result<Vec<SolrThreadInfo>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<Value>::deserialize(deserializer)?
+        .into_iter()
+        .filter(|value| value.is_object())
+        .map(|value| serde_json::from_value(value).map_err(D::Error::custom))
+        .collect()
+}
+
+/// Model of the response JSON of a request to `/solr/admin/info/properties`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrPropertiesResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Java system properties of the Solr instance, e.g. `java.version`.
+    #[serde(alias = "system.properties", default)]
+    pub properties: HashMap<String, String>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the response JSON of a request to `/solr/admin/info/logging`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrLoggingResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Name of the logging framework, e.g. `Log4j2 (org.slf4j.impl.Log4jLoggerFactory)`.
+    pub watcher: Option<String>,
+    /// Log levels available in the logging framework.
+    #[serde(default)]
+    pub levels: Vec<String>,
+    #[serde(default)]
+    pub loggers: Vec<SolrLoggerInfo>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+impl SolrLoggingResponse {
+    /// Return the logger of the name.
+    pub fn logger(&self, name: &str) -> Option<&SolrLoggerInfo> {
+        self.loggers.iter().find(|logger| logger.name == name)
+    }
+}
+
+/// Model of the logger in the response JSON of a request to `/solr/admin/info/logging`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrLoggerInfo {
+    pub name: String,
+    /// Effective level of the logger. None if the level is not determined.
+    pub level: Option<String>,
+    /// True if the level is set to the logger itself, not inherited from the parent.
+    #[serde(default)]
+    pub set: bool,
+}
+
+/// Model of the response JSON of the LISTALIASES action of the Collections API.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrAliasList {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Comma-separated collections of each alias.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    #[serde(default)]
+    pub properties: HashMap<String, HashMap<String, String>>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+impl SolrAliasList {
+    /// Return the collections of the alias.
+    pub fn collections(&self, alias: &str) -> Vec<String> {
+        self.aliases
+            .get(alias)
+            .map(|collections| collections.split(',').map(String::from).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Model of the response JSON of the CLUSTERSTATUS action of the Collections API.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrClusterStatus {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    pub cluster: Option<SolrClusterState>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of `cluster` field in the response JSON of the CLUSTERSTATUS action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrClusterState {
+    #[serde(default)]
+    pub collections: HashMap<String, SolrCollectionState>,
+    /// Comma-separated collections of each alias.
+    pub aliases: Option<HashMap<String, String>>,
+    pub roles: Option<Value>,
+    #[serde(default)]
+    pub live_nodes: Vec<String>,
+}
+
+/// Model of `cluster.collections.<COLLECTION_NAME>` field in the response JSON of the CLUSTERSTATUS action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrCollectionState {
+    #[serde(default)]
+    pub shards: HashMap<String, SolrShardState>,
+    #[serde(alias = "configName")]
+    pub config_name: Option<String>,
+    pub router: Option<SolrRouterInfo>,
+    pub health: Option<String>,
+    #[serde(alias = "znodeVersion")]
+    pub znode_version: Option<i64>,
+    pub aliases: Option<Vec<String>>,
+}
+
+impl SolrCollectionState {
+    /// Return the leader replica of the shard.
+    pub fn leader(&self, shard: &str) -> Option<&SolrReplicaState> {
+        self.shards.get(shard).and_then(|shard| shard.leader())
+    }
+
+    /// Return all replicas of the collection.
+    pub fn replicas(&self) -> Vec<&SolrReplicaState> {
+        self.shards
+            .values()
+            .flat_map(|shard| shard.replicas.values())
+            .collect()
+    }
+}
+
+/// Model of `router` field of the collection in the response JSON of the CLUSTERSTATUS action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrRouterInfo {
+    pub name: String,
+    pub field: Option<String>,
+}
+
+/// Model of the shard in the response JSON of the CLUSTERSTATUS action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrShardState {
+    /// Hash range of the shard, e.g. `80000000-ffffffff`. None with the implicit router.
+    pub range: Option<String>,
+    pub state: String,
+    #[serde(default)]
+    pub replicas: HashMap<String, SolrReplicaState>,
+    pub health: Option<String>,
+}
+
+impl SolrShardState {
+    /// Return the leader replica of the shard.
+    pub fn leader(&self) -> Option<&SolrReplicaState> {
+        self.replicas.values().find(|replica| replica.leader)
+    }
+
+    /// Return the replicas that are active on the live nodes.
+    pub fn active_replicas(&self) -> Vec<&SolrReplicaState> {
+        self.replicas
+            .values()
+            .filter(|replica| replica.is_active())
+            .collect()
+    }
+}
+
+/// Model of the replica in the response JSON of the CLUSTERSTATUS action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrReplicaState {
+    /// Name of the core of the replica.
+    pub core: String,
+    /// Base URL of the node, e.g. `http://localhost:8983/solr`.
+    pub base_url: String,
+    pub node_name: String,
+    /// State of the replica, e.g. `active`, `down` or `recovering`.
+    pub state: String,
+    /// Type of the replica, `NRT`, `TLOG` or `PULL`.
+    #[serde(alias = "type")]
+    pub replica_type: String,
+    /// Solr returns the flag as the string `"true"`, which is converted to the boolean.
+    #[serde(default, deserialize_with = "deserialize_string_bool")]
+    pub leader: bool,
+}
+
+impl SolrReplicaState {
+    pub fn is_active(&self) -> bool {
+        self.state == "active"
+    }
+
+    /// Return the URL of the core of the replica, e.g. `http://localhost:8983/solr/example_shard1_replica_n1`.
+    pub fn core_url(&self) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), self.core)
+    }
+}
+
+/// Deserialize the boolean given either as the boolean or as the string.
+fn deserialize_string_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Bool(value) => Ok(value),
+        Value::String(value) => value.parse::<bool>().map_err(D::Error::custom),
+        value => Err(D::Error::custom(format!(
+            "expected boolean, but got {}",
+            value
+        ))),
+    }
 }
 
 /// Model of the response JSON of a search request.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrSelectResponse<T> {
-    #[serde(alias = "responseHeader")]
+    /// The header is absent when the request was sent with `omitHeader=true`, in which case it is defaulted.
+    #[serde(alias = "responseHeader", default)]
     pub header: SolrResponseHeader,
     pub response: SolrSelectBody<T>,
     pub facet_counts: Option<SolrFacetBody>,
+    pub facets: Option<SolrJsonFacetResponse>,
+    pub debug: Option<SolrDebugInfo>,
+    pub stats: Option<SolrStats>,
+    #[serde(alias = "nextCursorMark")]
+    pub next_cursor_mark: Option<String>,
     pub error: Option<SolrErrorInfo>,
+    /// Sections of the response not modeled by this struct, such as output of custom search components.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl<T> SolrSelectResponse<T> {
+    /// Deserialize the section of the response that is not modeled by this struct into the given type.
+    ///
+    /// Return `Ok(None)` if the section is not present in the response.
+    pub fn section<S>(&self, name: &str) -> Result<Option<S>, serde_json::Error>
+    where
+        S: DeserializeOwned,
+    {
+        self.extra
+            .get(name)
+            .map(|value| S::deserialize(value))
+            .transpose()
+    }
+
+    /// Deserialize the `spellcheck` section of the response.
+    ///
+    /// Return `Ok(None)` if the spell checking is not enabled.
+    pub fn spellcheck(&self) -> Result<Option<SolrSpellcheck>, serde_json::Error> {
+        self.section("spellcheck")
+    }
+
+    /// Return true if there may be more documents to fetch with cursor pagination.
+    ///
+    /// Solr returns the same `nextCursorMark` as the given `cursorMark` when all documents have been fetched,
+    /// so compare it with the mark used for this request.
+    pub fn has_more(&self, previous_mark: &str) -> bool {
+        match &self.next_cursor_mark {
+            Some(next) => next != previous_mark,
+            None => false,
+        }
+    }
+}
+
+/// Highlighted snippets keyed by the unique key of the document, then by the field.
+pub type SolrHighlighting = HashMap<String, HashMap<String, Vec<String>>>;
+
+/// Result of a search, which extracts the documents, the facets and the highlighting from the response.
+#[derive(Debug)]
+pub struct SearchResult<D> {
+    pub docs: Vec<D>,
+    pub num_found: u64,
+    pub start: u64,
+    pub facet_counts: Option<SolrFacetBody>,
+    /// Highlighting of the documents, which is empty if highlighting is not enabled.
+    pub highlighting: SolrHighlighting,
+    pub next_cursor_mark: Option<String>,
+}
+
+impl<D> SearchResult<D> {
+    /// Extract the result from the response of a search request.
+    ///
+    /// Return an error if the `highlighting` section of the response is malformed.
+    pub fn from_response(response: SolrSelectResponse<D>) -> Result<Self, serde_json::Error> {
+        let highlighting = response.section("highlighting")?.unwrap_or_default();
+
+        Ok(Self {
+            docs: response.response.docs,
+            num_found: response.response.num_found,
+            start: response.response.start,
+            facet_counts: response.facet_counts,
+            highlighting,
+            next_cursor_mark: response.next_cursor_mark,
+        })
+    }
+
+    /// Return the counts of the facet field keyed by the typed values, in the order of the response.
+    ///
+    /// Return an error if the field is not in the response or a value can not be converted.
+    pub fn typed_facet_field<K>(&self, field: &str) -> Result<Vec<(K, u64)>, FacetValueError>
+    where
+        K: FacetValue,
+    {
+        match &self.facet_counts {
+            Some(facet_counts) => facet_counts.typed_facet_field(field),
+            None => Err(FacetValueError::FieldNotFoundError(String::from(field))),
+        }
+    }
+
+    /// Return the highlighted snippets of the field of the document.
+    pub fn highlights(&self, id: &str, field: &str) -> Option<&Vec<String>> {
+        self.highlighting
+            .get(id)
+            .and_then(|fields| fields.get(field))
+    }
 }
 
 /// Model of the `response` field in the response JSON of a search request response.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrSelectBody<T> {
     #[serde(alias = "numFound")]
-    pub num_found: u32,
-    pub start: u32,
+    pub num_found: u64,
+    pub start: u64,
     #[serde(alias = "numFoundExact")]
     pub num_found_exact: bool,
     // TODO: ジェネリクス化
@@ -165,22 +880,52 @@ pub struct SolrSelectBody<T> {
 pub struct SolrFacetBody {
     pub facet_queries: Value,
     #[serde(deserialize_with = "deserialize_facet_fields")]
-    pub facet_fields: HashMap<String, Vec<(String, u32)>>,
+    pub facet_fields: HashMap<String, Vec<(String, u64)>>,
     #[serde(deserialize_with = "deserialize_facet_ranges")]
     pub facet_ranges: HashMap<String, SolrRangeFacetKind>,
     pub facet_intervals: Value,
     pub facet_heatmaps: Value,
+    /// Trees of the pivot facets, keyed by the comma-separated fields.
+    #[serde(default)]
+    pub facet_pivot: HashMap<String, Vec<SolrPivotFacet>>,
+}
+
+impl SolrFacetBody {
+    /// Return the counts of the facet field keyed by the typed values, in the order of the response.
+    ///
+    /// Return an error if the field is not in the response or a value can not be converted.
+    pub fn typed_facet_field<K>(&self, field: &str) -> Result<Vec<(K, u64)>, FacetValueError>
+    where
+        K: FacetValue,
+    {
+        let counts = self
+            .facet_fields
+            .get(field)
+            .ok_or_else(|| FacetValueError::FieldNotFoundError(String::from(field)))?;
+
+        counts
+            .iter()
+            .map(|(value, count)| match K::from_facet_value(value) {
+                Some(key) => Ok((key, *count)),
+                None => Err(FacetValueError::UnknownValueError {
+                    field: String::from(field),
+                    value: value.clone(),
+                }),
+            })
+            .collect()
+    }
 }
 
 /// Function to deserialize an array with alternating fields and counts for Rust.
+#[allow(clippy::type_complexity)]
 fn deserialize_facet_fields<'de, D>(
     deserializer: D,
-) -> Result<HashMap<String, Vec<(String, u32)>>, D::Error>
+) -> Result<HashMap<String, Vec<(String, u64)>>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let value: HashMap<String, Vec<Value>> = Deserialize::deserialize(deserializer)?;
-    let value: HashMap<String, Vec<(String, u32)>> = value
+    let value: HashMap<String, Vec<(String, u64)>> = value
         .iter()
         .map(|(k, v)| {
             (
@@ -190,10 +935,10 @@ where
                     .map(|(v1, v2)| {
                         (
                             v1.as_str().unwrap_or("").to_string(),
-                            v2.as_u64().unwrap_or(0) as u32,
+                            v2.as_u64().unwrap_or(0),
                         )
                     })
-                    .collect::<Vec<(String, u32)>>(),
+                    .collect::<Vec<(String, u64)>>(),
             )
         })
         .collect();
@@ -221,7 +966,7 @@ where
                         .map_err(|e| {
                             D::Error::custom(format!(
                                 "Failed to parse integer range facet result. [{}]",
-                                e.to_string()
+                                e
                             ))
                         })?;
                     result.insert(field.to_string(), SolrRangeFacetKind::Integer(value));
@@ -230,7 +975,7 @@ where
                         .map_err(|e| {
                             D::Error::custom(format!(
                                 "Failed to parse float range facet result. [{}]",
-                                e.to_string()
+                                e
                             ))
                         })?;
                     result.insert(field.to_string(), SolrRangeFacetKind::Float(value));
@@ -242,7 +987,7 @@ where
                         .map_err(|e| {
                             D::Error::custom(format!(
                                 "Failed to parse datetime range facet result. [{}]",
-                                e.to_string()
+                                e
                             ))
                         })?;
                     result.insert(field.to_string(), SolrRangeFacetKind::DateTime(value));
@@ -271,26 +1016,26 @@ pub enum SolrRangeFacetKind {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrIntegerRangeFacet {
     #[serde(deserialize_with = "deserialize_range_facet_counts")]
-    pub counts: Vec<(String, u32)>,
+    pub counts: Vec<(String, u64)>,
     pub gap: i64,
     pub start: i64,
     pub end: i64,
-    pub before: Option<i64>,
-    pub after: Option<i64>,
-    pub between: Option<i64>,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub between: Option<u64>,
 }
 
 /// Model of the result of float range facet.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrFloatRangeFacet {
     #[serde(deserialize_with = "deserialize_range_facet_counts")]
-    pub counts: Vec<(String, u32)>,
+    pub counts: Vec<(String, u64)>,
     pub gap: f64,
     pub start: f64,
     pub end: f64,
-    pub before: Option<f64>,
-    pub after: Option<f64>,
-    pub between: Option<f64>,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub between: Option<u64>,
 }
 
 /// Model of the result of datetime range facet.
@@ -298,36 +1043,80 @@ pub struct SolrFloatRangeFacet {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrDateTimeRangeFacet {
     #[serde(deserialize_with = "deserialize_range_facet_counts")]
-    pub counts: Vec<(String, u32)>,
+    pub counts: Vec<(String, u64)>,
     pub gap: String,
     #[serde_as(as = "SolrDateTime")]
     pub start: DateTime<FixedOffset>,
     #[serde_as(as = "SolrDateTime")]
     pub end: DateTime<FixedOffset>,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub between: Option<u64>,
+}
+
+/// Model of a node of the pivot facet tree.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrPivotFacet {
+    pub field: String,
+    pub value: Value,
+    pub count: u64,
+    /// Nodes of the next field, absent in the leaves.
+    #[serde(default)]
+    pub pivot: Vec<SolrPivotFacet>,
+    /// Stats fields computed in this node, present when `stats` local parameter is given.
+    pub stats: Option<SolrStats>,
+    /// Range facets computed in this node, present when `range` local parameter is given.
+    #[serde(deserialize_with = "deserialize_facet_ranges", default)]
+    pub ranges: HashMap<String, SolrRangeFacetKind>,
+    /// Counts of the facet queries computed in this node, present when `query` local parameter is given.
     #[serde(default)]
-    #[serde_as(as = "Option<SolrDateTime>")]
-    pub before: Option<DateTime<FixedOffset>>,
-    #[serde(default)]
-    #[serde_as(as = "Option<SolrDateTime>")]
-    pub after: Option<DateTime<FixedOffset>>,
-    #[serde(default)]
-    #[serde_as(as = "Option<SolrDateTime>")]
-    pub between: Option<DateTime<FixedOffset>>,
+    pub queries: HashMap<String, u64>,
+}
+
+impl SolrPivotFacet {
+    /// Return the stats of the field computed in this node.
+    pub fn field_stats(&self, field: &str) -> Option<&SolrFieldStats> {
+        self.stats
+            .as_ref()
+            .and_then(|stats| stats.stats_fields.get(field))
+    }
+}
+
+/// Model of the `stats` field in the response JSON of a search request with the Stats Component.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrStats {
+    pub stats_fields: HashMap<String, SolrFieldStats>,
+}
+
+/// Model of the statistics of a field.
+///
+/// `min`, `max` and `mean` are strings for the date fields, so they are kept as the JSON values.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrFieldStats {
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+    pub count: Option<u64>,
+    pub missing: Option<u64>,
+    pub sum: Option<f64>,
+    #[serde(alias = "sumOfSquares")]
+    pub sum_of_squares: Option<f64>,
+    pub mean: Option<Value>,
+    pub stddev: Option<f64>,
 }
 
 /// Function to deserialize an array with alternating fields and counts for Rust.
-fn deserialize_range_facet_counts<'de, D>(deserializer: D) -> Result<Vec<(String, u32)>, D::Error>
+fn deserialize_range_facet_counts<'de, D>(deserializer: D) -> Result<Vec<(String, u64)>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let value: Vec<Value> = Deserialize::deserialize(deserializer)?;
-    let value: Vec<(String, u32)> = value
+    let value: Vec<(String, u64)> = value
         .iter()
         .tuples()
         .map(|(v1, v2)| {
             (
                 v1.as_str().unwrap_or("").to_string(),
-                v2.as_u64().unwrap_or(0) as u32,
+                v2.as_u64().unwrap_or(0),
             )
         })
         .collect();
@@ -335,6 +1124,330 @@ where
     Ok(value)
 }
 
+/// Model of the `facets` field in the response JSON of a search request with [JSON Facet API](https://solr.apache.org/guide/solr/latest/query-guide/json-facet-api.html).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrJsonFacetResponse {
+    pub count: u64,
+    /// Results of the facets and the aggregations, keyed by the name given in the request.
+    #[serde(flatten)]
+    pub facets: HashMap<String, Value>,
+}
+
+impl SolrJsonFacetResponse {
+    /// Return the buckets of the facet with the given name.
+    pub fn buckets(&self, name: &str) -> Result<Option<SolrJsonFacetBuckets>, serde_json::Error> {
+        deserialize_json_facet_entry(&self.facets, name)
+    }
+
+    /// Return the value of the aggregation (e.g. `avg(price)`, `unique(id)`) with the given name.
+    pub fn stat<V>(&self, name: &str) -> Result<Option<V>, serde_json::Error>
+    where
+        V: DeserializeOwned,
+    {
+        deserialize_json_facet_entry(&self.facets, name)
+    }
+}
+
+/// Model of the result of a `terms`, `range` or `query` facet in the JSON Facet API response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrJsonFacetBuckets {
+    #[serde(default)]
+    pub buckets: Vec<SolrJsonFacetBucket>,
+    #[serde(alias = "allBuckets")]
+    pub all_buckets: Option<SolrJsonFacetBucket>,
+    pub missing: Option<SolrJsonFacetBucket>,
+    #[serde(alias = "numBuckets")]
+    pub num_buckets: Option<u64>,
+    pub before: Option<SolrJsonFacetBucket>,
+    pub after: Option<SolrJsonFacetBucket>,
+    pub between: Option<SolrJsonFacetBucket>,
+}
+
+/// Model of a bucket in the JSON Facet API response.
+///
+/// `val` is absent for the `allBuckets`, `missing`, `before`, `after` and `between` buckets.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrJsonFacetBucket {
+    pub val: Option<Value>,
+    pub count: u64,
+    /// Results of the sub facets and the aggregations in this bucket, keyed by the name given in the request.
+    #[serde(flatten)]
+    pub facets: HashMap<String, Value>,
+}
+
+impl SolrJsonFacetBucket {
+    /// Return the value of the bucket as the given type.
+    pub fn val<V>(&self) -> Result<Option<V>, serde_json::Error>
+    where
+        V: DeserializeOwned,
+    {
+        self.val.as_ref().map(V::deserialize).transpose()
+    }
+
+    /// Return the buckets of the sub facet with the given name.
+    pub fn buckets(&self, name: &str) -> Result<Option<SolrJsonFacetBuckets>, serde_json::Error> {
+        deserialize_json_facet_entry(&self.facets, name)
+    }
+
+    /// Return the value of the aggregation in this bucket with the given name.
+    pub fn stat<V>(&self, name: &str) -> Result<Option<V>, serde_json::Error>
+    where
+        V: DeserializeOwned,
+    {
+        deserialize_json_facet_entry(&self.facets, name)
+    }
+}
+
+/// Result of an aggregation in the JSON Facet API response, whose type depends on the aggregation and the field.
+///
+/// For example, `unique` gives an integer, `avg` a float, `min` of a date field a string,
+/// and `percentile` with the multiple percents a list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SolrJsonFacetStat {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<SolrJsonFacetStat>),
+}
+
+impl SolrJsonFacetStat {
+    /// Return the value as a float if it is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SolrJsonFacetStat::Integer(value) => Some(*value as f64),
+            SolrJsonFacetStat::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Return the value as an integer if it is an integer, e.g. the result of `unique` or `hll`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            SolrJsonFacetStat::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Return the value as a string if it is a string, e.g. `min` or `max` of a date field.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SolrJsonFacetStat::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Return the values of `percentile`, which is a number for a percent and a list for the multiple percents.
+    pub fn as_percentiles(&self) -> Option<Vec<f64>> {
+        match self {
+            SolrJsonFacetStat::List(values) => values.iter().map(|value| value.as_f64()).collect(),
+            value => value.as_f64().map(|value| vec![value]),
+        }
+    }
+}
+
+/// Result of `relatedness` aggregation in the JSON Facet API response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SolrRelatedness {
+    /// Score of the relatedness, from -1 to 1. A positive value means the term is more common in the foreground set.
+    pub relatedness: f64,
+    /// Ratio of the documents with the term in the foreground set to the background set.
+    pub foreground_popularity: Option<f64>,
+    /// Ratio of the documents with the term in the background set to the background set.
+    pub background_popularity: Option<f64>,
+}
+
+/// Function to deserialize an entry of the JSON Facet API response into the given type.
+fn deserialize_json_facet_entry<V>(
+    facets: &HashMap<String, Value>,
+    name: &str,
+) -> Result<Option<V>, serde_json::Error>
+where
+    V: DeserializeOwned,
+{
+    facets.get(name).map(V::deserialize).transpose()
+}
+
+/// Model of the `debug` field in the response JSON of a search request response.
+///
+/// Which fields are present depends on the value of `debug` parameter.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrDebugInfo {
+    #[serde(alias = "rawquerystring")]
+    pub raw_query_string: Option<String>,
+    #[serde(alias = "querystring")]
+    pub query_string: Option<String>,
+    #[serde(alias = "parsedquery")]
+    pub parsed_query: Option<String>,
+    #[serde(alias = "parsedquery_toString")]
+    pub parsed_query_to_string: Option<String>,
+    #[serde(alias = "QParser")]
+    pub qparser: Option<String>,
+    pub filter_queries: Option<Vec<String>>,
+    pub parsed_filter_queries: Option<Vec<String>>,
+    /// Explanation of the score of each document, keyed by the unique key of the document.
+    pub explain: Option<HashMap<String, SolrExplainKind>>,
+    /// Query given by `explainOther` parameter.
+    #[serde(alias = "otherQuery")]
+    pub other_query: Option<String>,
+    /// Explanation of the score of each document matching `explainOther` query, keyed by the unique key of the document.
+    #[serde(alias = "explainOther")]
+    pub explain_other: Option<HashMap<String, SolrExplainKind>>,
+    pub timing: Option<SolrDebugTiming>,
+}
+
+/// Enum of the kind of the score explanation.
+///
+/// The explanation is a tree when `debug.explain.structured=true` is given, otherwise it is a plain text.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum SolrExplainKind {
+    Structured(ExplainNode),
+    Plain(String),
+}
+
+/// Model of a node of the structured score explanation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExplainNode {
+    #[serde(alias = "match")]
+    pub matched: bool,
+    pub value: f64,
+    pub description: String,
+    #[serde(default)]
+    pub details: Vec<ExplainNode>,
+}
+
+impl ExplainNode {
+    /// Return true if the node has no child nodes.
+    pub fn is_leaf(&self) -> bool {
+        self.details.is_empty()
+    }
+
+    /// Return the child node which contributes the most to the score of this node.
+    pub fn max_contributor(&self) -> Option<&ExplainNode> {
+        self.details
+            .iter()
+            .max_by(|a, b| a.value.total_cmp(&b.value))
+    }
+
+    /// Return the leaf nodes of the tree in depth-first order.
+    pub fn leaves(&self) -> Vec<&ExplainNode> {
+        if self.is_leaf() {
+            return vec![self];
+        }
+        self.details.iter().flat_map(|node| node.leaves()).collect()
+    }
+}
+
+/// Model of the `debug.timing` field in the response JSON of a search request response.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrDebugTiming {
+    pub time: f64,
+    pub prepare: SolrDebugTimingPhase,
+    pub process: SolrDebugTimingPhase,
+}
+
+/// Model of the `prepare` or `process` field in the `debug.timing` field.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrDebugTimingPhase {
+    pub time: f64,
+    /// Elapsed time of each search component, keyed by the name of the component.
+    #[serde(flatten)]
+    pub components: HashMap<String, SolrDebugComponentTiming>,
+}
+
+/// Model of the elapsed time of a search component in the `debug.timing` field.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrDebugComponentTiming {
+    pub time: f64,
+}
+
+/// Model of the `spellcheck` field in the response JSON of a search request with the spell checking.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrSpellcheck {
+    /// Suggestions for each misspelled term, as the flat array of the terms and the suggestions.
+    #[serde(default)]
+    pub suggestions: Value,
+    #[serde(alias = "correctlySpelled")]
+    pub correctly_spelled: Option<bool>,
+    /// Collations in the order of the ranking, present when `spellcheck.collate=true` is given.
+    #[serde(deserialize_with = "deserialize_collations", default)]
+    pub collations: Vec<SolrCollation>,
+}
+
+impl SolrSpellcheck {
+    /// Return the best collation that matches at least `min_hits` documents, for the "did you mean" suggestion.
+    ///
+    /// The number of hits is known only when `spellcheck.collateExtendedResults=true` is given,
+    /// otherwise it is regarded as 0.
+    pub fn best_collation(&self, min_hits: u64) -> Option<&SolrCollation> {
+        self.collations
+            .iter()
+            .find(|collation| collation.hits.unwrap_or(0) >= min_hits)
+    }
+}
+
+/// Model of a collation in the `spellcheck` field, the query rewritten with the corrections.
+///
+/// `hits` and `misspellings_and_corrections` are present only when `spellcheck.collateExtendedResults=true` is given.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SolrCollation {
+    #[serde(alias = "collationQuery")]
+    pub collation_query: String,
+    pub hits: Option<u64>,
+    /// Pairs of the misspelled term and its correction.
+    #[serde(
+        alias = "misspellingsAndCorrections",
+        deserialize_with = "deserialize_misspellings_and_corrections",
+        default
+    )]
+    pub misspellings_and_corrections: Vec<(String, String)>,
+}
+
+/// Function to deserialize the flat array of the collations, each of which is a string or an extended object.
+fn deserialize_collations<'de, D>(deserializer: D) -> Result<Vec<SolrCollation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Vec<Value> = Deserialize::deserialize(deserializer)?;
+    value
+        .into_iter()
+        .tuples()
+        .map(|(_, collation)| match collation {
+            Value::String(query) => Ok(SolrCollation {
+                collation_query: query,
+                hits: None,
+                misspellings_and_corrections: Vec::new(),
+            }),
+            collation => serde_json::from_value(collation).map_err(|e| {
+                D::Error::custom(format!("Failed to parse spellcheck collation. [{}]", e))
+            }),
+        })
+        .collect()
+}
+
+/// Function to deserialize an array with alternating misspelled terms and corrections for Rust.
+fn deserialize_misspellings_and_corrections<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(String, String)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Vec<String> = Deserialize::deserialize(deserializer)?;
+    Ok(value.into_iter().tuples().collect())
+}
+
+/// Model of the response JSON of a request to the Terms Component.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrTermsResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Terms and their document frequencies, keyed by the field.
+    #[serde(deserialize_with = "deserialize_facet_fields", default)]
+    pub terms: HashMap<String, Vec<(String, u64)>>,
+    pub error: Option<SolrErrorInfo>,
+}
+
 /// Model of the `analysis` field in the response JSON of a request to `/solr/<CORE_NAME>/analysis/field`.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SolrAnalysisBody {
@@ -357,6 +1470,99 @@ pub struct SolrAnalysisResponse {
     pub error: Option<SolrErrorInfo>,
 }
 
+/// Model of the response JSON of a request to `/solr/<CORE_NAME>/analysis/document`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SolrDocumentAnalysisResponse {
+    #[serde(alias = "responseHeader")]
+    pub header: SolrResponseHeader,
+    /// Analysis of the fields keyed by the unique key of the document, then by the field.
+    #[serde(default)]
+    pub analysis: HashMap<String, HashMap<String, SolrDocumentFieldAnalysis>>,
+    pub error: Option<SolrErrorInfo>,
+}
+
+/// Model of the analysis of a field of the document.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrDocumentFieldAnalysis {
+    /// Stages of the query analysis, present when the query is given.
+    #[serde(deserialize_with = "deserialize_analysis_stages", default)]
+    pub query: Vec<SolrAnalysisStage>,
+    /// Stages of the index analysis, keyed by the value of the field.
+    #[serde(deserialize_with = "deserialize_analysis_values", default)]
+    pub index: HashMap<String, Vec<SolrAnalysisStage>>,
+}
+
+/// Model of the output of a char filter, a tokenizer or a token filter in the analysis.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrAnalysisStage {
+    /// Class name of the component, e.g. `org.apache.lucene.analysis.standard.StandardTokenizer`.
+    pub name: String,
+    /// Tokens emitted by the tokenizer or the token filter.
+    pub tokens: Vec<SolrAnalysisToken>,
+    /// Text emitted by the char filter, which has no tokens.
+    pub text: Option<String>,
+}
+
+/// Model of a token in the analysis.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SolrAnalysisToken {
+    pub text: String,
+    pub start: Option<u32>,
+    pub end: Option<u32>,
+    pub position: Option<u32>,
+    #[serde(rename = "type")]
+    pub token_type: Option<String>,
+    /// Whether the token matches a token of the query analysis.
+    #[serde(alias = "match", default)]
+    pub matched: bool,
+}
+
+/// Function to deserialize an array with alternating component names and outputs for Rust.
+fn deserialize_analysis_stages<'de, D>(deserializer: D) -> Result<Vec<SolrAnalysisStage>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Vec<Value> = Deserialize::deserialize(deserializer)?;
+    parse_analysis_stages(value).map_err(D::Error::custom)
+}
+
+/// Function to deserialize the stages of the analysis keyed by the value of the field.
+fn deserialize_analysis_values<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, Vec<SolrAnalysisStage>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: HashMap<String, Vec<Value>> = Deserialize::deserialize(deserializer)?;
+    value
+        .into_iter()
+        .map(|(value, stages)| Ok((value, parse_analysis_stages(stages)?)))
+        .collect::<Result<_, serde_json::Error>>()
+        .map_err(D::Error::custom)
+}
+
+fn parse_analysis_stages(value: Vec<Value>) -> Result<Vec<SolrAnalysisStage>, serde_json::Error> {
+    value
+        .into_iter()
+        .tuples()
+        .map(|(name, output)| {
+            let name = name.as_str().unwrap_or("").to_string();
+            Ok(match output {
+                Value::String(text) => SolrAnalysisStage {
+                    name,
+                    tokens: Vec::new(),
+                    text: Some(text),
+                },
+                output => SolrAnalysisStage {
+                    name,
+                    tokens: serde_json::from_value(output)?,
+                    text: None,
+                },
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -384,6 +1590,52 @@ mod test {
         assert_eq!(header.qtime, 7);
     }
 
+    #[test]
+    fn test_deserialize_update_response_header() {
+        let raw = r#"
+        {
+            "status": 0,
+            "QTime": 12,
+            "rf": 2,
+            "min_rf": 3,
+            "errors": [
+                {"type": "ADD", "id": "002", "message": "ERROR: [doc=002] unknown field 'foo'"}
+            ],
+            "maxErrors": -1
+        }
+        "#;
+        let header: SolrResponseHeader = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(header.rf, Some(2));
+        assert_eq!(header.min_rf, Some(3));
+        assert!(header.is_replicated(2));
+        assert!(!header.is_replicated(3));
+        assert_eq!(header.max_errors, Some(-1));
+        assert_eq!(
+            header.update_errors(),
+            &[SolrUpdateError {
+                error_type: String::from("ADD"),
+                id: String::from("002"),
+                message: String::from("ERROR: [doc=002] unknown field 'foo'"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_partial_response_header() {
+        let raw = r#"
+        {
+            "status": 0,
+            "QTime": 1003,
+            "partialResults": true,
+            "segmentTerminatedEarly": false
+        }
+        "#;
+        let header: SolrResponseHeader = serde_json::from_str(raw).unwrap();
+        assert!(header.is_partial());
+        assert_eq!(header.segment_terminated_early, Some(false));
+    }
+
     #[test]
     fn test_deserialize_error_info() {
         let raw = r#"
@@ -402,6 +1654,47 @@ mod test {
         let error: SolrErrorInfo = serde_json::from_str(raw).unwrap();
         assert_eq!(error.msg, "Missing required parameter: f.difficulty.facet.range.start (or default: facet.range.start)".to_string());
         assert_eq!(error.code, 400);
+        assert_eq!(
+            error.error_class(),
+            Some("org.apache.solr.common.SolrException")
+        );
+        assert_eq!(
+            error.root_error_class(),
+            Some("org.apache.solr.common.SolrException")
+        );
+    }
+
+    #[test]
+    fn test_deserialize_error_info_with_map_metadata() {
+        let raw = r#"
+        {
+            "metadata": {
+                "error-class": "org.apache.solr.common.SolrException",
+                "root-error-class": "java.lang.NumberFormatException"
+            },
+            "msg": "ERROR: [doc=001] Error adding field 'price'='abc'",
+            "code": 400
+        }
+        "#;
+
+        let error: SolrErrorInfo = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            error.error_class(),
+            Some("org.apache.solr.common.SolrException")
+        );
+        assert_eq!(
+            error.root_error_class(),
+            Some("java.lang.NumberFormatException")
+        );
+    }
+
+    #[test]
+    fn test_deserialize_error_info_without_metadata() {
+        let raw = r#"{"msg": "Internal Server Error", "code": 500}"#;
+
+        let error: SolrErrorInfo = serde_json::from_str(raw).unwrap();
+        assert!(error.metadata.is_empty());
+        assert_eq!(error.error_class(), None);
     }
 
     #[test]
@@ -599,6 +1892,12 @@ mod test {
         let info: SolrCoreList = serde_json::from_str(raw).unwrap();
 
         assert_eq!(info.as_vec().unwrap(), vec![String::from("atcoder")]);
+        assert_eq!(info.len(), 1);
+        assert_eq!(info.get("atcoder").unwrap().uptime, 321775);
+        assert!(info.get("unknown").is_none());
+        let (name, status) = info.iter().next().unwrap();
+        assert_eq!(name, "atcoder");
+        assert_eq!(status.index.as_ref().unwrap().segments_file, "segments_1");
     }
 
     #[test]
@@ -689,6 +1988,46 @@ mod test {
         assert_eq!(body.num_found, 5650);
     }
 
+    #[test]
+    fn test_search_result_from_response() {
+        let raw = r#"
+        {
+            "responseHeader": {"status": 0, "QTime": 1},
+            "response": {
+                "numFound": 1,
+                "start": 0,
+                "numFoundExact": true,
+                "docs": [{"id": "001"}]
+            },
+            "facet_counts": {
+                "facet_queries": {},
+                "facet_fields": {"category": ["ABC", 1]},
+                "facet_ranges": {},
+                "facet_intervals": {},
+                "facet_heatmaps": {}
+            },
+            "highlighting": {
+                "001": {"title": ["<em>Rust</em> programming"]}
+            }
+        }
+        "#;
+
+        let response: SolrSelectResponse<Value> = serde_json::from_str(raw).unwrap();
+        let result = SearchResult::from_response(response).unwrap();
+
+        assert_eq!(result.num_found, 1);
+        assert_eq!(result.docs[0]["id"], "001");
+        assert_eq!(
+            result.typed_facet_field::<String>("category").unwrap(),
+            vec![(String::from("ABC"), 1)]
+        );
+        assert_eq!(
+            result.highlights("001", "title"),
+            Some(&vec![String::from("<em>Rust</em> programming")])
+        );
+        assert_eq!(result.highlights("002", "title"), None);
+    }
+
     #[test]
     fn test_deserialize_facet_counts() {
         let raw = r#"
@@ -770,6 +2109,137 @@ mod test {
         assert!(facet.facet_fields.contains_key("category"));
     }
 
+    #[test]
+    fn test_deserialize_facet_pivot() {
+        let raw = r#"
+        {
+            "facet_queries": {},
+            "facet_fields": {},
+            "facet_ranges": {
+                "price": {"counts": ["0", 3, "500", 1], "gap": 500, "start": 0, "end": 1000}
+            },
+            "facet_intervals": {},
+            "facet_heatmaps": {},
+            "facet_pivot": {
+                "category,in_stock": [
+                    {
+                        "field": "category",
+                        "value": "electronics",
+                        "count": 4,
+                        "pivot": [
+                            {"field": "in_stock", "value": true, "count": 3}
+                        ],
+                        "stats": {
+                            "stats_fields": {
+                                "price": {
+                                    "min": 10.0, "max": 600.0, "count": 4, "missing": 0,
+                                    "sum": 900.0, "sumOfSquares": 370000.0, "mean": 225.0, "stddev": 270.8
+                                }
+                            }
+                        },
+                        "ranges": {
+                            "price": {"counts": ["0", 3, "500", 1], "gap": 500, "start": 0, "end": 1000}
+                        }
+                    }
+                ]
+            }
+        }
+        "#;
+        let facet_counts: SolrFacetBody = serde_json::from_str(raw).unwrap();
+
+        let node = &facet_counts.facet_pivot["category,in_stock"][0];
+        assert_eq!(node.value, Value::from("electronics"));
+        assert_eq!(node.count, 4);
+        assert_eq!(node.pivot[0].value, Value::Bool(true));
+        assert!(node.pivot[0].stats.is_none());
+        assert_eq!(node.field_stats("price").unwrap().sum, Some(900.0));
+        match &node.ranges["price"] {
+            SolrRangeFacetKind::Integer(range) => {
+                assert_eq!(
+                    range.counts,
+                    vec![(String::from("0"), 3), (String::from("500"), 1)]
+                )
+            }
+            _ => panic!("expected integer range facet"),
+        }
+    }
+
+    #[test]
+    fn test_typed_facet_field() {
+        #[derive(FacetValue, Debug, PartialEq)]
+        enum Category {
+            #[facet(value = "ABC")]
+            Abc,
+            #[facet(value = "ARC")]
+            Arc,
+        }
+
+        let raw = r#"
+        {
+            "facet_queries": {},
+            "facet_fields": {
+                "category": ["ABC", 400, "ARC", 123],
+                "difficulty": ["800", 56, "Unknown", 1]
+            },
+            "facet_ranges": {},
+            "facet_intervals": {},
+            "facet_heatmaps": {}
+        }
+        "#;
+
+        let facet: SolrFacetBody = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            facet.typed_facet_field::<Category>("category").unwrap(),
+            vec![(Category::Abc, 400), (Category::Arc, 123)]
+        );
+        assert_eq!(
+            facet.typed_facet_field::<u32>("difficulty"),
+            Err(FacetValueError::UnknownValueError {
+                field: String::from("difficulty"),
+                value: String::from("Unknown")
+            })
+        );
+        assert_eq!(
+            facet.typed_facet_field::<String>("tags"),
+            Err(FacetValueError::FieldNotFoundError(String::from("tags")))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_datetime_range_facet_with_other_counts() {
+        let raw = r#"
+        {
+            "counts": ["2021-10-10T00:00:00Z", 19],
+            "gap": "+2MONTHS",
+            "before": 5000000000,
+            "after": 0,
+            "between": 19,
+            "start": "2021-10-10T00:00:00Z",
+            "end": "2021-12-10T00:00:00Z"
+        }
+        "#;
+
+        let facet: SolrDateTimeRangeFacet = serde_json::from_str(raw).unwrap();
+        assert_eq!(facet.before, Some(5000000000));
+        assert_eq!(facet.between, Some(19));
+    }
+
+    #[test]
+    fn test_deserialize_large_num_found() {
+        let raw = r#"
+        {
+            "numFound": 5000000000,
+            "start": 4999999990,
+            "numFoundExact": true,
+            "docs": []
+        }
+        "#;
+
+        let body: SolrSelectBody<Document> = serde_json::from_str(raw).unwrap();
+        assert_eq!(body.num_found, 5000000000);
+        assert_eq!(body.start, 4999999990);
+    }
+
     #[test]
     fn test_deserialize_select_response() {
         let raw = r#"
@@ -790,4 +2260,591 @@ mod test {
         let select: SolrSelectResponse<Document> = serde_json::from_str(raw).unwrap();
         assert_eq!(select.response.num_found, 0);
     }
+
+    #[test]
+    fn test_deserialize_debug_info() {
+        let raw = r#"
+        {
+            "rawquerystring": "name:alice",
+            "querystring": "name:alice",
+            "parsedquery": "name:alice",
+            "parsedquery_toString": "name:alice",
+            "explain": {
+                "001": {
+                    "match": true,
+                    "value": 0.9808291,
+                    "description": "weight(name:alice in 0) [SchemaSimilarity], result of:",
+                    "details": []
+                }
+            },
+            "QParser": "LuceneQParser",
+            "timing": {
+                "time": 2.0,
+                "prepare": {
+                    "time": 0.0,
+                    "query": {"time": 0.0},
+                    "facet": {"time": 0.0},
+                    "debug": {"time": 0.0}
+                },
+                "process": {
+                    "time": 1.0,
+                    "query": {"time": 1.0},
+                    "facet": {"time": 0.0},
+                    "debug": {"time": 0.0}
+                }
+            }
+        }
+        "#;
+
+        let debug: SolrDebugInfo = serde_json::from_str(raw).unwrap();
+        assert_eq!(debug.qparser, Some("LuceneQParser".to_string()));
+        assert!(matches!(
+            debug.explain.unwrap().get("001"),
+            Some(SolrExplainKind::Structured(_))
+        ));
+
+        let timing = debug.timing.unwrap();
+        assert_eq!(timing.time, 2.0);
+        assert_eq!(timing.process.components["query"].time, 1.0);
+    }
+
+    #[test]
+    fn test_deserialize_debug_info_with_explain_other() {
+        let raw = r#"
+        {
+            "rawquerystring": "name:alice",
+            "querystring": "name:alice",
+            "parsedquery": "name:alice",
+            "parsedquery_toString": "name:alice",
+            "otherQuery": "id:002",
+            "explain": {},
+            "explainOther": {
+                "002": {
+                    "match": false,
+                    "value": 0.0,
+                    "description": "no matching term",
+                    "details": []
+                }
+            },
+            "QParser": "LuceneQParser"
+        }
+        "#;
+
+        let debug: SolrDebugInfo = serde_json::from_str(raw).unwrap();
+        assert_eq!(debug.other_query, Some("id:002".to_string()));
+        match debug.explain_other.unwrap().get("002") {
+            Some(SolrExplainKind::Structured(node)) => assert!(!node.matched),
+            _ => panic!("structured explanation of 002 is expected"),
+        }
+    }
+
+    #[test]
+    fn test_explain_node() {
+        let raw = r#"
+        {
+            "match": true,
+            "value": 3.0,
+            "description": "sum of:",
+            "details": [
+                {
+                    "match": true,
+                    "value": 1.0,
+                    "description": "weight(name:alice in 0)"
+                },
+                {
+                    "match": true,
+                    "value": 2.0,
+                    "description": "weight(name:bob in 0)",
+                    "details": [
+                        {
+                            "match": true,
+                            "value": 2.0,
+                            "description": "idf"
+                        }
+                    ]
+                }
+            ]
+        }
+        "#;
+
+        let node: ExplainNode = serde_json::from_str(raw).unwrap();
+        assert!(node.matched);
+        assert_eq!(
+            node.max_contributor().unwrap().description,
+            "weight(name:bob in 0)".to_string()
+        );
+        assert_eq!(
+            node.leaves()
+                .iter()
+                .map(|leaf| leaf.description.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["weight(name:alice in 0)", "idf"]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_plain_explain() {
+        let raw = r#"{"001": "0.9808291 = weight(name:alice in 0)"}"#;
+
+        let explain: HashMap<String, SolrExplainKind> = serde_json::from_str(raw).unwrap();
+        assert!(matches!(explain["001"], SolrExplainKind::Plain(_)));
+    }
+
+    #[test]
+    fn test_next_cursor_mark() {
+        let raw = r#"
+        {
+            "responseHeader": {
+                "status": 0,
+                "QTime": 3
+            },
+            "response": {
+                "numFound": 0,
+                "start": 0,
+                "numFoundExact": true,
+                "docs": []
+            },
+            "nextCursorMark": "AoEjR0JQ"
+        }
+        "#;
+        let select: SolrSelectResponse<Document> = serde_json::from_str(raw).unwrap();
+        assert_eq!(select.next_cursor_mark, Some("AoEjR0JQ".to_string()));
+        assert!(select.has_more("*"));
+        assert!(!select.has_more("AoEjR0JQ"));
+    }
+
+    #[test]
+    fn test_extra_sections() {
+        #[derive(Deserialize)]
+        struct Custom {
+            name: String,
+        }
+
+        let raw = r#"
+        {
+            "responseHeader": {
+                "status": 0,
+                "QTime": 3
+            },
+            "response": {
+                "numFound": 0,
+                "start": 0,
+                "numFoundExact": true,
+                "docs": []
+            },
+            "custom": {
+                "name": "alice"
+            }
+        }
+        "#;
+        let select: SolrSelectResponse<Document> = serde_json::from_str(raw).unwrap();
+        assert_eq!(select.extra.len(), 1);
+
+        let custom: Custom = select.section("custom").unwrap().unwrap();
+        assert_eq!(custom.name, "alice".to_string());
+        assert!(select.section::<Custom>("unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_spellcheck() {
+        let raw = r#"
+        {
+            "responseHeader": {
+                "status": 0,
+                "QTime": 3
+            },
+            "response": {
+                "numFound": 0,
+                "start": 0,
+                "numFoundExact": true,
+                "docs": []
+            },
+            "spellcheck": {
+                "suggestions": [
+                    "delll", {"numFound": 1, "startOffset": 0, "endOffset": 5, "suggestion": ["dell"]}
+                ],
+                "correctlySpelled": false,
+                "collations": [
+                    "collation", {
+                        "collationQuery": "dell ultrasharp",
+                        "hits": 2,
+                        "misspellingsAndCorrections": ["delll", "dell", "ultrashar", "ultrasharp"]
+                    },
+                    "collation", {
+                        "collationQuery": "dell ultrasharpe",
+                        "hits": 0,
+                        "misspellingsAndCorrections": ["delll", "dell"]
+                    }
+                ]
+            }
+        }
+        "#;
+        let select: SolrSelectResponse<Document> = serde_json::from_str(raw).unwrap();
+        let spellcheck = select.spellcheck().unwrap().unwrap();
+
+        assert_eq!(spellcheck.correctly_spelled, Some(false));
+        assert_eq!(spellcheck.collations.len(), 2);
+        assert_eq!(
+            spellcheck.collations[0].misspellings_and_corrections,
+            vec![
+                (String::from("delll"), String::from("dell")),
+                (String::from("ultrashar"), String::from("ultrasharp")),
+            ]
+        );
+        assert_eq!(
+            spellcheck.best_collation(1).unwrap().collation_query,
+            "dell ultrasharp"
+        );
+        assert!(spellcheck.best_collation(3).is_none());
+    }
+
+    #[test]
+    fn test_deserialize_simple_collations() {
+        let spellcheck: SolrSpellcheck = serde_json::from_str(
+            r#"{"suggestions": [], "collations": ["collation", "dell ultrasharp"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            spellcheck.collations,
+            vec![SolrCollation {
+                collation_query: String::from("dell ultrasharp"),
+                hits: None,
+                misspellings_and_corrections: Vec::new(),
+            }]
+        );
+        assert_eq!(spellcheck.correctly_spelled, None);
+    }
+
+    #[test]
+    fn test_deserialize_json_facet_response() {
+        let raw = r#"
+        {
+            "count": 5650,
+            "avg_difficulty": 1024.5,
+            "categories": {
+                "numBuckets": 2,
+                "allBuckets": {
+                    "count": 523
+                },
+                "buckets": [
+                    {
+                        "val": "ABC",
+                        "count": 400,
+                        "max_difficulty": 3500,
+                        "contests": {
+                            "buckets": [
+                                {
+                                    "val": "abc001",
+                                    "count": 4
+                                }
+                            ]
+                        }
+                    },
+                    {
+                        "val": "ARC",
+                        "count": 123,
+                        "max_difficulty": 3800
+                    }
+                ],
+                "missing": {
+                    "count": 0
+                }
+            }
+        }
+        "#;
+
+        let facets: SolrJsonFacetResponse = serde_json::from_str(raw).unwrap();
+        assert_eq!(facets.count, 5650);
+        assert_eq!(facets.stat::<f64>("avg_difficulty").unwrap(), Some(1024.5));
+
+        let categories = facets.buckets("categories").unwrap().unwrap();
+        assert_eq!(categories.num_buckets, Some(2));
+        assert_eq!(categories.all_buckets.unwrap().count, 523);
+        assert_eq!(categories.missing.unwrap().count, 0);
+
+        let abc = &categories.buckets[0];
+        assert_eq!(abc.val::<String>().unwrap(), Some("ABC".to_string()));
+        assert_eq!(abc.stat::<u64>("max_difficulty").unwrap(), Some(3500));
+
+        let contests = abc.buckets("contests").unwrap().unwrap();
+        assert_eq!(contests.buckets[0].count, 4);
+        assert!(facets.buckets("unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_json_facet_stats() {
+        let raw = r#"
+        {
+            "count": 10,
+            "brands": 4,
+            "latency": [12.5, 80, 140.25],
+            "median": 30.0,
+            "first": "2023-01-01T00:00:00Z",
+            "categories": {
+                "buckets": [
+                    {"val": "book", "count": 6, "avg_price": 12.5, "max_price": 40}
+                ]
+            }
+        }
+        "#;
+        let facets: SolrJsonFacetResponse = serde_json::from_str(raw).unwrap();
+
+        let brands: SolrJsonFacetStat = facets.stat("brands").unwrap().unwrap();
+        assert_eq!(brands.as_i64(), Some(4));
+        let latency: SolrJsonFacetStat = facets.stat("latency").unwrap().unwrap();
+        assert_eq!(latency.as_percentiles(), Some(vec![12.5, 80.0, 140.25]));
+        let median: SolrJsonFacetStat = facets.stat("median").unwrap().unwrap();
+        assert_eq!(median.as_percentiles(), Some(vec![30.0]));
+        let first: SolrJsonFacetStat = facets.stat("first").unwrap().unwrap();
+        assert_eq!(first.as_str(), Some("2023-01-01T00:00:00Z"));
+
+        let bucket = &facets.buckets("categories").unwrap().unwrap().buckets[0];
+        let avg_price: SolrJsonFacetStat = bucket.stat("avg_price").unwrap().unwrap();
+        assert_eq!(avg_price, SolrJsonFacetStat::Float(12.5));
+        let max_price: SolrJsonFacetStat = bucket.stat("max_price").unwrap().unwrap();
+        assert_eq!(max_price.as_f64(), Some(40.0));
+    }
+
+    #[test]
+    fn test_deserialize_relatedness() {
+        let raw = r#"
+        {
+            "count": 16,
+            "keywords": {
+                "buckets": [
+                    {
+                        "val": "async",
+                        "count": 5,
+                        "r": {
+                            "relatedness": 0.38753,
+                            "foreground_popularity": 0.0625,
+                            "background_popularity": 0.09375
+                        }
+                    }
+                ]
+            }
+        }
+        "#;
+        let facets: SolrJsonFacetResponse = serde_json::from_str(raw).unwrap();
+
+        let bucket = &facets.buckets("keywords").unwrap().unwrap().buckets[0];
+        let r: SolrRelatedness = bucket.stat("r").unwrap().unwrap();
+        assert_eq!(r.relatedness, 0.38753);
+        assert_eq!(r.foreground_popularity, Some(0.0625));
+    }
+
+    #[test]
+    fn test_deserialize_document_analysis() {
+        let raw = r#"
+        {
+            "responseHeader": {"status": 0, "QTime": 2},
+            "analysis": {
+                "001": {
+                    "title": {
+                        "query": [
+                            "org.apache.lucene.analysis.standard.StandardTokenizer",
+                            [{"text": "rust", "start": 0, "end": 4, "position": 1, "type": "<ALPHANUM>"}]
+                        ],
+                        "index": {
+                            "Rust &amp; Solr": [
+                                "org.apache.solr.analysis.HTMLStripCharFilter",
+                                "Rust & Solr",
+                                "org.apache.lucene.analysis.standard.StandardTokenizer",
+                                [
+                                    {"text": "Rust", "start": 0, "end": 4, "position": 1, "type": "<ALPHANUM>"},
+                                    {"text": "Solr", "start": 11, "end": 15, "position": 2, "type": "<ALPHANUM>"}
+                                ],
+                                "org.apache.lucene.analysis.core.LowerCaseFilter",
+                                [
+                                    {"text": "rust", "start": 0, "end": 4, "position": 1, "type": "<ALPHANUM>", "match": true},
+                                    {"text": "solr", "start": 11, "end": 15, "position": 2, "type": "<ALPHANUM>"}
+                                ]
+                            ]
+                        }
+                    }
+                }
+            }
+        }
+        "#;
+        let response: SolrDocumentAnalysisResponse = serde_json::from_str(raw).unwrap();
+
+        let title = &response.analysis["001"]["title"];
+        assert_eq!(title.query.len(), 1);
+        let stages = &title.index["Rust &amp; Solr"];
+        assert_eq!(stages.len(), 3);
+        assert_eq!(stages[0].text.as_deref(), Some("Rust & Solr"));
+        assert!(stages[0].tokens.is_empty());
+        let last = stages.last().unwrap();
+        assert_eq!(last.name, "org.apache.lucene.analysis.core.LowerCaseFilter");
+        assert!(last.tokens[0].matched);
+        assert!(!last.tokens[1].matched);
+        assert_eq!(last.tokens[1].token_type.as_deref(), Some("<ALPHANUM>"));
+    }
+
+    #[test]
+    fn test_deserialize_select_response_without_header() {
+        let raw = r#"
+        {
+            "response": {
+                "numFound": 0,
+                "start": 0,
+                "numFoundExact": true,
+                "docs": []
+            }
+        }
+        "#;
+        let select: SolrSelectResponse<Document> = serde_json::from_str(raw).unwrap();
+        assert_eq!(select.header.status, 0);
+        assert!(select.header.params.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_cluster_status() {
+        let raw = r#"
+        {
+            "responseHeader": {"status": 0, "QTime": 3},
+            "cluster": {
+                "collections": {
+                    "example": {
+                        "pullReplicas": "0",
+                        "replicationFactor": "2",
+                        "shards": {
+                            "shard1": {
+                                "range": "80000000-ffffffff",
+                                "state": "active",
+                                "replicas": {
+                                    "core_node3": {
+                                        "core": "example_shard1_replica_n1",
+                                        "base_url": "http://127.0.0.1:8983/solr",
+                                        "node_name": "127.0.0.1:8983_solr",
+                                        "state": "active",
+                                        "type": "NRT",
+                                        "force_set_state": "false",
+                                        "leader": "true"
+                                    },
+                                    "core_node5": {
+                                        "core": "example_shard1_replica_n2",
+                                        "base_url": "http://127.0.0.1:7574/solr",
+                                        "node_name": "127.0.0.1:7574_solr",
+                                        "state": "down",
+                                        "type": "NRT",
+                                        "force_set_state": "false"
+                                    }
+                                },
+                                "health": "YELLOW"
+                            }
+                        },
+                        "router": {"name": "compositeId"},
+                        "nrtReplicas": "2",
+                        "tlogReplicas": "0",
+                        "health": "YELLOW",
+                        "znodeVersion": 11,
+                        "configName": "_default"
+                    }
+                },
+                "live_nodes": ["127.0.0.1:8983_solr"]
+            }
+        }
+        "#;
+
+        let status: SolrClusterStatus = serde_json::from_str(raw).unwrap();
+        let cluster = status.cluster.unwrap();
+        let example = &cluster.collections["example"];
+
+        assert_eq!(
+            cluster.live_nodes,
+            vec![String::from("127.0.0.1:8983_solr")]
+        );
+        assert_eq!(example.config_name, Some(String::from("_default")));
+        assert_eq!(example.router.as_ref().unwrap().name, "compositeId");
+        assert_eq!(example.replicas().len(), 2);
+
+        let leader = example.leader("shard1").unwrap();
+        assert_eq!(leader.core, "example_shard1_replica_n1");
+        assert_eq!(
+            leader.core_url(),
+            "http://127.0.0.1:8983/solr/example_shard1_replica_n1"
+        );
+        assert_eq!(leader.replica_type, "NRT");
+        assert_eq!(example.shards["shard1"].active_replicas().len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_metrics() {
+        let raw = r#"
+        {
+            "responseHeader": {"status": 0, "QTime": 5},
+            "metrics": {
+                "solr.jvm": {
+                    "memory.heap.used": 123456789,
+                    "system.properties": {"java.version": "17"}
+                },
+                "solr.core.example": {
+                    "CACHE.searcher.queryResultCache": {
+                        "lookups": 10, "hits": 4, "hitratio": 0.4, "inserts": 6, "evictions": 0, "size": 6,
+                        "cumulative_lookups": 10, "cumulative_hits": 4, "cumulative_hitratio": 0.4
+                    },
+                    "QUERY./select.requestTimes": {
+                        "count": 10, "meanRate": 0.1, "1minRate": 0.2, "5minRate": 0.1, "15minRate": 0.05,
+                        "min_ms": 1.0, "max_ms": 20.0, "mean_ms": 5.5, "median_ms": 4.0, "stddev_ms": 2.0,
+                        "p75_ms": 6.0, "p95_ms": 15.0, "p99_ms": 19.0, "p999_ms": 20.0
+                    },
+                    "QUERY./select.errors": {"count": 1, "meanRate": 0.01, "1minRate": 0.0, "5minRate": 0.0, "15minRate": 0.0},
+                    "CORE.coreName": "example"
+                }
+            }
+        }
+        "#;
+
+        let metrics: SolrMetricsResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(
+            metrics
+                .get("solr.jvm", "memory.heap.used")
+                .unwrap()
+                .as_f64(),
+            Some(123456789.0)
+        );
+        assert!(matches!(
+            metrics.get("solr.jvm", "system.properties"),
+            Some(SolrMetric::Other(_))
+        ));
+        match metrics.get("solr.core.example", "CACHE.searcher.queryResultCache") {
+            Some(SolrMetric::Cache(cache)) => assert_eq!(cache.hitratio, 0.4),
+            other => panic!("unexpected metric: {:?}", other),
+        }
+        match metrics.get("solr.core.example", "QUERY./select.requestTimes") {
+            Some(SolrMetric::Timer(timer)) => assert_eq!(timer.p95_ms, Some(15.0)),
+            other => panic!("unexpected metric: {:?}", other),
+        }
+        assert!(matches!(
+            metrics.get("solr.core.example", "QUERY./select.errors"),
+            Some(SolrMetric::Meter(_))
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_logging_response() {
+        let raw = r#"
+        {
+            "responseHeader": {"status": 0, "QTime": 1},
+            "watcher": "Log4j2 (org.apache.logging.slf4j.Log4jLoggerFactory)",
+            "levels": ["ALL", "TRACE", "DEBUG", "INFO", "WARN", "ERROR", "FATAL", "OFF"],
+            "loggers": [
+                {"name": "root", "level": "WARN", "set": true},
+                {"name": "org.apache.solr.core", "level": null, "set": false}
+            ]
+        }
+        "#;
+
+        let logging: SolrLoggingResponse = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(logging.levels.len(), 8);
+        assert_eq!(
+            logging.logger("root").unwrap().level,
+            Some(String::from("WARN"))
+        );
+        assert!(!logging.logger("org.apache.solr.core").unwrap().set);
+    }
 }
@@ -0,0 +1,295 @@
+//! This module defines the parser of the XML response of Solr (`wt=xml`).
+//!
+//! The XML response is converted into the same JSON structure as `wt=json`, so that it is deserialized into
+//! [`SolrSelectResponse`] and the other models of the response.
+
+use crate::types::response::SolrSelectResponse;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum XmlResponseError {
+    #[error("Failed to read XML response")]
+    XmlError(#[from] quick_xml::Error),
+    #[error("Invalid value `{value}` of element `{tag}`")]
+    InvalidValueError { tag: String, value: String },
+    #[error("XML response has no root element")]
+    EmptyResponseError,
+    #[error("Failed to deserialize XML response")]
+    DeserializeError(#[from] serde_json::Error),
+}
+
+/// Deserialize the XML response of a search request.
+pub fn from_xml_str<T>(xml: &str) -> Result<SolrSelectResponse<T>, XmlResponseError>
+where
+    T: DeserializeOwned,
+{
+    let value = xml_to_value(xml)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Convert the XML response into the JSON value that Solr would respond with `wt=json`.
+pub fn xml_to_value(xml: &str) -> Result<Value, XmlResponseError> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Frame> = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let frame = Frame::start(&e, stack.last())?;
+                stack.push(frame);
+            }
+            Event::Empty(e) => {
+                let frame = Frame::start(&e, stack.last())?;
+                if let Some(value) = close(frame, &mut stack)? {
+                    return Ok(value);
+                }
+            }
+            Event::Text(e) => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.text.push_str(&e.unescape()?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.text.push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            Event::End(_) => {
+                if let Some(frame) = stack.pop() {
+                    if let Some(value) = close(frame, &mut stack)? {
+                        return Ok(value);
+                    }
+                }
+            }
+            Event::Eof => return Err(XmlResponseError::EmptyResponseError),
+            _ => {}
+        }
+    }
+}
+
+/// Kind of the value that the element builds.
+enum Container {
+    Object(Map<String, Value>),
+    Array(Vec<Value>),
+    /// Named list rendered as the flat array of the names and the values, as `json.nl=flat` does.
+    Flat(Vec<Value>),
+    /// `<result>` element whose children are the documents.
+    Result(Map<String, Value>),
+    Scalar,
+}
+
+/// Element being read.
+struct Frame {
+    tag: String,
+    name: Option<String>,
+    container: Container,
+    text: String,
+}
+
+impl Frame {
+    fn start(e: &BytesStart, parent: Option<&Frame>) -> Result<Self, XmlResponseError> {
+        let tag = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        let name = match e.try_get_attribute("name")? {
+            Some(attr) => Some(attr.unescape_value()?.into_owned()),
+            None => None,
+        };
+
+        let container = match tag.as_str() {
+            "response" | "doc" => Container::Object(Map::new()),
+            "arr" => Container::Array(Vec::new()),
+            "lst" => {
+                // The facet counts are named lists, which are rendered as flat arrays in JSON.
+                let flat = name.as_deref() == Some("counts")
+                    || parent.and_then(|parent| parent.name.as_deref()) == Some("facet_fields");
+                if flat {
+                    Container::Flat(Vec::new())
+                } else {
+                    Container::Object(Map::new())
+                }
+            }
+            "result" => {
+                let mut result = Map::new();
+                for attr in e.attributes() {
+                    let attr = attr.map_err(quick_xml::Error::from)?;
+                    let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+                    let value = attr.unescape_value()?;
+                    let value = match key.as_str() {
+                        "numFound" | "start" => parse_scalar("long", &value)?,
+                        "maxScore" => parse_scalar("float", &value)?,
+                        "numFoundExact" => parse_scalar("bool", &value)?,
+                        _ => continue,
+                    };
+                    result.insert(key, value);
+                }
+                result.insert(String::from("docs"), Value::Array(Vec::new()));
+                Container::Result(result)
+            }
+            _ => Container::Scalar,
+        };
+
+        Ok(Self {
+            tag,
+            name,
+            container,
+            text: String::new(),
+        })
+    }
+
+    fn into_value(self) -> Result<Value, XmlResponseError> {
+        match self.container {
+            Container::Object(map) | Container::Result(map) => Ok(Value::Object(map)),
+            Container::Array(values) | Container::Flat(values) => Ok(Value::Array(values)),
+            Container::Scalar => parse_scalar(&self.tag, &self.text),
+        }
+    }
+}
+
+/// Close the element and add its value to the parent. Return the value if the element is the root.
+fn close(frame: Frame, stack: &mut [Frame]) -> Result<Option<Value>, XmlResponseError> {
+    let name = frame.name.clone();
+    let value = frame.into_value()?;
+
+    let parent = match stack.last_mut() {
+        Some(parent) => parent,
+        None => return Ok(Some(value)),
+    };
+    match &mut parent.container {
+        Container::Object(map) => {
+            map.insert(name.unwrap_or_default(), value);
+        }
+        Container::Array(values) => values.push(value),
+        Container::Flat(values) => {
+            values.push(Value::String(name.unwrap_or_default()));
+            values.push(value);
+        }
+        Container::Result(map) => {
+            if let Some(Value::Array(docs)) = map.get_mut("docs") {
+                docs.push(value);
+            }
+        }
+        Container::Scalar => {}
+    }
+
+    Ok(None)
+}
+
+/// Parse the text of the element of the type, e.g. `<int>`.
+fn parse_scalar(tag: &str, text: &str) -> Result<Value, XmlResponseError> {
+    let invalid = || XmlResponseError::InvalidValueError {
+        tag: String::from(tag),
+        value: String::from(text),
+    };
+
+    match tag {
+        "int" | "long" | "short" | "byte" => text
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| invalid()),
+        "float" | "double" => {
+            let value = text.trim().parse::<f64>().map_err(|_| invalid())?;
+            Number::from_f64(value)
+                .map(Value::Number)
+                .ok_or_else(invalid)
+        }
+        "bool" => match text.trim() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(invalid()),
+        },
+        "null" => Ok(Value::Null),
+        _ => Ok(Value::String(String::from(text))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Document {
+        id: String,
+        title: String,
+        tags: Vec<String>,
+        difficulty: i64,
+    }
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<response>
+<lst name="responseHeader">
+  <int name="status">0</int>
+  <int name="QTime">1</int>
+  <lst name="params">
+    <str name="q">*:*</str>
+  </lst>
+</lst>
+<result name="response" numFound="1" start="0" numFoundExact="true">
+  <doc>
+    <str name="id">001</str>
+    <str name="title">A &amp; B</str>
+    <arr name="tags"><str>easy</str><str>math</str></arr>
+    <long name="difficulty">400</long>
+  </doc>
+</result>
+<lst name="facet_counts">
+  <lst name="facet_queries"/>
+  <lst name="facet_fields">
+    <lst name="category">
+      <int name="ABC">400</int>
+      <int name="ARC">123</int>
+    </lst>
+  </lst>
+  <lst name="facet_ranges">
+    <lst name="difficulty">
+      <lst name="counts">
+        <int name="0">210</int>
+        <int name="400">69</int>
+      </lst>
+      <int name="gap">400</int>
+      <int name="start">0</int>
+      <int name="end">800</int>
+    </lst>
+  </lst>
+  <lst name="facet_intervals"/>
+  <lst name="facet_heatmaps"/>
+</lst>
+</response>
+"#;
+
+    #[test]
+    fn test_from_xml_str() {
+        let response: SolrSelectResponse<Document> = from_xml_str(XML).unwrap();
+
+        assert_eq!(response.header.qtime, 1);
+        assert_eq!(response.response.num_found, 1);
+        assert!(response.response.num_found_exact);
+
+        let doc = &response.response.docs[0];
+        assert_eq!(doc.id, "001");
+        assert_eq!(doc.title, "A & B");
+        assert_eq!(doc.tags, vec!["easy", "math"]);
+        assert_eq!(doc.difficulty, 400);
+
+        let facet_counts = response.facet_counts.unwrap();
+        assert_eq!(
+            facet_counts.facet_fields["category"],
+            vec![(String::from("ABC"), 400), (String::from("ARC"), 123)]
+        );
+        assert_eq!(facet_counts.facet_ranges.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        let xml = r#"<response><int name="status">zero</int></response>"#;
+
+        assert!(matches!(
+            xml_to_value(xml),
+            Err(XmlResponseError::InvalidValueError { .. })
+        ));
+    }
+}
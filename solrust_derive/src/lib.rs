@@ -1,6 +1,12 @@
 use proc_macro::TokenStream;
 
-#[proc_macro_derive(SolrCommonQueryParser)]
+/// Derive `SolrCommonQueryBuilder`.
+///
+/// With the struct-level attribute `#[solr_params(defType = "edismax")]`, the `new()` constructor inserting
+/// the given parameters by default is also generated.
+///
+/// `Display` rendering the query string and `Deserialize` loading a preset on top of `new()` are also generated.
+#[proc_macro_derive(SolrCommonQueryParser, attributes(solr_params))]
 pub fn derive_common_query_parser(input: TokenStream) -> TokenStream {
     solrust_derive_internals::impl_common_query_parser(input.into()).into()
 }
@@ -18,3 +24,29 @@ pub fn derive_dismax_query_parser(input: TokenStream) -> TokenStream {
 pub fn derive_edismax_query_parser(input: TokenStream) -> TokenStream {
     solrust_derive_internals::impl_edismax_query_parser(input.into()).into()
 }
+
+/// Derive `SolrDocument`, which lists the names of the fields in Solr.
+///
+/// The names follow `#[serde(rename = "...")]` and `#[serde(rename_all = "...")]`, and the fields with
+/// `#[serde(skip)]` are excluded. The name of each field is also generated as the associated constant, e.g. `Book::TITLE`.
+#[proc_macro_derive(SolrDocument)]
+pub fn derive_solr_document(input: TokenStream) -> TokenStream {
+    solrust_derive_internals::impl_solr_document(input.into()).into()
+}
+
+/// Derive `SolrFilters`, which converts the fields annotated with `#[filter(field = "...", op = "...")]` to `fq` expressions.
+///
+/// The operator is one of `eq` (default), `ne`, `gt`, `ge`, `lt`, `le`, `in` and `phrase`, and the field name defaults to
+/// the name of the struct field. The fields of `Option` are converted only when the value is set.
+#[proc_macro_derive(SolrFilters, attributes(filter))]
+pub fn derive_solr_filters(input: TokenStream) -> TokenStream {
+    solrust_derive_internals::impl_solr_filters(input.into()).into()
+}
+
+/// Derive `FacetValue` for an enum with unit variants, which matches the value of the facet field.
+///
+/// Each variant matches its name, or the value given by `#[facet(value = "...")]`.
+#[proc_macro_derive(FacetValue, attributes(facet))]
+pub fn derive_facet_value(input: TokenStream) -> TokenStream {
+    solrust_derive_internals::impl_facet_value(input.into()).into()
+}
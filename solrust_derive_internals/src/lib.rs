@@ -1,10 +1,91 @@
-use proc_macro2::TokenStream;
-use syn::DeriveInput;
+use proc_macro2::{Ident, Span, TokenStream};
+use syn::{Attribute, Data, DataStruct, DeriveInput, Fields, Lit, Meta, NestedMeta, Type};
+
+/// Parse the struct-level `#[solr_params(key = "value", ...)]` attributes into the list of the default parameters.
+///
+/// Return `None` if the struct has no `solr_params` attribute.
+fn parse_default_params(attrs: &[Attribute]) -> syn::Result<Option<Vec<(String, String)>>> {
+    let mut params: Option<Vec<(String, String)>> = None;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("solr_params"))
+    {
+        let params = params.get_or_insert_with(Vec::new);
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    r#"expected `#[solr_params(key = "value", ...)]`"#,
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(pair)) => {
+                    let key = pair
+                        .path
+                        .get_ident()
+                        .map(|ident| ident.to_string())
+                        .ok_or_else(|| {
+                            syn::Error::new_spanned(&pair.path, "expected a parameter name")
+                        })?;
+                    let value = match &pair.lit {
+                        Lit::Str(value) => value.value(),
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "expected a string literal as the parameter value",
+                            ))
+                        }
+                    };
+                    params.push((key, value));
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        r#"expected `key = "value"`"#,
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(params)
+}
 
 pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input.into()).expect("Failed to parse input TokenStream");
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
     let struct_name = ast.ident;
+
+    // Generate `new()` inserting the default parameters given by `#[solr_params(...)]`.
+    let constructor = match parse_default_params(&ast.attrs) {
+        Ok(Some(params)) => {
+            let keys = params.iter().map(|(key, _)| key);
+            let values = params.iter().map(|(_, value)| value);
+            quote::quote! {
+                impl #struct_name {
+                    pub fn new() -> Self {
+                        let mut params = BTreeMap::new();
+                        #(params.insert(#keys.to_string(), #values.to_string());)*
+
+                        Self {
+                            params,
+                            multi_params: BTreeMap::new(),
+                        }
+                    }
+                }
+            }
+        }
+        Ok(None) => TokenStream::new(),
+        Err(e) => e.to_compile_error(),
+    };
+
     let gen = quote::quote! {
+        #constructor
+
         impl SolrCommonQueryBuilder for #struct_name {
             fn sort(mut self, sort: &SortOrderBuilder) -> Self {
                 self.params.insert("sort".to_string(), sort.build());
@@ -21,6 +102,11 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
+            fn cursor_mark(mut self, cursor_mark: &str) -> Self {
+                self.params.insert("cursorMark".to_string(), cursor_mark.to_string());
+                self
+            }
+
             fn fq(mut self, fq: &impl SolrQueryExpression) -> Self {
                 self.multi_params
                     .entry("fq".to_string())
@@ -29,6 +115,14 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
+            fn filters(mut self, filters: &impl SolrFilters) -> Self {
+                self.multi_params
+                    .entry("fq".to_string())
+                    .or_default()
+                    .extend(filters.filters());
+                self
+            }
+
             fn fq_with_local_params(
                 mut self,
                 fq: &impl SolrQueryExpression,
@@ -50,8 +144,21 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn fl(mut self, fl: String) -> Self {
-                self.params.insert("fl".to_string(), fl);
+            fn fq_with(mut self, fq: &impl SolrQueryExpression, options: FqOptions) -> Self {
+                self.multi_params
+                    .entry("fq".to_string())
+                    .or_default()
+                    .push(options.apply(&fq.to_string()));
+                self
+            }
+
+            fn fl(mut self, fl: &FlBuilder) -> Self {
+                self.params.insert("fl".to_string(), fl.build());
+                self
+            }
+
+            fn fl_for<T: SolrDocument>(mut self) -> Self {
+                self.params.insert("fl".to_string(), T::field_list());
                 self
             }
 
@@ -62,7 +169,17 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn wt(mut self, wt: &str) -> Self {
+            fn explain_other(mut self, q: &impl SolrQueryExpression) -> Self {
+                self.params.insert("explainOther".to_string(), q.to_string());
+                self
+            }
+
+            fn qt(mut self, qt: &str) -> Self {
+                self.params.insert("qt".to_string(), qt.to_string());
+                self
+            }
+
+            fn wt(mut self, wt: ResponseFormat) -> Self {
                 self.params.insert("wt".to_string(), wt.to_string());
                 self
             }
@@ -71,9 +188,9 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 self.params.insert("facet".to_string(), "true".to_string());
                 for (key, value) in facet.build() {
                     // facet.fieldパラメータは複数値を取れるパラメータなので別で処理する
-                    if key == "facet.field".to_string() {
+                    if key == "facet.field" || key == "facet.pivot" || key == "stats.field" {
                         self.multi_params
-                            .entry("facet.field".to_string())
+                            .entry(key)
                             .or_default()
                             .push(value);
                     } else {
@@ -98,11 +215,20 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 );
                 self.params.insert("facet".to_string(), "true".to_string());
                 for (key, value) in facet.build() {
-                    if &key == "facet.field" || &key == "facet.range" {
+                    if &key == "facet.field"
+                        || &key == "facet.range"
+                        || &key == "facet.pivot"
+                        || &key == "stats.field"
+                    {
+                        // Solr reads only the first local parameters, so merge them into those of the facet.
+                        let value = match value.strip_prefix("{!") {
+                            Some(rest) => format!("{} {}", local_params.trim_end_matches('}'), rest),
+                            None => format!("{}{}", local_params, value),
+                        };
                         self.multi_params
                             .entry(key.clone())
                             .or_default()
-                            .push(format!("{}{}", local_params, value));
+                            .push(value);
                     } else {
                         self.params.insert(key, value);
                     }
@@ -111,6 +237,62 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
 
             }
 
+            fn highlight(mut self, highlight: &HighlightBuilder) -> Self {
+                self.params.extend(highlight.build());
+                self
+            }
+
+            fn echo_params(mut self, echo_params: EchoParams) -> Self {
+                self.params.insert("echoParams".to_string(), echo_params.to_string());
+                self
+            }
+
+            fn omit_header(mut self, omit_header: bool) -> Self {
+                self.params.insert("omitHeader".to_string(), omit_header.to_string());
+                self
+            }
+
+            fn query_uuid(mut self, uuid: &str) -> Self {
+                self.params.insert("canCancel".to_string(), "true".to_string());
+                self.params.insert("queryUUID".to_string(), uuid.to_string());
+                self
+            }
+
+            fn time_allowed(mut self, time_allowed: u32) -> Self {
+                self.params.insert("timeAllowed".to_string(), time_allowed.to_string());
+                self
+            }
+
+            fn min_exact_count(mut self, min_exact_count: u32) -> Self {
+                self.params.insert("minExactCount".to_string(), min_exact_count.to_string());
+                self
+            }
+
+            fn shards(mut self, shards: &[&str]) -> Self {
+                self.params.insert("shards".to_string(), shards.join(","));
+                self
+            }
+
+            fn shards_tolerant(mut self, tolerant: bool) -> Self {
+                self.params.insert("shards.tolerant".to_string(), tolerant.to_string());
+                self
+            }
+
+            fn shards_preference(mut self, preference: &str) -> Self {
+                self.params.insert("shards.preference".to_string(), preference.to_string());
+                self
+            }
+
+            fn distrib(mut self, distrib: bool) -> Self {
+                self.params.insert("distrib".to_string(), distrib.to_string());
+                self
+            }
+
+            fn route(mut self, route: &str) -> Self {
+                self.params.insert("_route_".to_string(), route.to_string());
+                self
+            }
+
             fn op(mut self, op: Operator) -> Self {
                 match op {
                     Operator::AND => {
@@ -123,8 +305,8 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn build(self) -> Vec<(String, String)> {
-                let mut params = Vec::new();
+            fn into_params(self) -> SolrQueryParams {
+                let mut params = SolrQueryParams::new();
 
                 params.extend(self.params.into_iter());
                 for (key, values) in self.multi_params.into_iter() {
@@ -134,16 +316,63 @@ pub fn impl_common_query_parser(input: TokenStream) -> TokenStream {
                 params
             }
 
+            fn build(self) -> Vec<(String, String)> {
+                self.into_params().into()
+            }
+
+            fn to_params(&self) -> SolrQueryParams {
+                let mut params = SolrQueryParams::new();
+
+                params.extend(self.params.iter().map(|(key, value)| (key.clone(), value.clone())));
+                for (key, values) in self.multi_params.iter() {
+                    params.extend(values.iter().map(|value| (key.clone(), value.clone())));
+                }
+
+                params
+            }
+
+            fn to_query_string(&self) -> String {
+                self.to_params().to_query_string()
+            }
+
             fn sanitize<'a>(&self, s: &'a str) -> Cow<'a, str> {
                 SOLR_SPECIAL_CHARACTERS.replace_all(s, r"\$0")
             }
         }
+
+        impl Display for #struct_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.to_query_string())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for #struct_name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                #[derive(Deserialize)]
+                struct Preset {
+                    #[serde(default)]
+                    params: BTreeMap<String, String>,
+                    #[serde(default)]
+                    multi_params: BTreeMap<String, Vec<String>>,
+                }
+
+                // Start from `new()` so that the default parameters such as `defType` are kept.
+                let preset = Preset::deserialize(deserializer)?;
+                let mut builder = Self::new();
+                builder.params.extend(preset.params);
+                builder.multi_params.extend(preset.multi_params);
+                Ok(builder)
+            }
+        }
     };
-    gen.into()
+    gen
 }
 
 pub fn impl_standard_query_parser(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input.into()).expect("Failed to parse input TokenStream");
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
     let struct_name = ast.ident;
     let gen = quote::quote! {
         impl SolrStandardQueryBuilder for #struct_name {
@@ -169,21 +398,20 @@ pub fn impl_standard_query_parser(input: TokenStream) -> TokenStream {
         }
     };
 
-    gen.into()
+    gen
 }
 
 pub fn impl_dismax_query_parser(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input.into()).expect("Failed to parse input TokenStream");
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
     let struct_name = ast.ident;
     let gen = quote::quote! {
         impl SolrDisMaxQueryBuilder for #struct_name {
-            fn q(mut self, q: String) -> Self {
-                // TODO: 引数の型を抽象モデルに変更する
-                self.params.insert("q".to_string(), self.sanitize(&q).to_string());
+            fn q(mut self, q: &DisMaxQueryInput) -> Self {
+                self.params.insert("q".to_string(), q.to_string());
                 self
             }
 
-            fn qf(mut self, qf: &str) -> Self {
+            fn qf(mut self, qf: &FieldBoosts) -> Self {
                 self.params.insert("qf".to_string(), qf.to_string());
                 self
             }
@@ -193,7 +421,7 @@ pub fn impl_dismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn pf(mut self, pf: &str) -> Self {
+            fn pf(mut self, pf: &FieldBoosts) -> Self {
                 self.params.insert("pf".to_string(), pf.to_string());
                 self
             }
@@ -203,7 +431,7 @@ pub fn impl_dismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn mm(mut self, mm: &str) -> Self {
+            fn mm(mut self, mm: &Mm) -> Self {
                 self.params.insert("mm".to_string(), mm.to_string());
                 self
             }
@@ -226,20 +454,43 @@ pub fn impl_dismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn bf(mut self, bf: &str) -> Self {
+            fn bf(mut self, bf: impl Into<FunctionQuery>) -> Self {
+                self.multi_params
+                    .entry("bf".to_string())
+                    .or_default()
+                    .push(bf.into().to_string());
+                self
+            }
+
+            fn bqs(mut self, bqs: &[impl SolrQueryExpression]) -> Self {
+                self.multi_params
+                    .entry("bq".to_string())
+                    .or_default()
+                    .extend(bqs.iter().map(|bq| bq.to_string()));
+                self
+            }
+
+            fn bfs(mut self, bfs: &[impl Into<FunctionQuery> + Clone]) -> Self {
                 self.multi_params
                     .entry("bf".to_string())
                     .or_default()
-                    .push(bf.to_string());
+                    .extend(bfs.iter().map(|bf| bf.clone().into().to_string()));
+                self
+            }
+
+            fn match_all_if_empty(mut self) -> Self {
+                if !matches!(self.params.get("q"), Some(q) if !q.trim().is_empty()) {
+                    self.params.insert("q.alt".to_string(), "*:*".to_string());
+                }
                 self
             }
         }
     };
-    gen.into()
+    gen
 }
 
 pub fn impl_edismax_query_parser(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input.into()).expect("Failed to parse input TokenStream");
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
     let struct_name = ast.ident;
     let gen = quote::quote! {
         impl SolrEDisMaxQueryBuilder for #struct_name {
@@ -252,8 +503,24 @@ pub fn impl_edismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn boost(mut self, boost: &str) -> Self {
-                self.params.insert("boost".to_string(), boost.to_string());
+            fn boost(mut self, boost: impl Into<FunctionQuery>) -> Self {
+                self.multi_params
+                    .entry("boost".to_string())
+                    .or_default()
+                    .push(boost.into().to_string());
+                self
+            }
+
+            fn boosts(mut self, boosts: &[impl Into<FunctionQuery> + Clone]) -> Self {
+                self.multi_params
+                    .entry("boost".to_string())
+                    .or_default()
+                    .extend(boosts.iter().map(|boost| boost.clone().into().to_string()));
+                self
+            }
+
+            fn mm_auto_relax(mut self, flag: bool) -> Self {
+                self.params.insert("mm.autoRelax".to_string(), flag.to_string());
                 self
             }
 
@@ -266,7 +533,7 @@ pub fn impl_edismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn pf2(mut self, pf: &str) -> Self {
+            fn pf2(mut self, pf: &FieldBoosts) -> Self {
                 self.params.insert("pf2".to_string(), pf.to_string());
                 self
             }
@@ -276,7 +543,7 @@ pub fn impl_edismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn pf3(mut self, pf: &str) -> Self {
+            fn pf3(mut self, pf: &FieldBoosts) -> Self {
                 self.params.insert("pf3".to_string(), pf.to_string());
                 self
             }
@@ -295,11 +562,371 @@ pub fn impl_edismax_query_parser(input: TokenStream) -> TokenStream {
                 self
             }
 
-            fn uf(mut self, uf: &str) -> Self {
+            fn uf(mut self, uf: &UserFields) -> Self {
                 self.params.insert("uf".to_string(), uf.to_string());
                 self
             }
         }
     };
-    gen.into()
+    gen
+}
+
+/// Return the value of `#[serde(<key> = "...")]` in the attributes.
+fn serde_attr_value(attrs: &[Attribute], key: &str) -> Option<String> {
+    serde_metas(attrs).find_map(|meta| match meta {
+        Meta::NameValue(pair) if pair.path.is_ident(key) => match pair.lit {
+            Lit::Str(value) => Some(value.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Return true if the attributes contain `#[serde(<flag>)]`.
+fn has_serde_flag(attrs: &[Attribute], flag: &str) -> bool {
+    serde_metas(attrs).any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(flag)))
+}
+
+fn serde_metas(attrs: &[Attribute]) -> impl Iterator<Item = Meta> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(meta) => Some(meta),
+            _ => None,
+        })
+}
+
+/// Convert the snake case field name according to `#[serde(rename_all = "...")]`.
+fn rename_field(name: &str, rule: &str) -> String {
+    let pascal_case = || -> String {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    };
+
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => {
+            let pascal = pascal_case();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+pub fn impl_solr_document(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(
+                &ast.ident,
+                "SolrDocument can be derived only for structs with named fields",
+            )
+            .to_compile_error()
+        }
+    };
+    let rename_all = serde_attr_value(&ast.attrs, "rename_all");
+
+    // Pairs of the name of the associated constant and the name of the field in Solr.
+    let names: Vec<(Ident, String)> = fields
+        .iter()
+        .filter(|field| {
+            !has_serde_flag(&field.attrs, "skip")
+                && !has_serde_flag(&field.attrs, "skip_deserializing")
+        })
+        .filter_map(|field| {
+            let ident = field.ident.as_ref()?.to_string();
+            let ident = ident.trim_start_matches("r#");
+            let name =
+                serde_attr_value(&field.attrs, "rename").unwrap_or_else(|| match &rename_all {
+                    Some(rule) => rename_field(ident, rule),
+                    None => ident.to_string(),
+                });
+            Some((Ident::new(&ident.to_uppercase(), Span::call_site()), name))
+        })
+        .collect();
+
+    let consts = names.iter().map(|(ident, name)| {
+        let doc = format!("Name of the `{}` field in Solr.", name);
+        quote::quote! {
+            #[doc = #doc]
+            pub const #ident: &'static str = #name;
+        }
+    });
+    let field_names = names.iter().map(|(_, name)| name);
+
+    let gen = quote::quote! {
+        impl #struct_name {
+            #(#consts)*
+        }
+
+        impl SolrDocument for #struct_name {
+            const FIELDS: &'static [&'static str] = &[#(#field_names),*];
+        }
+    };
+    gen
+}
+
+/// Return true if the type is `Option<T>`.
+fn is_option_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+pub fn impl_solr_filters(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(
+                &ast.ident,
+                "SolrFilters can be derived only for structs with named fields",
+            )
+            .to_compile_error()
+        }
+    };
+
+    let mut filters = Vec::new();
+    for field in fields {
+        let attr = match field.attrs.iter().find(|attr| attr.path.is_ident("filter")) {
+            Some(attr) => attr,
+            None => continue,
+        };
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("named field has the identifier");
+
+        let mut name = ident.to_string().trim_start_matches("r#").to_string();
+        let mut op = String::from("eq");
+        match attr.parse_meta() {
+            Ok(Meta::Path(_)) => {}
+            Ok(Meta::List(list)) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::NameValue(pair))
+                            if pair.path.is_ident("field") || pair.path.is_ident("op") =>
+                        {
+                            let value = match &pair.lit {
+                                Lit::Str(value) => value.value(),
+                                lit => {
+                                    return syn::Error::new_spanned(
+                                        lit,
+                                        "expected a string literal",
+                                    )
+                                    .to_compile_error()
+                                }
+                            };
+                            if pair.path.is_ident("field") {
+                                name = value;
+                            } else {
+                                op = value;
+                            }
+                        }
+                        nested => {
+                            return syn::Error::new_spanned(
+                                nested,
+                                r#"expected `field = "..."` or `op = "..."`"#,
+                            )
+                            .to_compile_error()
+                        }
+                    }
+                }
+            }
+            Ok(meta) => {
+                return syn::Error::new_spanned(
+                    meta,
+                    r#"expected `#[filter(field = "...", op = "...")]`"#,
+                )
+                .to_compile_error()
+            }
+            Err(e) => return e.to_compile_error(),
+        }
+
+        let variant = match op.as_str() {
+            "eq" => "Eq",
+            "ne" => "Ne",
+            "gt" => "Gt",
+            "ge" => "Ge",
+            "lt" => "Lt",
+            "le" => "Le",
+            "in" => "In",
+            "phrase" => "Phrase",
+            _ => {
+                return syn::Error::new_spanned(
+                    attr,
+                    "unknown operator, expected one of eq, ne, gt, ge, lt, le, in and phrase",
+                )
+                .to_compile_error()
+            }
+        };
+        let variant = Ident::new(variant, Span::call_site());
+
+        // Only the `in` operator takes multiple values from the collection.
+        let values = if op == "in" {
+            quote::quote! {
+                value.into_iter().map(|value| value.to_string()).collect::<Vec<String>>()
+            }
+        } else {
+            quote::quote! { [value.to_string()] }
+        };
+        let is_option = is_option_type(&field.ty);
+
+        let filter = if is_option {
+            quote::quote! {
+                if let Some(value) = &self.#ident {
+                    if let Some(filter) = FilterOp::#variant.expression(#name, &#values) {
+                        filters.push(filter);
+                    }
+                }
+            }
+        } else {
+            quote::quote! {
+                let value = &self.#ident;
+                if let Some(filter) = FilterOp::#variant.expression(#name, &#values) {
+                    filters.push(filter);
+                }
+            }
+        };
+        filters.push(filter);
+    }
+
+    let gen = quote::quote! {
+        impl SolrFilters for #struct_name {
+            fn filters(&self) -> Vec<String> {
+                let mut filters = Vec::new();
+                #(#filters)*
+                filters
+            }
+        }
+    };
+    gen
+}
+
+pub fn impl_facet_value(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse2(input).expect("Failed to parse input TokenStream");
+    let enum_name = &ast.ident;
+
+    let variants = match &ast.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return syn::Error::new_spanned(&ast.ident, "FacetValue can be derived only for enums")
+                .to_compile_error()
+        }
+    };
+
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return syn::Error::new_spanned(
+                variant,
+                "FacetValue can be derived only for unit variants",
+            )
+            .to_compile_error();
+        }
+
+        let mut value = variant.ident.to_string();
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("facet"))
+        {
+            match attr.parse_meta() {
+                Ok(Meta::List(list)) => {
+                    for nested in list.nested {
+                        match nested {
+                            NestedMeta::Meta(Meta::NameValue(pair))
+                                if pair.path.is_ident("value") =>
+                            {
+                                match pair.lit {
+                                    Lit::Str(lit) => value = lit.value(),
+                                    lit => {
+                                        return syn::Error::new_spanned(
+                                            lit,
+                                            "expected a string literal",
+                                        )
+                                        .to_compile_error()
+                                    }
+                                }
+                            }
+                            nested => {
+                                return syn::Error::new_spanned(
+                                    nested,
+                                    r#"expected `value = "..."`"#,
+                                )
+                                .to_compile_error()
+                            }
+                        }
+                    }
+                }
+                Ok(meta) => {
+                    return syn::Error::new_spanned(meta, r#"expected `#[facet(value = "...")]`"#)
+                        .to_compile_error()
+                }
+                Err(e) => return e.to_compile_error(),
+            }
+        }
+
+        idents.push(&variant.ident);
+        values.push(value);
+    }
+
+    let gen = quote::quote! {
+        impl #enum_name {
+            /// Return the value of the facet field corresponding to this variant.
+            pub fn as_facet_value(&self) -> &'static str {
+                match self {
+                    #(#enum_name::#idents => #values,)*
+                }
+            }
+        }
+
+        impl FacetValue for #enum_name {
+            fn from_facet_value(value: &str) -> Option<Self> {
+                match value {
+                    #(#values => Some(#enum_name::#idents),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+    gen
 }